@@ -3,7 +3,7 @@ extern crate tiledb_api as tiledb;
 use std::path::PathBuf;
 
 use tiledb::config::Config;
-use tiledb::query::{QueryBuilder, ReadQuery, ReadQueryBuilder};
+use tiledb::query::{Query, QueryBuilder, ReadQuery, ReadQueryBuilder};
 use tiledb::vfs::VFS;
 use tiledb::Datatype;
 use tiledb::{Array, Result as TileDBResult};
@@ -155,6 +155,13 @@ pub fn read_array(json: bool) -> TileDBResult<()> {
             Some(stats_json) => print_metrics(&stats_json),
             None => println!("No stats associated with this query."),
         }
+
+        // stats can also be retrieved for just this query,
+        // which is useful when other queries run concurrently
+        match query.stats()? {
+            Some(metrics) => print_metrics(std::slice::from_ref(&metrics)),
+            None => println!("No stats associated with this query."),
+        }
     } else {
         let stats = tiledb::stats::dump()?;
         match stats {
@@ -21,6 +21,10 @@ pub(crate) enum RawAttribute {
     Owned(*mut ffi::tiledb_attribute_t),
 }
 
+// SAFETY: see "Thread safety of handles" in the crate root.
+unsafe impl Send for RawAttribute {}
+unsafe impl Sync for RawAttribute {}
+
 impl Deref for RawAttribute {
     type Target = *mut ffi::tiledb_attribute_t;
     fn deref(&self) -> &Self::Target {
@@ -0,0 +1,280 @@
+use std::ops::Deref;
+
+use crate::array::Array;
+use crate::config::Config;
+use crate::context::{CApiInterface, Context, ContextBound};
+use crate::error::Error;
+use crate::Result as TileDBResult;
+
+pub(crate) enum RawConsolidationPlan {
+    Owned(*mut ffi::tiledb_consolidation_plan_t),
+}
+
+impl Deref for RawConsolidationPlan {
+    type Target = *mut ffi::tiledb_consolidation_plan_t;
+    fn deref(&self) -> &Self::Target {
+        let RawConsolidationPlan::Owned(ref ffi) = *self;
+        ffi
+    }
+}
+
+impl Drop for RawConsolidationPlan {
+    fn drop(&mut self) {
+        let RawConsolidationPlan::Owned(ref mut ffi) = *self;
+        unsafe {
+            ffi::tiledb_consolidation_plan_free(ffi);
+        }
+    }
+}
+
+/// A group of fragments which a [ConsolidationPlan] would consolidate
+/// into a single fragment.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ConsolidationPlanNode {
+    /// URIs of the fragments in this node.
+    pub fragment_uris: Vec<String>,
+}
+
+impl ConsolidationPlanNode {
+    /// Returns the names of the fragments in this node,
+    /// i.e. the last component of each fragment URI.
+    pub fn fragment_names(&self) -> Vec<&str> {
+        self.fragment_uris
+            .iter()
+            .map(|uri| {
+                uri.trim_end_matches('/').rsplit('/').next().unwrap_or(uri)
+            })
+            .collect()
+    }
+}
+
+/// Describes how TileDB would consolidate the fragments of an array
+/// to produce fragments of approximately a desired size.
+///
+/// The plan is divided into nodes, each of which is a set of fragments
+/// which would be consolidated together. The nodes are independent
+/// of each other and can be consolidated separately using
+/// [Self::consolidate_node].
+pub struct ConsolidationPlan {
+    context: Context,
+    raw: RawConsolidationPlan,
+    array_uri: String,
+}
+
+impl ContextBound for ConsolidationPlan {
+    fn context(&self) -> Context {
+        self.context.clone()
+    }
+}
+
+impl ConsolidationPlan {
+    /// Computes the consolidation plan for `array`, which must be open
+    /// for reading, which would produce fragments of approximately
+    /// `fragment_size` bytes.
+    pub fn new(array: &Array, fragment_size: u64) -> TileDBResult<Self> {
+        let context = array.context();
+        let c_array = **array.capi();
+        let mut c_plan: *mut ffi::tiledb_consolidation_plan_t = out_ptr!();
+        context.capi_call(|ctx| unsafe {
+            ffi::tiledb_consolidation_plan_create_with_mbr(
+                ctx,
+                c_array,
+                fragment_size,
+                &mut c_plan,
+            )
+        })?;
+
+        Ok(ConsolidationPlan {
+            context,
+            raw: RawConsolidationPlan::Owned(c_plan),
+            array_uri: array.uri().to_owned(),
+        })
+    }
+
+    pub(crate) fn capi(&self) -> *mut ffi::tiledb_consolidation_plan_t {
+        *self.raw
+    }
+
+    /// Returns the URI of the array which this plan is for.
+    pub fn array_uri(&self) -> &str {
+        &self.array_uri
+    }
+
+    /// Returns the number of nodes in this plan.
+    pub fn num_nodes(&self) -> TileDBResult<usize> {
+        let c_plan = self.capi();
+        let mut c_num_nodes: u64 = out_ptr!();
+        self.capi_call(|ctx| unsafe {
+            ffi::tiledb_consolidation_plan_get_num_nodes(
+                ctx,
+                c_plan,
+                &mut c_num_nodes,
+            )
+        })?;
+        Ok(c_num_nodes as usize)
+    }
+
+    /// Returns the number of fragments in the node at index `node`.
+    pub fn num_fragments(&self, node: usize) -> TileDBResult<usize> {
+        let c_plan = self.capi();
+        let mut c_num_fragments: u64 = out_ptr!();
+        self.capi_call(|ctx| unsafe {
+            ffi::tiledb_consolidation_plan_get_num_fragments(
+                ctx,
+                c_plan,
+                node as u64,
+                &mut c_num_fragments,
+            )
+        })?;
+        Ok(c_num_fragments as usize)
+    }
+
+    /// Returns the URI of fragment `fragment` in the node at index `node`.
+    pub fn fragment_uri(
+        &self,
+        node: usize,
+        fragment: usize,
+    ) -> TileDBResult<String> {
+        let c_plan = self.capi();
+        let mut c_uri: *const std::ffi::c_char = out_ptr!();
+        self.capi_call(|ctx| unsafe {
+            ffi::tiledb_consolidation_plan_get_fragment_uri(
+                ctx,
+                c_plan,
+                node as u64,
+                fragment as u64,
+                &mut c_uri,
+            )
+        })?;
+
+        // N.B. This API only lends a pointer to an internally managed
+        // std::string, thus we do *not* want to free it.
+        let uri = unsafe { std::ffi::CStr::from_ptr(c_uri) };
+        Ok(String::from(uri.to_string_lossy()))
+    }
+
+    /// Returns the node at index `node`.
+    pub fn node(&self, node: usize) -> TileDBResult<ConsolidationPlanNode> {
+        let num_nodes = self.num_nodes()?;
+        if node >= num_nodes {
+            return Err(Error::InvalidIndex(node));
+        }
+        Ok(ConsolidationPlanNode {
+            fragment_uris: (0..self.num_fragments(node)?)
+                .map(|f| self.fragment_uri(node, f))
+                .collect::<TileDBResult<Vec<String>>>()?,
+        })
+    }
+
+    /// Returns all of the nodes of this plan.
+    pub fn nodes(&self) -> TileDBResult<Vec<ConsolidationPlanNode>> {
+        (0..self.num_nodes()?)
+            .map(|n| self.node(n))
+            .collect::<TileDBResult<Vec<_>>>()
+    }
+
+    /// Returns a JSON representation of this plan.
+    pub fn to_json(&self) -> TileDBResult<String> {
+        let c_plan = self.capi();
+        let mut c_json: *mut std::ffi::c_char = out_ptr!();
+        self.capi_call(|ctx| unsafe {
+            ffi::tiledb_consolidation_plan_dump_json_str(
+                ctx,
+                c_plan,
+                &mut c_json,
+            )
+        })?;
+
+        let json = String::from(
+            unsafe { std::ffi::CStr::from_ptr(c_json) }.to_string_lossy(),
+        );
+        unsafe {
+            ffi::tiledb_consolidation_plan_free_json_str(&mut c_json);
+        }
+        Ok(json)
+    }
+
+    /// Consolidates the fragments of the node at index `node`
+    /// into a single fragment.
+    ///
+    /// The consolidated fragments are not removed from storage
+    /// until the array is vacuumed.
+    pub fn consolidate_node(
+        &self,
+        node: usize,
+        config: Option<&Config>,
+    ) -> TileDBResult<()> {
+        let node = self.node(node)?;
+        let fragment_names = node.fragment_names();
+        if fragment_names.is_empty() {
+            return Ok(());
+        }
+
+        Array::consolidate_fragments(
+            &self.context,
+            self.array_uri.as_str(),
+            &fragment_names,
+            config,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::array::fragment_info::tests::create_sparse_array;
+    use crate::array::fragment_info::Builder as FragmentInfoBuilder;
+    use crate::array::Mode;
+
+    #[test]
+    fn consolidation_plan() -> TileDBResult<()> {
+        let ctx = Context::new()?;
+        let test_uri = uri::get_uri_generator()
+            .map_err(|e| Error::Other(e.to_string()))?;
+        let array_uri = create_sparse_array(&ctx, &test_uri)?;
+
+        let fragment_uris = FragmentInfoBuilder::new(&ctx, &array_uri)?
+            .build()?
+            .iter()?
+            .map(|f| f.uri())
+            .collect::<TileDBResult<Vec<String>>>()?;
+        assert_eq!(2, fragment_uris.len());
+
+        let plan = {
+            let array = Array::open(&ctx, &array_uri, Mode::Read)?;
+            ConsolidationPlan::new(&array, 1024 * 1024 * 1024)?
+        };
+        assert_eq!(array_uri, plan.array_uri());
+        assert!(!plan.to_json()?.is_empty());
+
+        let nodes = plan.nodes()?;
+        assert_eq!(plan.num_nodes()?, nodes.len());
+        for (n, node) in nodes.iter().enumerate() {
+            assert_eq!(plan.num_fragments(n)?, node.fragment_uris.len());
+            for uri in node.fragment_uris.iter() {
+                assert!(
+                    fragment_uris.contains(uri),
+                    "Unexpected fragment in plan: {}",
+                    uri
+                );
+            }
+        }
+        assert!(matches!(
+            plan.node(nodes.len()),
+            Err(Error::InvalidIndex(_))
+        ));
+
+        // the two fragments overlap and are small, so they go together
+        assert_eq!(1, nodes.len());
+        assert_eq!(2, nodes[0].fragment_uris.len());
+
+        plan.consolidate_node(0, None)?;
+
+        let fragment_info =
+            FragmentInfoBuilder::new(&ctx, &array_uri)?.build()?;
+        assert_eq!(1, fragment_info.num_fragments()?);
+        assert_eq!(2, fragment_info.num_to_vacuum()?);
+
+        Ok(())
+    }
+}
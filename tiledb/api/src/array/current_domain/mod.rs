@@ -0,0 +1,595 @@
+//! The current domain of an array schema is the region of the array
+//! domain which can currently be written to and read from.
+//!
+//! The current domain of an array starts out as a sub-region of its domain
+//! and can be expanded over time using
+//! [`SchemaEvolutionBuilder::expand_current_domain`](crate::array::schema::SchemaEvolutionBuilder::expand_current_domain),
+//! without re-creating the array.
+
+use std::ops::Deref;
+
+#[cfg(any(test, feature = "pod"))]
+use std::fmt::{Debug, Formatter, Result as FmtResult};
+
+use anyhow::anyhow;
+
+use crate::array::{CellValNum, Domain};
+use crate::context::{CApiInterface, Context, ContextBound};
+use crate::error::Error;
+use crate::key::LookupKey;
+use crate::range::{Range, SingleValueRange, TypedRange, VarValueRange};
+use crate::Datatype;
+use crate::Result as TileDBResult;
+
+use tiledb_common::{single_value_range_go, var_value_range_go};
+
+pub(crate) enum RawNDRectangle {
+    Owned(*mut ffi::tiledb_ndrectangle_t),
+}
+
+// SAFETY: see "Thread safety of handles" in the crate root.
+unsafe impl Send for RawNDRectangle {}
+unsafe impl Sync for RawNDRectangle {}
+
+impl Deref for RawNDRectangle {
+    type Target = *mut ffi::tiledb_ndrectangle_t;
+    fn deref(&self) -> &Self::Target {
+        let RawNDRectangle::Owned(ref ffi) = *self;
+        ffi
+    }
+}
+
+impl Drop for RawNDRectangle {
+    fn drop(&mut self) {
+        let RawNDRectangle::Owned(ref mut ffi) = *self;
+        unsafe {
+            ffi::tiledb_ndrectangle_free(ffi);
+        }
+    }
+}
+
+/// A hyper-rectangle which has one range for each dimension of a domain.
+pub struct NDRectangle {
+    context: Context,
+    raw: RawNDRectangle,
+}
+
+impl ContextBound for NDRectangle {
+    fn context(&self) -> Context {
+        self.context.clone()
+    }
+}
+
+impl NDRectangle {
+    pub(crate) fn capi(&self) -> *mut ffi::tiledb_ndrectangle_t {
+        *self.raw
+    }
+
+    /// Returns the number of dimensions.
+    pub fn num_dimensions(&self) -> TileDBResult<usize> {
+        let c_ndr = self.capi();
+        let mut c_ndim: u32 = out_ptr!();
+        self.capi_call(|ctx| unsafe {
+            ffi::tiledb_ndrectangle_get_dim_num(ctx, c_ndr, &mut c_ndim)
+        })?;
+        Ok(c_ndim as usize)
+    }
+
+    /// Returns the datatype of a dimension.
+    pub fn datatype<K: Into<LookupKey>>(
+        &self,
+        key: K,
+    ) -> TileDBResult<Datatype> {
+        let c_ndr = self.capi();
+        let mut c_dtype: ffi::tiledb_datatype_t = out_ptr!();
+        match key.into() {
+            LookupKey::Index(idx) => {
+                let c_idx = dimension_index(idx)?;
+                self.capi_call(|ctx| unsafe {
+                    ffi::tiledb_ndrectangle_get_dtype(
+                        ctx,
+                        c_ndr,
+                        c_idx,
+                        &mut c_dtype,
+                    )
+                })?;
+            }
+            LookupKey::Name(name) => {
+                let c_name = cstring!(name);
+                self.capi_call(|ctx| unsafe {
+                    ffi::tiledb_ndrectangle_get_dtype_from_name(
+                        ctx,
+                        c_ndr,
+                        c_name.as_ptr(),
+                        &mut c_dtype,
+                    )
+                })?;
+            }
+        }
+        Ok(Datatype::try_from(c_dtype)?)
+    }
+
+    /// Returns the range of a dimension.
+    pub fn range<K: Into<LookupKey> + Clone>(
+        &self,
+        key: K,
+    ) -> TileDBResult<Range> {
+        let datatype = self.datatype(key.clone())?;
+
+        let c_ndr = self.capi();
+        let mut c_range = ffi::tiledb_range_t {
+            min: std::ptr::null(),
+            min_size: 0,
+            max: std::ptr::null(),
+            max_size: 0,
+        };
+        match key.into() {
+            LookupKey::Index(idx) => {
+                let c_idx = dimension_index(idx)?;
+                self.capi_call(|ctx| unsafe {
+                    ffi::tiledb_ndrectangle_get_range(
+                        ctx,
+                        c_ndr,
+                        c_idx,
+                        &mut c_range,
+                    )
+                })?;
+            }
+            LookupKey::Name(name) => {
+                let c_name = cstring!(name);
+                self.capi_call(|ctx| unsafe {
+                    ffi::tiledb_ndrectangle_get_range_from_name(
+                        ctx,
+                        c_ndr,
+                        c_name.as_ptr(),
+                        &mut c_range,
+                    )
+                })?;
+            }
+        }
+
+        // N.B. the range lends pointers into the NDRectangle,
+        // thus we copy them and do *not* free them.
+        let (min, max) = unsafe {
+            (
+                std::slice::from_raw_parts(
+                    c_range.min as *const u8,
+                    c_range.min_size as usize,
+                ),
+                std::slice::from_raw_parts(
+                    c_range.max as *const u8,
+                    c_range.max_size as usize,
+                ),
+            )
+        };
+
+        let cell_val_num = if datatype.is_string_type() {
+            CellValNum::Var
+        } else {
+            CellValNum::single()
+        };
+        Ok(TypedRange::from_slices(datatype, cell_val_num, min, max)?.range)
+    }
+
+    /// Returns the range of each dimension, in order.
+    pub fn ranges(&self) -> TileDBResult<Vec<Range>> {
+        (0..self.num_dimensions()?)
+            .map(|d| self.range(d))
+            .collect::<TileDBResult<Vec<Range>>>()
+    }
+}
+
+impl PartialEq<NDRectangle> for NDRectangle {
+    fn eq(&self, other: &NDRectangle) -> bool {
+        match (self.ranges(), other.ranges()) {
+            (Ok(mine), Ok(theirs)) => mine == theirs,
+            _ => false,
+        }
+    }
+}
+
+#[cfg(any(test, feature = "pod"))]
+impl Debug for NDRectangle {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        match self.ranges() {
+            Ok(ranges) => f.debug_tuple("NDRectangle").field(&ranges).finish(),
+            Err(e) => {
+                let RawNDRectangle::Owned(ptr) = self.raw;
+                write!(f, "<NDRectangle @ {:?}: error: {}>", ptr, e)
+            }
+        }
+    }
+}
+
+fn dimension_index(idx: usize) -> TileDBResult<u32> {
+    idx.try_into().map_err(|e: <usize as TryInto<u32>>::Error| {
+        Error::InvalidArgument(anyhow!(e))
+    })
+}
+
+/// Provides methods to construct an [NDRectangle] over a [Domain].
+pub struct NDRectangleBuilder {
+    ndrectangle: NDRectangle,
+    domain: Domain,
+}
+
+impl ContextBound for NDRectangleBuilder {
+    fn context(&self) -> Context {
+        self.ndrectangle.context()
+    }
+}
+
+impl NDRectangleBuilder {
+    pub fn new(context: &Context, domain: Domain) -> TileDBResult<Self> {
+        let c_domain = domain.capi();
+        let mut c_ndr: *mut ffi::tiledb_ndrectangle_t = out_ptr!();
+        context.capi_call(|ctx| unsafe {
+            ffi::tiledb_ndrectangle_alloc(ctx, c_domain, &mut c_ndr)
+        })?;
+
+        Ok(NDRectangleBuilder {
+            ndrectangle: NDRectangle {
+                context: context.clone(),
+                raw: RawNDRectangle::Owned(c_ndr),
+            },
+            domain,
+        })
+    }
+
+    /// Sets the range of a dimension.
+    pub fn range<K, R>(self, key: K, range: R) -> TileDBResult<Self>
+    where
+        K: Into<LookupKey> + Clone,
+        R: Into<Range>,
+    {
+        let dim = self.domain.dimension(key.clone())?;
+        let range = range.into();
+        range
+            .check_dimension_compatibility(dim.datatype()?, dim.cell_val_num()?)
+            .map_err(|e| {
+                Error::InvalidArgument(
+                    anyhow!("Invalid range variant for dimension").context(e),
+                )
+            })?;
+
+        match range {
+            Range::Single(range) => {
+                single_value_range_go!(range, _DT, start, end, {
+                    let start = start.to_le_bytes();
+                    let end = end.to_le_bytes();
+                    self.set_range(key, &start, &end)?;
+                })
+            }
+            Range::Multi(_) => unreachable!(
+                "This is rejected by range.check_dimension_compatibility"
+            ),
+            Range::Var(range) => {
+                var_value_range_go!(range, _DT, start, end, {
+                    let start = unsafe {
+                        std::slice::from_raw_parts(
+                            start.as_ptr() as *const u8,
+                            std::mem::size_of_val(&*start),
+                        )
+                    };
+                    let end = unsafe {
+                        std::slice::from_raw_parts(
+                            end.as_ptr() as *const u8,
+                            std::mem::size_of_val(&*end),
+                        )
+                    };
+                    self.set_range(key, start, end)?;
+                })
+            }
+        }
+
+        Ok(self)
+    }
+
+    fn set_range<K: Into<LookupKey>>(
+        &self,
+        key: K,
+        start: &[u8],
+        end: &[u8],
+    ) -> TileDBResult<()> {
+        let c_ndr = self.ndrectangle.capi();
+        let mut c_range = ffi::tiledb_range_t {
+            min: start.as_ptr() as *const std::ffi::c_void,
+            min_size: start.len() as u64,
+            max: end.as_ptr() as *const std::ffi::c_void,
+            max_size: end.len() as u64,
+        };
+        match key.into() {
+            LookupKey::Index(idx) => {
+                let c_idx = dimension_index(idx)?;
+                self.capi_call(|ctx| unsafe {
+                    ffi::tiledb_ndrectangle_set_range(
+                        ctx,
+                        c_ndr,
+                        c_idx,
+                        &mut c_range,
+                    )
+                })?;
+            }
+            LookupKey::Name(name) => {
+                let c_name = cstring!(name);
+                self.capi_call(|ctx| unsafe {
+                    ffi::tiledb_ndrectangle_set_range_for_name(
+                        ctx,
+                        c_ndr,
+                        c_name.as_ptr(),
+                        &mut c_range,
+                    )
+                })?;
+            }
+        }
+        Ok(())
+    }
+
+    pub fn build(self) -> NDRectangle {
+        self.ndrectangle
+    }
+}
+
+impl From<NDRectangleBuilder> for NDRectangle {
+    fn from(builder: NDRectangleBuilder) -> NDRectangle {
+        builder.build()
+    }
+}
+
+pub(crate) enum RawCurrentDomain {
+    Owned(*mut ffi::tiledb_current_domain_t),
+}
+
+// SAFETY: see "Thread safety of handles" in the crate root.
+unsafe impl Send for RawCurrentDomain {}
+unsafe impl Sync for RawCurrentDomain {}
+
+impl Deref for RawCurrentDomain {
+    type Target = *mut ffi::tiledb_current_domain_t;
+    fn deref(&self) -> &Self::Target {
+        let RawCurrentDomain::Owned(ref ffi) = *self;
+        ffi
+    }
+}
+
+impl Drop for RawCurrentDomain {
+    fn drop(&mut self) {
+        let RawCurrentDomain::Owned(ref mut ffi) = *self;
+        unsafe {
+            ffi::tiledb_current_domain_free(ffi);
+        }
+    }
+}
+
+/// The region of an array domain which can currently be accessed.
+///
+/// An empty current domain does not restrict access to the array domain.
+pub struct CurrentDomain {
+    context: Context,
+    raw: RawCurrentDomain,
+}
+
+impl ContextBound for CurrentDomain {
+    fn context(&self) -> Context {
+        self.context.clone()
+    }
+}
+
+impl CurrentDomain {
+    pub(crate) fn capi(&self) -> *mut ffi::tiledb_current_domain_t {
+        *self.raw
+    }
+
+    pub(crate) fn new(context: &Context, raw: RawCurrentDomain) -> Self {
+        CurrentDomain {
+            context: context.clone(),
+            raw,
+        }
+    }
+
+    /// Returns whether this current domain is empty.
+    pub fn is_empty(&self) -> TileDBResult<bool> {
+        let c_current_domain = self.capi();
+        let mut c_is_empty: u32 = out_ptr!();
+        self.capi_call(|ctx| unsafe {
+            ffi::tiledb_current_domain_get_is_empty(
+                ctx,
+                c_current_domain,
+                &mut c_is_empty,
+            )
+        })?;
+        Ok(c_is_empty != 0)
+    }
+
+    /// Returns the [NDRectangle] which represents this current domain,
+    /// or `None` if this current domain is empty.
+    pub fn ndrectangle(&self) -> TileDBResult<Option<NDRectangle>> {
+        if self.is_empty()? {
+            return Ok(None);
+        }
+
+        let c_current_domain = self.capi();
+        let mut c_type: ffi::tiledb_current_domain_type_t = out_ptr!();
+        self.capi_call(|ctx| unsafe {
+            ffi::tiledb_current_domain_get_type(
+                ctx,
+                c_current_domain,
+                &mut c_type,
+            )
+        })?;
+        if c_type != ffi::tiledb_current_domain_type_t_TILEDB_NDRECTANGLE {
+            return Err(Error::Internal(format!(
+                "Unrecognized current domain type: {}",
+                c_type
+            )));
+        }
+
+        let mut c_ndr: *mut ffi::tiledb_ndrectangle_t = out_ptr!();
+        self.capi_call(|ctx| unsafe {
+            ffi::tiledb_current_domain_get_ndrectangle(
+                ctx,
+                c_current_domain,
+                &mut c_ndr,
+            )
+        })?;
+
+        Ok(Some(NDRectangle {
+            context: self.context.clone(),
+            raw: RawNDRectangle::Owned(c_ndr),
+        }))
+    }
+}
+
+impl PartialEq<CurrentDomain> for CurrentDomain {
+    fn eq(&self, other: &CurrentDomain) -> bool {
+        match (self.ndrectangle(), other.ndrectangle()) {
+            (Ok(mine), Ok(theirs)) => mine == theirs,
+            _ => false,
+        }
+    }
+}
+
+#[cfg(any(test, feature = "pod"))]
+impl Debug for CurrentDomain {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        match self.ndrectangle() {
+            Ok(None) => write!(f, "CurrentDomain(<empty>)"),
+            Ok(Some(ndr)) => {
+                f.debug_tuple("CurrentDomain").field(&ndr).finish()
+            }
+            Err(e) => {
+                let RawCurrentDomain::Owned(ptr) = self.raw;
+                write!(f, "<CurrentDomain @ {:?}: error: {}>", ptr, e)
+            }
+        }
+    }
+}
+
+/// Provides methods to construct a [CurrentDomain].
+pub struct Builder {
+    current_domain: CurrentDomain,
+}
+
+impl ContextBound for Builder {
+    fn context(&self) -> Context {
+        self.current_domain.context()
+    }
+}
+
+impl Builder {
+    /// Returns a builder for an initially empty current domain.
+    pub fn new(context: &Context) -> TileDBResult<Self> {
+        let mut c_current_domain: *mut ffi::tiledb_current_domain_t =
+            out_ptr!();
+        context.capi_call(|ctx| unsafe {
+            ffi::tiledb_current_domain_create(ctx, &mut c_current_domain)
+        })?;
+
+        Ok(Builder {
+            current_domain: CurrentDomain::new(
+                context,
+                RawCurrentDomain::Owned(c_current_domain),
+            ),
+        })
+    }
+
+    /// Sets the current domain to the region covered by `ndrectangle`.
+    pub fn ndrectangle(self, ndrectangle: NDRectangle) -> TileDBResult<Self> {
+        let c_current_domain = self.current_domain.capi();
+        let c_ndr = ndrectangle.capi();
+        self.capi_call(|ctx| unsafe {
+            ffi::tiledb_current_domain_set_ndrectangle(
+                ctx,
+                c_current_domain,
+                c_ndr,
+            )
+        })?;
+        Ok(self)
+    }
+
+    pub fn build(self) -> CurrentDomain {
+        self.current_domain
+    }
+}
+
+impl From<Builder> for CurrentDomain {
+    fn from(builder: Builder) -> CurrentDomain {
+        builder.build()
+    }
+}
+
+#[cfg(any(test, feature = "pod"))]
+pub mod pod;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::array::{DimensionBuilder, DimensionConstraints, DomainBuilder};
+
+    fn sample_domain(context: &Context) -> TileDBResult<Domain> {
+        Ok(DomainBuilder::new(context)?
+            .add_dimension(
+                DimensionBuilder::new(
+                    context,
+                    "id",
+                    Datatype::UInt64,
+                    ([0u64, 1_000_000], 100u64),
+                )?
+                .build(),
+            )?
+            .add_dimension(
+                DimensionBuilder::new(
+                    context,
+                    "name",
+                    Datatype::StringAscii,
+                    DimensionConstraints::StringAscii,
+                )?
+                .build(),
+            )?
+            .build())
+    }
+
+    #[test]
+    fn empty() -> TileDBResult<()> {
+        let context = Context::new()?;
+        let current_domain = Builder::new(&context)?.build();
+        assert!(current_domain.is_empty()?);
+        assert!(current_domain.ndrectangle()?.is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn ndrectangle() -> TileDBResult<()> {
+        let context = Context::new()?;
+
+        let ndr = NDRectangleBuilder::new(&context, sample_domain(&context)?)?
+            .range(0, SingleValueRange::from(0u64..=100))?
+            .range("name", ("a", "m"))?
+            .build();
+        assert_eq!(2, ndr.num_dimensions()?);
+        assert_eq!(Datatype::UInt64, ndr.datatype(0)?);
+        assert_eq!(Datatype::StringAscii, ndr.datatype("name")?);
+        assert_eq!(
+            Range::Single(SingleValueRange::UInt64(0, 100)),
+            ndr.range("id")?
+        );
+        assert_eq!(Range::Var(VarValueRange::from(("a", "m"))), ndr.range(1)?);
+
+        // ranges must match the dimension type
+        let r = NDRectangleBuilder::new(&context, sample_domain(&context)?)?
+            .range("id", SingleValueRange::from(0i32..=100));
+        assert!(matches!(r, Err(Error::InvalidArgument(_))));
+
+        let current_domain = Builder::new(&context)?.ndrectangle(ndr)?.build();
+        assert!(!current_domain.is_empty()?);
+        assert_eq!(
+            vec![
+                Range::Single(SingleValueRange::UInt64(0, 100)),
+                Range::Var(VarValueRange::from(("a", "m")))
+            ],
+            current_domain.ndrectangle()?.unwrap().ranges()?
+        );
+
+        Ok(())
+    }
+}
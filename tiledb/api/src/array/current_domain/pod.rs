@@ -0,0 +1,85 @@
+use anyhow::anyhow;
+use tiledb_pod::array::current_domain::{CurrentDomainData, NDRectangleData};
+
+use super::{Builder, CurrentDomain, NDRectangle, NDRectangleBuilder};
+use crate::array::Domain;
+use crate::error::Error as TileDBError;
+use crate::{Context, Result as TileDBResult};
+
+impl TryFrom<&NDRectangle> for NDRectangleData {
+    type Error = TileDBError;
+
+    fn try_from(ndrectangle: &NDRectangle) -> Result<Self, Self::Error> {
+        Ok(NDRectangleData {
+            dimension: ndrectangle.ranges()?,
+        })
+    }
+}
+
+impl TryFrom<NDRectangle> for NDRectangleData {
+    type Error = TileDBError;
+
+    fn try_from(ndrectangle: NDRectangle) -> Result<Self, Self::Error> {
+        Self::try_from(&ndrectangle)
+    }
+}
+
+impl TryFrom<&CurrentDomain> for CurrentDomainData {
+    type Error = TileDBError;
+
+    fn try_from(current_domain: &CurrentDomain) -> Result<Self, Self::Error> {
+        match current_domain.ndrectangle()? {
+            Some(ndr) => {
+                Ok(CurrentDomainData::from(NDRectangleData::try_from(&ndr)?))
+            }
+            None => Err(TileDBError::InvalidArgument(anyhow!(
+                "Cannot convert empty current domain"
+            ))),
+        }
+    }
+}
+
+impl TryFrom<CurrentDomain> for CurrentDomainData {
+    type Error = TileDBError;
+
+    fn try_from(current_domain: CurrentDomain) -> Result<Self, Self::Error> {
+        Self::try_from(&current_domain)
+    }
+}
+
+impl NDRectangle {
+    /// Constructs an [NDRectangle] over `domain` from its data.
+    ///
+    /// This is not a [Factory](crate::Factory) implementation because
+    /// an NDRectangle cannot be created without its domain.
+    pub fn from_data(
+        context: &Context,
+        domain: Domain,
+        data: &NDRectangleData,
+    ) -> TileDBResult<Self> {
+        Ok(data
+            .dimension
+            .iter()
+            .enumerate()
+            .try_fold(
+                NDRectangleBuilder::new(context, domain)?,
+                |b, (d, range)| b.range(d, range.clone()),
+            )?
+            .build())
+    }
+}
+
+impl CurrentDomain {
+    /// Constructs a [CurrentDomain] of an array with `domain` from its data.
+    pub fn from_data(
+        context: &Context,
+        domain: Domain,
+        data: &CurrentDomainData,
+    ) -> TileDBResult<Self> {
+        match data {
+            CurrentDomainData::NDRectangle(ndr) => Ok(Builder::new(context)?
+                .ndrectangle(NDRectangle::from_data(context, domain, ndr)?)?
+                .build()),
+        }
+    }
+}
@@ -16,6 +16,10 @@ pub(crate) enum RawDimension {
     Owned(*mut ffi::tiledb_dimension_t),
 }
 
+// SAFETY: see "Thread safety of handles" in the crate root.
+unsafe impl Send for RawDimension {}
+unsafe impl Sync for RawDimension {}
+
 impl Deref for RawDimension {
     type Target = *mut ffi::tiledb_dimension_t;
     fn deref(&self) -> &Self::Target {
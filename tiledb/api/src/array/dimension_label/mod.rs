@@ -0,0 +1,186 @@
+//! Dimension labels map the coordinates of a dimension to label values,
+//! so that the array can be queried by label value instead of coordinate.
+//!
+//! Dimension labels are added to a schema using
+//! [`SchemaBuilder::add_dimension_label`](crate::array::SchemaBuilder::add_dimension_label),
+//! and are queried using
+//! [`subarray::Builder::add_label_range`](crate::query::subarray::Builder::add_label_range).
+
+use std::ops::Deref;
+
+#[cfg(any(test, feature = "pod"))]
+use std::fmt::{Debug, Formatter, Result as FmtResult};
+
+use crate::array::{CellValNum, DataOrder};
+use crate::context::{CApiInterface, Context, ContextBound};
+use crate::{Datatype, Result as TileDBResult};
+
+pub(crate) enum RawDimensionLabel {
+    Owned(*mut ffi::tiledb_dimension_label_t),
+}
+
+// SAFETY: dimension labels are read-only.
+unsafe impl Send for RawDimensionLabel {}
+unsafe impl Sync for RawDimensionLabel {}
+
+impl Deref for RawDimensionLabel {
+    type Target = *mut ffi::tiledb_dimension_label_t;
+    fn deref(&self) -> &Self::Target {
+        let RawDimensionLabel::Owned(ref ffi) = *self;
+        ffi
+    }
+}
+
+impl Drop for RawDimensionLabel {
+    fn drop(&mut self) {
+        let RawDimensionLabel::Owned(ref mut ffi) = *self;
+        unsafe {
+            ffi::tiledb_dimension_label_free(ffi);
+        }
+    }
+}
+
+pub struct DimensionLabel {
+    context: Context,
+    raw: RawDimensionLabel,
+}
+
+impl ContextBound for DimensionLabel {
+    fn context(&self) -> Context {
+        self.context.clone()
+    }
+}
+
+impl DimensionLabel {
+    pub(crate) fn capi(&self) -> *mut ffi::tiledb_dimension_label_t {
+        *self.raw
+    }
+
+    pub(crate) fn new(context: &Context, raw: RawDimensionLabel) -> Self {
+        DimensionLabel {
+            context: context.clone(),
+            raw,
+        }
+    }
+
+    fn string_property(
+        &self,
+        ffi_function: unsafe extern "C" fn(
+            *mut ffi::tiledb_ctx_t,
+            *mut ffi::tiledb_dimension_label_t,
+            *mut *const std::ffi::c_char,
+        ) -> i32,
+    ) -> TileDBResult<String> {
+        let c_label = self.capi();
+        let mut c_str = std::ptr::null::<std::ffi::c_char>();
+        self.capi_call(|ctx| unsafe {
+            ffi_function(ctx, c_label, &mut c_str)
+        })?;
+        let c_str = unsafe { std::ffi::CStr::from_ptr(c_str) };
+        Ok(String::from(c_str.to_string_lossy()))
+    }
+
+    /// Returns the name of the label.
+    pub fn name(&self) -> TileDBResult<String> {
+        self.string_property(ffi::tiledb_dimension_label_get_name)
+    }
+
+    /// Returns the URI of the array which stores the label values.
+    pub fn uri(&self) -> TileDBResult<String> {
+        self.string_property(ffi::tiledb_dimension_label_get_uri)
+    }
+
+    /// Returns the name of the attribute which holds the label values
+    /// in the label array.
+    pub fn attribute_name(&self) -> TileDBResult<String> {
+        self.string_property(ffi::tiledb_dimension_label_get_label_attr_name)
+    }
+
+    /// Returns the index of the dimension which this label applies to.
+    pub fn dimension_index(&self) -> TileDBResult<usize> {
+        let c_label = self.capi();
+        let mut c_index: u32 = out_ptr!();
+        self.capi_call(|ctx| unsafe {
+            ffi::tiledb_dimension_label_get_dimension_index(
+                ctx,
+                c_label,
+                &mut c_index,
+            )
+        })?;
+        Ok(c_index as usize)
+    }
+
+    /// Returns the datatype of the label values.
+    pub fn datatype(&self) -> TileDBResult<Datatype> {
+        let c_label = self.capi();
+        let mut c_dtype: ffi::tiledb_datatype_t = out_ptr!();
+        self.capi_call(|ctx| unsafe {
+            ffi::tiledb_dimension_label_get_label_type(
+                ctx,
+                c_label,
+                &mut c_dtype,
+            )
+        })?;
+        Ok(Datatype::try_from(c_dtype)?)
+    }
+
+    /// Returns the number of values in each label.
+    pub fn cell_val_num(&self) -> TileDBResult<CellValNum> {
+        let c_label = self.capi();
+        let mut c_num: std::ffi::c_uint = out_ptr!();
+        self.capi_call(|ctx| unsafe {
+            ffi::tiledb_dimension_label_get_label_cell_val_num(
+                ctx, c_label, &mut c_num,
+            )
+        })?;
+        Ok(CellValNum::try_from(c_num)?)
+    }
+
+    /// Returns the order of the label values.
+    pub fn order(&self) -> TileDBResult<DataOrder> {
+        let c_label = self.capi();
+        let mut c_order: ffi::tiledb_data_order_t = out_ptr!();
+        self.capi_call(|ctx| unsafe {
+            ffi::tiledb_dimension_label_get_label_order(
+                ctx,
+                c_label,
+                &mut c_order,
+            )
+        })?;
+        Ok(DataOrder::try_from(c_order)?)
+    }
+}
+
+impl PartialEq<DimensionLabel> for DimensionLabel {
+    fn eq(&self, other: &DimensionLabel) -> bool {
+        eq_helper!(self.name(), other.name());
+        eq_helper!(self.dimension_index(), other.dimension_index());
+        eq_helper!(self.datatype(), other.datatype());
+        eq_helper!(self.cell_val_num(), other.cell_val_num());
+        eq_helper!(self.order(), other.order());
+
+        true
+    }
+}
+
+#[cfg(any(test, feature = "pod"))]
+impl Debug for DimensionLabel {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        match tiledb_pod::array::dimension_label::DimensionLabelData::try_from(
+            self,
+        ) {
+            Ok(d) => Debug::fmt(&d, f),
+            Err(e) => {
+                let RawDimensionLabel::Owned(ptr) = self.raw;
+                write!(
+                    f,
+                    "<DimensionLabel @ {:?}: serialization error: {}>",
+                    ptr, e
+                )
+            }
+        }
+    }
+}
+
+#[cfg(any(test, feature = "pod"))]
+pub mod pod;
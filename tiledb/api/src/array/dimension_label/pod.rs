@@ -0,0 +1,26 @@
+use tiledb_pod::array::dimension_label::DimensionLabelData;
+
+use super::DimensionLabel;
+use crate::error::Error as TileDBError;
+
+impl TryFrom<&DimensionLabel> for DimensionLabelData {
+    type Error = TileDBError;
+
+    fn try_from(label: &DimensionLabel) -> Result<Self, Self::Error> {
+        Ok(DimensionLabelData {
+            name: label.name()?,
+            dimension: label.dimension_index()?,
+            order: label.order()?,
+            datatype: label.datatype()?,
+            filters: None,
+        })
+    }
+}
+
+impl TryFrom<DimensionLabel> for DimensionLabelData {
+    type Error = TileDBError;
+
+    fn try_from(label: DimensionLabel) -> Result<Self, Self::Error> {
+        Self::try_from(&label)
+    }
+}
@@ -15,6 +15,10 @@ pub(crate) enum RawDomain {
     Owned(*mut ffi::tiledb_domain_t),
 }
 
+// SAFETY: see "Thread safety of handles" in the crate root.
+unsafe impl Send for RawDomain {}
+unsafe impl Sync for RawDomain {}
+
 impl Deref for RawDomain {
     type Target = *mut ffi::tiledb_domain_t;
     fn deref(&self) -> &Self::Target {
@@ -13,6 +13,11 @@ pub(crate) enum RawEnumeration {
     Owned(*mut ffi::tiledb_enumeration_t),
 }
 
+// SAFETY: see "Thread safety of handles" in the crate root.
+// Extending an enumeration creates a new one rather than modifying it.
+unsafe impl Send for RawEnumeration {}
+unsafe impl Sync for RawEnumeration {}
+
 impl Deref for RawEnumeration {
     type Target = *mut ffi::tiledb_enumeration_t;
     fn deref(&self) -> &Self::Target {
@@ -4,6 +4,7 @@ use std::ops::Deref;
 use anyhow::anyhow;
 
 use crate::array::schema::{RawSchema, Schema};
+use crate::array::Array;
 use crate::config::{Config, RawConfig};
 use crate::context::{CApiInterface, Context, ContextBound};
 use crate::datatype::Datatype;
@@ -41,6 +42,7 @@ impl Drop for RawFragmentInfo {
 struct FragmentInfoInternal {
     context: Context,
     raw: RawFragmentInfo,
+    array_uri: String,
 }
 
 // impl<'ctx> ContextBoundBase<'ctx> for FragmentInfoInternal<'ctx> {}
@@ -631,6 +633,36 @@ impl FragmentInfoList {
     pub fn iter(&self) -> TileDBResult<FragmentInfoListIterator> {
         FragmentInfoListIterator::try_from(self)
     }
+
+    /// Returns the URI of the array which this fragment info describes.
+    pub fn array_uri(&self) -> &str {
+        &self.info.array_uri
+    }
+
+    /// Deletes `fragments` from the array which this fragment info describes.
+    ///
+    /// This fragment info is not updated by the deletion.
+    /// Call [Self::load] to observe the remaining fragments.
+    pub fn delete_fragments<'info, I>(
+        &'info self,
+        fragments: I,
+    ) -> TileDBResult<()>
+    where
+        I: IntoIterator<Item = FragmentInfo<'info>>,
+    {
+        let fragment_uris = fragments
+            .into_iter()
+            .map(|f| f.uri())
+            .collect::<TileDBResult<Vec<String>>>()?;
+        if fragment_uris.is_empty() {
+            return Ok(());
+        }
+        Array::delete_fragments_list(
+            &self.context(),
+            self.array_uri(),
+            &fragment_uris,
+        )
+    }
 }
 
 pub struct FragmentInfoListIterator<'info> {
@@ -714,6 +746,7 @@ impl Builder {
             info: FragmentInfoInternal {
                 context: context.clone(),
                 raw: RawFragmentInfo::Owned(c_frag_info),
+                array_uri: uri.as_ref().to_owned(),
             },
         })
     }
@@ -23,18 +23,28 @@ use crate::Result as TileDBResult;
 use crate::{physical_type_go, Datatype};
 
 pub mod attribute;
+pub mod consolidation_plan;
+pub mod current_domain;
 pub mod dimension;
+pub mod dimension_label;
 pub mod domain;
 pub mod enumeration;
 pub mod fragment_info;
+pub mod pool;
 pub mod schema;
 
 use crate::config::Config;
 
 pub use attribute::{Attribute, Builder as AttributeBuilder};
+pub use consolidation_plan::{ConsolidationPlan, ConsolidationPlanNode};
+pub use current_domain::{
+    Builder as CurrentDomainBuilder, CurrentDomain, NDRectangle,
+    NDRectangleBuilder,
+};
 pub use dimension::{
     Builder as DimensionBuilder, Dimension, DimensionConstraints,
 };
+pub use dimension_label::DimensionLabel;
 pub use domain::{Builder as DomainBuilder, Domain};
 pub use enumeration::{
     Builder as EnumerationBuilder, Enumeration, ExtendedEnumeration,
@@ -43,10 +53,11 @@ use ffi::tiledb_config_t;
 pub use fragment_info::{
     Builder as FragmentInfoBuilder, FragmentInfo, FragmentInfoList,
 };
+pub use pool::{ArrayPool, PooledArray, SharedArray};
 pub use schema::{
     ArrayType, Builder as SchemaBuilder, CellValNum, Field, Schema,
 };
-pub use tiledb_common::array::{CellOrder, Mode, TileOrder};
+pub use tiledb_common::array::{CellOrder, DataOrder, Mode, TileOrder};
 
 /// Method of encryption.
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
@@ -137,6 +148,12 @@ pub enum RawArray {
     Owned(*mut ffi::tiledb_array_t),
 }
 
+// SAFETY: libtiledb allows an open array to be used by several threads
+// at once, for example to submit queries concurrently. Operations which
+// modify the array handle itself take `&mut self` or `self`.
+unsafe impl Send for RawArray {}
+unsafe impl Sync for RawArray {}
+
 impl Deref for RawArray {
     type Target = *mut ffi::tiledb_array_t;
     fn deref(&self) -> &Self::Target {
@@ -245,6 +262,16 @@ impl Array {
         self.uri.as_ref()
     }
 
+    /// Returns the mode which this array is open for.
+    pub fn mode(&self) -> TileDBResult<Mode> {
+        let c_array = *self.raw;
+        let mut c_type: ffi::tiledb_query_type_t = out_ptr!();
+        self.capi_call(|ctx| unsafe {
+            ffi::tiledb_array_get_query_type(ctx, c_array, &mut c_type)
+        })?;
+        Ok(Mode::try_from(c_type)?)
+    }
+
     pub fn schema(&self) -> TileDBResult<Schema> {
         let c_array = *self.raw;
         let mut c_schema: *mut ffi::tiledb_array_schema_t = out_ptr!();
@@ -517,6 +544,69 @@ impl Array {
         Ok(())
     }
 
+    /// Deletes the fragments of the array located at `array_uri` which were
+    /// written within the inclusive timestamp range `[start_timestamp, end_timestamp]`.
+    ///
+    /// Deleted fragments are removed from storage and cannot be recovered.
+    pub fn delete_fragments<S>(
+        context: &Context,
+        array_uri: S,
+        start_timestamp: u64,
+        end_timestamp: u64,
+    ) -> TileDBResult<()>
+    where
+        S: AsRef<str>,
+    {
+        let c_array_uri = cstring!(array_uri.as_ref());
+
+        context.capi_call(|ctx| unsafe {
+            ffi::tiledb_array_delete_fragments_v2(
+                ctx,
+                c_array_uri.as_ptr(),
+                start_timestamp,
+                end_timestamp,
+            )
+        })?;
+        Ok(())
+    }
+
+    /// Deletes the fragments with the given URIs from the array
+    /// located at `array_uri`.
+    ///
+    /// See [FragmentInfoList::delete_fragments] to delete fragments
+    /// selected from the array's fragment info.
+    pub fn delete_fragments_list<S, F>(
+        context: &Context,
+        array_uri: S,
+        fragment_uris: &[F],
+    ) -> TileDBResult<()>
+    where
+        S: AsRef<str>,
+        F: AsRef<str>,
+    {
+        let c_array_uri = cstring!(array_uri.as_ref());
+
+        // This array has to outlive the API call below.
+        let fragment_uris_cstr = fragment_uris
+            .iter()
+            .map(|fragment_uri| Ok(cstring!(fragment_uri.as_ref())))
+            .collect::<TileDBResult<Vec<_>>>()?;
+        let mut fragment_uris_ptr = fragment_uris_cstr
+            .iter()
+            .map(|fragment_uri| fragment_uri.as_ptr())
+            .collect::<Vec<_>>();
+
+        context.capi_call(|ctx| unsafe {
+            ffi::tiledb_array_delete_fragments_list(
+                ctx,
+                c_array_uri.as_ptr(),
+                fragment_uris_ptr.as_mut_ptr(),
+                fragment_uris_ptr.len(),
+            )
+        })?;
+        Ok(())
+    }
+
     /// Evolves the schema of an array.
     pub fn evolve<S>(
         context: &Context,
@@ -1341,6 +1431,52 @@ pub mod tests {
         Ok(())
     }
 
+    #[test]
+    fn delete_fragments() -> TileDBResult<()> {
+        let test_uri = uri::get_uri_generator()
+            .map_err(|e| Error::Other(e.to_string()))?;
+
+        let ctx = Context::new()?;
+        let array_uri = create_simple_dense(&test_uri, &ctx)?;
+
+        // fragments at timestamps 1, 2, 3, 4
+        write_dense_vector_4_fragments(&ctx, &array_uri, 0)?;
+
+        let fragment_info =
+            || FragmentInfoBuilder::new(&ctx, &array_uri)?.build();
+        let timestamps = || -> TileDBResult<Vec<u64>> {
+            let fragment_info = fragment_info()?;
+            let timestamps = fragment_info
+                .iter()?
+                .map(|f| Ok(f.timestamp_range()?[0]))
+                .collect::<TileDBResult<Vec<u64>>>();
+            timestamps
+        };
+        assert_eq!(vec![1, 2, 3, 4], timestamps()?);
+
+        // by timestamp range
+        Array::delete_fragments(&ctx, &array_uri, 1, 2)?;
+        assert_eq!(vec![3, 4], timestamps()?);
+
+        // from a filtered fragment info iterator
+        {
+            let fragment_info = fragment_info()?;
+            fragment_info.delete_fragments(
+                fragment_info
+                    .iter()?
+                    .filter(|f| f.timestamp_range().unwrap()[0] == 4),
+            )?;
+        }
+        assert_eq!(vec![3], timestamps()?);
+
+        // by fragment URI
+        let fragment_uri = fragment_info()?.get_fragment(0)?.uri()?;
+        Array::delete_fragments_list(&ctx, &array_uri, &[fragment_uri])?;
+        assert_eq!(Vec::<u64>::new(), timestamps()?);
+
+        Ok(())
+    }
+
     #[test]
     fn create_enumeration() -> TileDBResult<()> {
         let test_uri = uri::get_uri_generator()
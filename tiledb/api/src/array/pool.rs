@@ -0,0 +1,306 @@
+use std::ops::{Deref, DerefMut};
+use std::sync::{Arc, Mutex};
+
+use crate::array::{Array, Mode};
+use crate::context::{Context, ContextBound};
+use crate::Result as TileDBResult;
+
+/// An array which is open for reading and which can be shared by
+/// many threads.
+///
+/// The read-only methods of [Array], such as [Array::schema] and
+/// [Array::metadata], are available through `Deref`. Queries take
+/// ownership of an [Array]; use an [ArrayPool] to run queries from
+/// several threads.
+#[derive(Clone)]
+pub struct SharedArray {
+    array: Arc<Array>,
+}
+
+impl SharedArray {
+    /// Opens the array located at `uri` for reading.
+    pub fn open<S>(context: &Context, uri: S) -> TileDBResult<Self>
+    where
+        S: AsRef<str>,
+    {
+        Ok(SharedArray {
+            array: Arc::new(Array::open(context, uri, Mode::Read)?),
+        })
+    }
+}
+
+impl ContextBound for SharedArray {
+    fn context(&self) -> Context {
+        self.array.context()
+    }
+}
+
+impl Deref for SharedArray {
+    type Target = Array;
+
+    fn deref(&self) -> &Self::Target {
+        &self.array
+    }
+}
+
+/// A pool of [Array]s which are open for reading the same URI.
+///
+/// Opening an array reads its schema and fragment metadata from storage.
+/// An `ArrayPool` keeps arrays open after they are used, so that threads
+/// which run many queries do not need to open the array each time.
+///
+/// An array only sees the fragments which existed when it was opened.
+/// Idle arrays are not reopened, so queries which use arrays from the pool
+/// read a snapshot of the array as of when each array was first opened,
+/// and may not see fragments written since then. Use [Array::reopen]
+/// on the array, or a new pool, to read later fragments.
+pub struct ArrayPool {
+    context: Context,
+    uri: String,
+    capacity: usize,
+    idle: Mutex<Vec<Array>>,
+}
+
+impl ArrayPool {
+    /// Returns a pool of arrays open for reading `uri`, which keeps at most
+    /// as many idle arrays as there are threads available to the process.
+    pub fn new<S>(context: &Context, uri: S) -> Self
+    where
+        S: AsRef<str>,
+    {
+        let capacity = std::thread::available_parallelism()
+            .map(|n| n.get())
+            .unwrap_or(1);
+        Self::with_capacity(context, uri, capacity)
+    }
+
+    /// Returns a pool of arrays open for reading `uri`, which keeps at most
+    /// `capacity` idle arrays.
+    pub fn with_capacity<S>(context: &Context, uri: S, capacity: usize) -> Self
+    where
+        S: AsRef<str>,
+    {
+        ArrayPool {
+            context: context.clone(),
+            uri: uri.as_ref().to_owned(),
+            capacity,
+            idle: Mutex::new(vec![]),
+        }
+    }
+
+    /// Returns the URI of the arrays in this pool.
+    pub fn uri(&self) -> &str {
+        &self.uri
+    }
+
+    /// Returns the number of idle arrays in this pool.
+    pub fn num_idle(&self) -> usize {
+        self.lock().len()
+    }
+
+    /// Takes an idle array from the pool, or opens a new one if there are
+    /// none. The array returns to the pool when the `PooledArray` is dropped.
+    ///
+    /// An idle array is returned as it is, without being reopened;
+    /// see [ArrayPool] for how this affects which fragments it reads.
+    pub fn get(&self) -> TileDBResult<PooledArray<'_>> {
+        let idle = self.lock().pop();
+        let array = match idle {
+            Some(array) => array,
+            None => Array::open(&self.context, &self.uri, Mode::Read)?,
+        };
+        Ok(PooledArray {
+            pool: self,
+            array: Some(array),
+        })
+    }
+
+    /// Returns an array to the pool, for example after a query which
+    /// took ownership of a [PooledArray::into_inner] is finalized.
+    ///
+    /// The array is closed instead if it is not open for reading,
+    /// if it is located at a different URI, or if the pool is already full.
+    pub fn put(&self, array: Array) {
+        if array.uri() != self.uri || !matches!(array.mode(), Ok(Mode::Read)) {
+            return;
+        }
+        let mut idle = self.lock();
+        if idle.len() < self.capacity {
+            idle.push(array);
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Vec<Array>> {
+        self.idle.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl ContextBound for ArrayPool {
+    fn context(&self) -> Context {
+        self.context.clone()
+    }
+}
+
+/// An [Array] which has been taken from an [ArrayPool].
+pub struct PooledArray<'pool> {
+    pool: &'pool ArrayPool,
+    array: Option<Array>,
+}
+
+impl PooledArray<'_> {
+    /// Takes the array out of the pool, for example to build a query with it.
+    /// Use [ArrayPool::put] to return it afterwards.
+    pub fn into_inner(mut self) -> Array {
+        self.array.take().unwrap()
+    }
+}
+
+impl Deref for PooledArray<'_> {
+    type Target = Array;
+
+    fn deref(&self) -> &Self::Target {
+        self.array.as_ref().unwrap()
+    }
+}
+
+impl DerefMut for PooledArray<'_> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.array.as_mut().unwrap()
+    }
+}
+
+impl Drop for PooledArray<'_> {
+    fn drop(&mut self) {
+        if let Some(array) = self.array.take() {
+            self.pool.put(array)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::metadata::Metadata;
+    use crate::query::ReadQueryBuilder;
+    use crate::tests::prelude::*;
+
+    const NTHREADS: usize = 8;
+    const NITERATIONS: usize = 16;
+
+    #[test]
+    fn handles_are_send_and_sync() {
+        fn is_send_sync<T: Send + Sync>() {}
+        is_send_sync::<Context>();
+        is_send_sync::<Array>();
+        is_send_sync::<Schema>();
+        is_send_sync::<SharedArray>();
+        is_send_sync::<ArrayPool>();
+
+        fn is_send<T: Send>() {}
+        is_send::<WriteQuery<'static>>();
+    }
+
+    fn quickstart_array(name: &str) -> anyhow::Result<TestArray> {
+        let mut array = sparse_array(name)?;
+        array.for_write()?.put_metadata(Metadata::new(
+            "key".to_owned(),
+            Datatype::Int32,
+            vec![42],
+        )?)?;
+        Ok(array)
+    }
+
+    #[test]
+    fn shared_array() -> anyhow::Result<()> {
+        let test_array = quickstart_array("shared_array")?;
+        let shared = SharedArray::open(&test_array.context, &test_array.uri)?;
+
+        std::thread::scope(|s| {
+            let handles = (0..NTHREADS)
+                .map(|_| {
+                    let shared = shared.clone();
+                    s.spawn(move || -> TileDBResult<()> {
+                        for _ in 0..NITERATIONS {
+                            assert_eq!(1, shared.schema()?.num_attributes()?);
+                            assert!(shared.nonempty_domain()?.is_some());
+                            assert_eq!(1, shared.num_metadata()?);
+                        }
+                        Ok(())
+                    })
+                })
+                .collect::<Vec<_>>();
+            handles
+                .into_iter()
+                .try_for_each(|h| h.join().expect("Thread panicked"))
+        })?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn array_pool() -> anyhow::Result<()> {
+        let test_array = quickstart_array("array_pool")?;
+        let pool = ArrayPool::with_capacity(
+            &test_array.context,
+            &test_array.uri,
+            NTHREADS,
+        );
+
+        std::thread::scope(|s| {
+            let handles = (0..NTHREADS)
+                .map(|_| {
+                    s.spawn(|| -> TileDBResult<()> {
+                        for _ in 0..NITERATIONS {
+                            let array = pool.get()?.into_inner();
+                            let mut q = ReadBuilder::new(array)?
+                                .register_constructor::<_, Vec<i32>>(
+                                    "a",
+                                    Default::default(),
+                                )?
+                                .build();
+                            let (a, _) = q.execute()?;
+                            assert_eq!(vec![10, 11, 12, 13], a);
+                            pool.put(q.finalize()?);
+                        }
+                        Ok(())
+                    })
+                })
+                .collect::<Vec<_>>();
+            handles
+                .into_iter()
+                .try_for_each(|h| h.join().expect("Thread panicked"))
+        })?;
+
+        assert!(pool.num_idle() > 0);
+        assert!(pool.num_idle() <= NTHREADS);
+
+        {
+            let array = pool.get()?;
+            assert_eq!(test_array.uri, array.uri());
+        }
+        assert!(pool.num_idle() > 0);
+
+        Ok(())
+    }
+
+    #[test]
+    fn array_pool_put() -> anyhow::Result<()> {
+        let mut test_array = quickstart_array("array_pool_put")?;
+        let pool = ArrayPool::new(&test_array.context, &test_array.uri);
+
+        // arrays which are not open for reading are closed
+        pool.put(test_array.for_write()?);
+        pool.put(Array::open(
+            &test_array.context,
+            &test_array.uri,
+            Mode::Delete,
+        )?);
+        assert_eq!(0, pool.num_idle());
+
+        pool.put(test_array.for_read()?);
+        assert_eq!(1, pool.num_idle());
+        assert_eq!(Mode::Read, pool.get()?.mode()?);
+
+        Ok(())
+    }
+}
@@ -1,6 +1,8 @@
 use std::ops::Deref;
 
-use crate::array::{Attribute, Enumeration, ExtendedEnumeration};
+use crate::array::{
+    Attribute, CurrentDomain, Enumeration, ExtendedEnumeration,
+};
 use crate::{Context, ContextBound, Result as TileDBResult};
 
 enum RawSchemaEvolution {
@@ -157,6 +159,28 @@ impl Builder {
         Ok(self)
     }
 
+    /// Expands the current domain of the target array.
+    ///
+    /// The new current domain must contain the existing current domain
+    /// and must be contained within the array domain.
+    pub fn expand_current_domain(
+        self,
+        current_domain: CurrentDomain,
+    ) -> TileDBResult<Self> {
+        let c_evolution = *self.inner.raw;
+        let c_current_domain = current_domain.capi();
+
+        self.context().capi_call(|ctx| unsafe {
+            ffi::tiledb_array_schema_evolution_expand_current_domain(
+                ctx,
+                c_evolution,
+                c_current_domain,
+            )
+        })?;
+
+        Ok(self)
+    }
+
     /// Sets the timestamp for the evolved schema.
     pub fn timestamp_range(self, t: u64) -> TileDBResult<Self> {
         let c_evolution = *self.inner.raw;
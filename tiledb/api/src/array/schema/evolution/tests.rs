@@ -2,12 +2,16 @@ use super::*;
 
 use tiledb_common::array::{ArrayType, CellValNum};
 use tiledb_common::Datatype;
-use tiledb_pod::array::{AttributeData, EnumerationData};
+use tiledb_pod::array::schema::SchemaData;
+use tiledb_pod::array::{
+    AttributeData, CurrentDomainData, EnumerationData, NDRectangleData,
+};
 use tiledb_utils::assert_option_subset;
 
 use crate::array::schema::EnumerationKey;
-use crate::array::Array;
+use crate::array::{Array, CurrentDomain};
 use crate::error::Error;
+use crate::range::{Range, SingleValueRange};
 use crate::tests::examples::{quickstart, TestArray};
 use crate::Factory;
 
@@ -259,3 +263,59 @@ fn drop_enumeration() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn expand_current_domain() -> anyhow::Result<()> {
+    let current_domain = |rows: [i32; 2], cols: [i32; 2]| {
+        CurrentDomainData::from(NDRectangleData {
+            dimension: vec![
+                Range::Single(SingleValueRange::from(&rows)),
+                Range::Single(SingleValueRange::from(&cols)),
+            ],
+        })
+    };
+
+    let array = TestArray::new("expand_current_domain", {
+        let mut b = quickstart::Builder::new(ArrayType::Sparse);
+        b.schema.current_domain = Some(current_domain([1, 2], [1, 2]));
+        b.build().into()
+    })?;
+
+    let old_schema = array.for_read()?.schema()?;
+    assert_eq!(
+        Some(current_domain([1, 2], [1, 2])),
+        SchemaData::try_from(&old_schema)?.current_domain
+    );
+
+    let expanded = CurrentDomain::from_data(
+        &array.context,
+        old_schema.domain()?,
+        &current_domain([1, 4], [1, 3]),
+    )?;
+    let evolution = Builder::new(&array.context)?
+        .expand_current_domain(expanded)?
+        .build();
+
+    Array::evolve(&array.context, &array.uri, evolution)?;
+
+    let new_schema = array.for_read()?.schema()?;
+    assert_eq!(
+        Some(current_domain([1, 4], [1, 3])),
+        SchemaData::try_from(&new_schema)?.current_domain
+    );
+
+    // the current domain cannot shrink
+    let shrunk = CurrentDomain::from_data(
+        &array.context,
+        new_schema.domain()?,
+        &current_domain([1, 1], [1, 1]),
+    )?;
+    let evolution = Builder::new(&array.context)?
+        .expand_current_domain(shrunk)?
+        .build();
+
+    let evolve = Array::evolve(&array.context, &array.uri, evolution);
+    assert!(matches!(evolve, Err(Error::LibTileDB(_))));
+
+    Ok(())
+}
@@ -11,10 +11,12 @@ use anyhow::anyhow;
 use itertools::Itertools;
 
 use crate::array::attribute::RawAttribute;
+use crate::array::current_domain::{CurrentDomain, RawCurrentDomain};
 use crate::array::dimension::Dimension;
+use crate::array::dimension_label::{DimensionLabel, RawDimensionLabel};
 use crate::array::domain::RawDomain;
 use crate::array::enumeration::{Enumeration, RawEnumeration};
-use crate::array::{Attribute, CellOrder, Domain, TileOrder};
+use crate::array::{Attribute, CellOrder, DataOrder, Domain, TileOrder};
 use crate::context::{CApiInterface, Context, ContextBound};
 use crate::error::Error;
 use crate::filter::list::{FilterList, RawFilterList};
@@ -32,6 +34,11 @@ pub(crate) enum RawSchema {
     Owned(*mut ffi::tiledb_array_schema_t),
 }
 
+// SAFETY: libtiledb only reads from a schema once it has been built,
+// and `Builder` owns the schema while it is being modified.
+unsafe impl Send for RawSchema {}
+unsafe impl Sync for RawSchema {}
+
 impl Deref for RawSchema {
     type Target = *mut ffi::tiledb_array_schema_t;
 
@@ -171,6 +178,27 @@ impl Schema {
         Ok(Domain::new(&self.context, RawDomain::Owned(c_domain)))
     }
 
+    /// Returns the current domain of this schema.
+    ///
+    /// The current domain is empty if it has not been set.
+    pub fn current_domain(&self) -> TileDBResult<CurrentDomain> {
+        let c_schema = *self.raw;
+        let mut c_current_domain: *mut ffi::tiledb_current_domain_t =
+            out_ptr!();
+        self.capi_call(|ctx| unsafe {
+            ffi::tiledb_array_schema_get_current_domain(
+                ctx,
+                c_schema,
+                &mut c_current_domain,
+            )
+        })?;
+
+        Ok(CurrentDomain::new(
+            &self.context,
+            RawCurrentDomain::Owned(c_current_domain),
+        ))
+    }
+
     /// Retrieve the schema of an array from storage
     pub fn load<S>(context: &Context, uri: S) -> TileDBResult<Self>
     where
@@ -322,6 +350,26 @@ impl Schema {
         Ok(Attribute::new(&self.context, RawAttribute::Owned(c_attr)))
     }
 
+    /// Returns whether this schema has an attribute with the given name.
+    pub fn has_attribute<S>(&self, name: S) -> TileDBResult<bool>
+    where
+        S: AsRef<str>,
+    {
+        let c_schema = *self.raw;
+        let c_name = cstring!(name.as_ref());
+        let mut c_has: i32 = out_ptr!();
+        self.capi_call(|ctx| unsafe {
+            ffi::tiledb_array_schema_has_attribute(
+                ctx,
+                c_schema,
+                c_name.as_ptr(),
+                &mut c_has,
+            )
+        })?;
+
+        Ok(c_has != 0)
+    }
+
     /// Returns an [Iterator] over the attributes of this schema.
     pub fn attributes(
         &self,
@@ -329,6 +377,85 @@ impl Schema {
         Ok((0..self.num_attributes()?).map(|a| self.attribute(a)))
     }
 
+    /// Returns the number of dimension labels in this schema.
+    pub fn num_dimension_labels(&self) -> TileDBResult<usize> {
+        let c_schema = *self.raw;
+        let mut c_nlabels: u64 = out_ptr!();
+        self.capi_call(|ctx| unsafe {
+            ffi::tiledb_array_schema_get_dimension_label_num(
+                ctx,
+                c_schema,
+                &mut c_nlabels,
+            )
+        })?;
+        Ok(c_nlabels as usize)
+    }
+
+    pub fn dimension_label<K: Into<LookupKey>>(
+        &self,
+        key: K,
+    ) -> TileDBResult<DimensionLabel> {
+        let c_schema = *self.raw;
+        let mut c_label: *mut ffi::tiledb_dimension_label_t = out_ptr!();
+
+        match key.into() {
+            LookupKey::Index(idx) => {
+                self.capi_call(|ctx| unsafe {
+                    ffi::tiledb_array_schema_get_dimension_label_from_index(
+                        ctx,
+                        c_schema,
+                        idx as u64,
+                        &mut c_label,
+                    )
+                })?;
+            }
+            LookupKey::Name(name) => {
+                let c_name = cstring!(name);
+                self.capi_call(|ctx| unsafe {
+                    ffi::tiledb_array_schema_get_dimension_label_from_name(
+                        ctx,
+                        c_schema,
+                        c_name.as_ptr(),
+                        &mut c_label,
+                    )
+                })?;
+            }
+        }
+
+        Ok(DimensionLabel::new(
+            &self.context,
+            RawDimensionLabel::Owned(c_label),
+        ))
+    }
+
+    /// Returns whether this schema has a dimension label with the given name.
+    pub fn has_dimension_label<S>(&self, name: S) -> TileDBResult<bool>
+    where
+        S: AsRef<str>,
+    {
+        let c_schema = *self.raw;
+        let c_name = cstring!(name.as_ref());
+        let mut c_has: i32 = out_ptr!();
+        self.capi_call(|ctx| unsafe {
+            ffi::tiledb_array_schema_has_dimension_label(
+                ctx,
+                c_schema,
+                c_name.as_ptr(),
+                &mut c_has,
+            )
+        })?;
+
+        Ok(c_has != 0)
+    }
+
+    /// Returns an [Iterator] over the dimension labels of this schema.
+    pub fn dimension_labels(
+        &self,
+    ) -> TileDBResult<impl Iterator<Item = TileDBResult<DimensionLabel>> + '_>
+    {
+        Ok((0..self.num_dimension_labels()?).map(|l| self.dimension_label(l)))
+    }
+
     pub fn num_fields(&self) -> TileDBResult<usize> {
         Ok(self.domain()?.num_dimensions()? + self.num_attributes()?)
     }
@@ -464,6 +591,12 @@ impl PartialEq<Schema> for Schema {
         }
 
         eq_helper!(self.domain(), other.domain());
+        eq_helper!(self.current_domain(), other.current_domain());
+
+        eq_helper!(self.num_dimension_labels(), other.num_dimension_labels());
+        for l in 0..self.num_dimension_labels().unwrap() {
+            eq_helper!(self.dimension_label(l), other.dimension_label(l));
+        }
 
         true
     }
@@ -654,6 +787,87 @@ impl Builder {
         Ok(self)
     }
 
+    /// Adds a label named `name` to a dimension of the schema.
+    ///
+    /// The label values have type `datatype` and must be written
+    /// in the order given by `order`.
+    pub fn add_dimension_label<K>(
+        self,
+        dimension: K,
+        name: &str,
+        order: DataOrder,
+        datatype: Datatype,
+    ) -> TileDBResult<Self>
+    where
+        K: Into<LookupKey>,
+    {
+        let dim_idx = self.schema.domain()?.dimension_index(dimension)?;
+        let c_dim_idx: u32 = dim_idx.try_into().map_err(
+            |e: <usize as TryInto<u32>>::Error| {
+                Error::InvalidArgument(anyhow!(e))
+            },
+        )?;
+
+        let c_schema = self.schema.capi();
+        let c_name = cstring!(name);
+        let c_order = ffi::tiledb_data_order_t::from(order);
+        let c_datatype = ffi::tiledb_datatype_t::from(datatype);
+        self.capi_call(|ctx| unsafe {
+            ffi::tiledb_array_schema_add_dimension_label(
+                ctx,
+                c_schema,
+                c_dim_idx,
+                c_name.as_ptr(),
+                c_order,
+                c_datatype,
+            )
+        })?;
+        Ok(self)
+    }
+
+    /// Sets the filters applied to the values of the dimension label `name`.
+    pub fn dimension_label_filters<FL>(
+        self,
+        name: &str,
+        filters: FL,
+    ) -> TileDBResult<Self>
+    where
+        FL: Borrow<FilterList>,
+    {
+        let c_schema = self.schema.capi();
+        let c_name = cstring!(name);
+        let filters = filters.borrow();
+        self.capi_call(|ctx| unsafe {
+            ffi::tiledb_array_schema_set_dimension_label_filter_list(
+                ctx,
+                c_schema,
+                c_name.as_ptr(),
+                filters.capi(),
+            )
+        })?;
+        Ok(self)
+    }
+
+    /// Sets the current domain of the schema.
+    ///
+    /// The current domain must be set after the domain,
+    /// and must be contained within it.
+    pub fn current_domain(
+        self,
+        current_domain: CurrentDomain,
+    ) -> TileDBResult<Self> {
+        let c_schema = self.schema.capi();
+        let c_current_domain = current_domain.capi();
+        self.capi_call(|ctx| unsafe {
+            ffi::tiledb_array_schema_set_current_domain(
+                ctx,
+                c_schema,
+                c_current_domain,
+            )
+        })?;
+        Ok(self)
+    }
+
     fn filter_list<FL>(
         self,
         filters: FL,
@@ -1514,6 +1728,136 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_current_domain() -> TileDBResult<()> {
+        use tiledb_pod::array::{CurrentDomainData, NDRectangleData};
+
+        use crate::range::{Range, SingleValueRange, VarValueRange};
+
+        let ctx = Context::new().unwrap();
+
+        let mut spec = SchemaData {
+            array_type: ArrayType::Sparse,
+            domain: DomainData {
+                dimension: vec![
+                    DimensionData {
+                        name: "id".to_string(),
+                        datatype: Datatype::Int64,
+                        constraints: DimensionConstraints::Int64(
+                            [0, 1_000_000],
+                            None,
+                        ),
+                        filters: None,
+                    },
+                    DimensionData {
+                        name: "name".to_string(),
+                        datatype: Datatype::StringAscii,
+                        constraints: DimensionConstraints::StringAscii,
+                        filters: None,
+                    },
+                ],
+            },
+            attributes: vec![AttributeData {
+                name: "a".to_string(),
+                datatype: Datatype::Int32,
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+
+        // no current domain
+        {
+            let schema = spec.create(&ctx)?;
+            assert!(schema.current_domain()?.is_empty()?);
+            assert_eq!(None, SchemaData::try_from(&schema)?.current_domain);
+        }
+
+        // current domain round trips
+        {
+            spec.current_domain =
+                Some(CurrentDomainData::from(NDRectangleData {
+                    dimension: vec![
+                        Range::Single(SingleValueRange::Int64(0, 1000)),
+                        Range::Var(VarValueRange::from(("a", "z"))),
+                    ],
+                }));
+
+            let schema = spec.create(&ctx)?;
+            assert!(!schema.current_domain()?.is_empty()?);
+            assert_option_subset!(spec, SchemaData::try_from(&schema)?);
+
+            let other = spec.create(&ctx)?;
+            assert_eq!(schema, other);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_dimension_labels() -> TileDBResult<()> {
+        use tiledb_pod::array::DimensionLabelData;
+
+        let ctx = Context::new().unwrap();
+
+        let spec = SchemaData {
+            array_type: ArrayType::Dense,
+            domain: DomainData {
+                dimension: vec![DimensionData {
+                    name: "offset".to_string(),
+                    datatype: Datatype::UInt64,
+                    constraints: DimensionConstraints::UInt64(
+                        [0, 1000],
+                        Some(100),
+                    ),
+                    filters: None,
+                }],
+            },
+            dimension_labels: vec![
+                DimensionLabelData {
+                    name: "timestamp".to_string(),
+                    dimension: 0,
+                    order: DataOrder::Increasing,
+                    datatype: Datatype::DateTimeMillisecond,
+                    filters: None,
+                },
+                DimensionLabelData {
+                    name: "elevation".to_string(),
+                    dimension: 0,
+                    order: DataOrder::Unordered,
+                    datatype: Datatype::Float64,
+                    filters: Some(vec![FilterData::Compression(
+                        CompressionData::new(CompressionType::Zstd),
+                    )]),
+                },
+            ],
+            attributes: vec![AttributeData {
+                name: "a".to_string(),
+                datatype: Datatype::Int32,
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+
+        let schema = spec.create(&ctx)?;
+        assert_eq!(2, schema.num_dimension_labels()?);
+        assert!(schema.has_dimension_label("timestamp")?);
+        assert!(schema.has_dimension_label("elevation")?);
+        assert!(!schema.has_dimension_label("offset")?);
+
+        let timestamp = schema.dimension_label("timestamp")?;
+        assert_eq!(0, timestamp.dimension_index()?);
+        assert_eq!(Datatype::DateTimeMillisecond, timestamp.datatype()?);
+        assert_eq!(CellValNum::single(), timestamp.cell_val_num()?);
+        assert_eq!(DataOrder::Increasing, timestamp.order()?);
+
+        // label filters cannot be read back from the schema
+        let mut expect = spec.clone();
+        expect.dimension_labels[1].filters = None;
+        assert_option_subset!(expect, SchemaData::try_from(&schema)?);
+
+        Ok(())
+    }
+
     /// Creates a schema with a single dimension of the given `Datatype` with one attribute.
     /// Used by the test to check if the `Datatype` can be used in this way.
     fn dimension_comprehensive_schema(
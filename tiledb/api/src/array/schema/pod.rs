@@ -1,10 +1,13 @@
 use itertools::Itertools;
 use tiledb_common::filter::FilterData;
 use tiledb_pod::array::attribute::AttributeData;
+use tiledb_pod::array::current_domain::CurrentDomainData;
+use tiledb_pod::array::dimension_label::DimensionLabelData;
 use tiledb_pod::array::schema::{FieldData, SchemaData};
 use tiledb_pod::array::{DimensionData, DomainData, EnumerationData};
 
 use super::{Builder, EnumerationKey, Field, Schema};
+use crate::array::current_domain::CurrentDomain;
 use crate::error::Error;
 use crate::{Context, Factory, Result as TileDBResult};
 
@@ -28,9 +31,25 @@ impl TryFrom<&Schema> for SchemaData {
             })
             .collect::<TileDBResult<Vec<EnumerationData>>>()?;
 
+        let current_domain = {
+            let current_domain = schema.current_domain()?;
+            if current_domain.is_empty()? {
+                None
+            } else {
+                Some(CurrentDomainData::try_from(&current_domain)?)
+            }
+        };
+
+        let dimension_labels = schema
+            .dimension_labels()?
+            .map(|l| DimensionLabelData::try_from(&l?))
+            .collect::<TileDBResult<Vec<DimensionLabelData>>>()?;
+
         Ok(SchemaData {
             array_type: schema.array_type()?,
             domain: DomainData::try_from(&schema.domain()?)?,
+            current_domain,
+            dimension_labels,
             capacity: Some(schema.capacity()?),
             cell_order: Some(schema.cell_order()?),
             tile_order: Some(schema.tile_order()?),
@@ -71,6 +90,21 @@ impl Factory for SchemaData {
         .offsets_filters(self.offsets_filters.create(context)?)?
         .nullity_filters(self.nullity_filters.create(context)?)?;
 
+        for l in self.dimension_labels.iter() {
+            b = b.add_dimension_label(
+                l.dimension,
+                &l.name,
+                l.order,
+                l.datatype,
+            )?;
+            if let Some(filters) = l.filters.as_ref() {
+                b = b.dimension_label_filters(
+                    &l.name,
+                    filters.create(context)?,
+                )?;
+            }
+        }
+
         b = self
             .enumerations
             .iter()
@@ -93,6 +127,12 @@ impl Factory for SchemaData {
         if let Some(o) = self.tile_order {
             b = b.tile_order(o)?;
         }
+        if let Some(c) = self.current_domain.as_ref() {
+            let domain = b.schema.domain()?;
+            b = b.current_domain(CurrentDomain::from_data(
+                context, domain, c,
+            )?)?;
+        }
 
         b.build()
     }
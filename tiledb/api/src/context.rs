@@ -1,7 +1,7 @@
 use std::convert::From;
 use std::fmt::{Debug, Display, Formatter, Result as FmtResult};
 use std::ops::Deref;
-use std::rc::Rc;
+use std::sync::Arc;
 
 use crate::config::{Config, RawConfig};
 use crate::filesystem::Filesystem;
@@ -75,6 +75,12 @@ pub(crate) struct RawContext {
     raw: *mut ffi::tiledb_ctx_t,
 }
 
+// SAFETY: libtiledb contexts are thread-safe. Their thread pools,
+// caches and storage managers are shared by all of the threads
+// which use them.
+unsafe impl Send for RawContext {}
+unsafe impl Sync for RawContext {}
+
 impl Drop for RawContext {
     fn drop(&mut self) {
         unsafe {
@@ -109,9 +115,14 @@ where
     }
 }
 
+/// Shared state for interacting with libtiledb.
+///
+/// A `Context` can be shared by any number of threads. Errors are
+/// reported through the context, so if several threads encounter errors
+/// at the same time then the messages which they report may be mixed up.
 #[derive(Clone)]
 pub struct Context {
-    raw: Rc<RawContext>,
+    raw: Arc<RawContext>,
 }
 
 impl Context {
@@ -125,7 +136,7 @@ impl Context {
         let res = unsafe { ffi::tiledb_ctx_alloc(cfg.capi(), &mut c_ctx) };
         match res {
             ffi::TILEDB_OK => Ok(Context {
-                raw: Rc::new(RawContext { raw: c_ctx }),
+                raw: Arc::new(RawContext { raw: c_ctx }),
             }),
             ffi::TILEDB_OOM => Err(CreateContextError::OutOfMemory),
             ffi::TILEDB_ERR => Err(CreateContextError::Fatal),
@@ -135,6 +146,10 @@ impl Context {
         }
     }
 
+    pub(crate) fn capi(&self) -> *mut ffi::tiledb_ctx_t {
+        self.raw.raw
+    }
+
     pub fn capi_call<Callable>(&self, action: Callable) -> CApiResult<()>
     where
         Callable: FnOnce(*mut ffi::tiledb_ctx_t) -> i32,
@@ -164,6 +179,14 @@ impl Context {
         Ok(String::from(json.to_string_lossy()))
     }
 
+    /// Cancels all of the in-progress tasks of this context,
+    /// such as queries which are currently being submitted.
+    /// The cancelled operations return an error.
+    pub fn cancel_tasks(&self) -> TileDBResult<()> {
+        self.capi_call(|ctx| unsafe { ffi::tiledb_ctx_cancel_tasks(ctx) })?;
+        Ok(())
+    }
+
     pub fn get_config(&self) -> TileDBResult<Config> {
         let mut c_cfg: *mut ffi::tiledb_config_t = out_ptr!();
         self.capi_call(|ctx| unsafe {
@@ -297,6 +320,12 @@ mod tests {
         assert!(ctx.get_last_error().is_none());
     }
 
+    #[test]
+    fn ctx_cancel_tasks() {
+        let ctx = Context::new().expect("Error creating instance.");
+        ctx.cancel_tasks().expect("Error cancelling tasks.");
+    }
+
     #[test]
     fn ctx_is_supported_fs() {
         let ctx = Context::new().expect("Error creating instance.");
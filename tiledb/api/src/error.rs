@@ -53,6 +53,8 @@ pub enum Error {
     CellOrder(#[from] tiledb_common::array::CellOrderError),
     #[error("TileOrder error: {0}")]
     TileOrder(#[from] tiledb_common::array::TileOrderError),
+    #[error("DataOrder error: {0}")]
+    DataOrder(#[from] tiledb_common::array::DataOrderError),
     #[error("FilterType error: {0}")]
     FilterType(#[from] crate::filter::FilterTypeError),
     #[error("FilterOption error: {0}")]
@@ -82,6 +84,9 @@ pub enum Error {
     },
     #[error("Unexpected null values")]
     UnexpectedValidity,
+    /// Error converting a metadata value into a requested type
+    #[error("Metadata value error: {0}")]
+    MetadataValue(#[from] tiledb_common::metadata::FromValueError),
     /// Error serializing data
     #[error("Serialization error: {0}: {1}")]
     Serialization(String, #[source] anyhow::Error),
@@ -92,11 +97,23 @@ pub enum Error {
     #[error("Query callback error for attribute [{}]: {1}",
         .0.iter().map(|s| s.as_ref()).collect::<Vec<&str>>().join(","))]
     QueryCallback(Vec<String>, #[source] anyhow::Error),
+    /// An operation was cancelled, e.g. because a query ran past its deadline.
+    /// Cancellation stops all of the tasks of a context, so a query which
+    /// may be cancelled should use a dedicated [Context](crate::Context)
+    /// in order not to abort unrelated queries.
+    #[error("Operation cancelled")]
+    Cancelled,
     /// Any error which cannot be categorized as any of the above
     #[error("{0}")]
     Other(String),
 }
 
+impl From<Error> for std::io::Error {
+    fn from(value: Error) -> Self {
+        std::io::Error::other(value)
+    }
+}
+
 #[cfg(feature = "serde")]
 impl Serialize for Error {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
@@ -10,6 +10,10 @@ pub(crate) enum RawFilterList {
     Owned(*mut ffi::tiledb_filter_list_t),
 }
 
+// SAFETY: see "Thread safety of handles" in the crate root.
+unsafe impl Send for RawFilterList {}
+unsafe impl Sync for RawFilterList {}
+
 impl Deref for RawFilterList {
     type Target = *mut ffi::tiledb_filter_list_t;
     fn deref(&self) -> &Self::Target {
@@ -25,6 +25,10 @@ pub(crate) enum RawFilter {
     Owned(*mut ffi::tiledb_filter_t),
 }
 
+// SAFETY: filters are only modified while they are being created.
+unsafe impl Send for RawFilter {}
+unsafe impl Sync for RawFilter {}
+
 impl Deref for RawFilter {
     type Target = *mut ffi::tiledb_filter_t;
     fn deref(&self) -> &Self::Target {
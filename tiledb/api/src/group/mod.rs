@@ -1,3 +1,5 @@
+mod tree;
+
 use std::ops::Deref;
 
 use crate::config::{Config, RawConfig};
@@ -11,6 +13,8 @@ use crate::string::{RawTDBString, TDBString};
 use crate::Datatype;
 use crate::Result as TileDBResult;
 
+pub use self::tree::{GroupTree, GroupTreeMember};
+
 pub type QueryType = crate::array::Mode;
 
 #[derive(Clone, Debug, PartialEq)]
@@ -271,6 +275,22 @@ impl Group {
         })
     }
 
+    /// Returns an iterator over the members of this group.
+    pub fn members(
+        &self,
+    ) -> TileDBResult<impl Iterator<Item = TileDBResult<GroupInfo>> + '_> {
+        Ok((0..self.num_members()?)
+            .map(|m| self.member(LookupKey::Index(m as usize))))
+    }
+
+    /// Recursively opens the members of this group and returns
+    /// the structure of the group and all of its descendants.
+    ///
+    /// See [GroupTree].
+    pub fn tree(&self) -> TileDBResult<GroupTree> {
+        GroupTree::from_group(self)
+    }
+
     pub fn is_relative_uri<S>(&self, name: S) -> TileDBResult<bool>
     where
         S: AsRef<str>,
@@ -612,6 +632,54 @@ mod tests {
         test_uri.close().map_err(|e| Error::Other(e.to_string()))
     }
 
+    #[test]
+    fn test_group_metadata_logical_types() -> TileDBResult<()> {
+        let test_uri = uri::get_uri_generator()
+            .map_err(|e| Error::Other(e.to_string()))?;
+
+        let tdb = Context::new()?;
+        let group_uri = test_uri
+            .with_path("group_logical_metadata")
+            .map_err(|e| Error::Other(e.to_string()))?;
+        Group::create(&tdb, &group_uri)?;
+
+        let metadata = vec![
+            Metadata::string("name".to_owned(), "hello, world"),
+            Metadata::new(
+                "ascii".to_owned(),
+                Datatype::StringAscii,
+                b"abc".to_vec(),
+            )?,
+            Metadata::blob("blob".to_owned(), vec![0, 255, 1]),
+            Metadata::boolean("flags".to_owned(), vec![true, false, true]),
+        ];
+        {
+            let mut group_write =
+                Group::open(&tdb, &group_uri, QueryType::Write, None)?;
+            for m in metadata.iter() {
+                group_write.put_metadata(m.clone())?;
+            }
+        }
+
+        {
+            let group_read =
+                Group::open(&tdb, &group_uri, QueryType::Read, None)?;
+            for m in metadata.iter() {
+                assert_eq!(*m, group_read.metadata(m.key.as_str())?);
+            }
+            assert_eq!(
+                metadata::Value::StringValue("abc".to_owned()),
+                group_read.metadata("ascii")?.value
+            );
+            assert_eq!(
+                Some(Datatype::Boolean),
+                group_read.has_metadata_key("flags")?
+            );
+        }
+
+        Ok(())
+    }
+
     fn create_array<S>(array_uri: S, array_type: ArrayType) -> TileDBResult<()>
     where
         S: AsRef<str>,
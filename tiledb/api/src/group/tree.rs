@@ -0,0 +1,255 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use super::{Group, QueryType};
+use crate::array::{ArrayType, Schema};
+use crate::context::{Context, ContextBound, ObjectType};
+use crate::Result as TileDBResult;
+
+/// The structure of a group and all of its descendants.
+///
+/// This is the structured equivalent of [Group::dump] with `recursive`
+/// set to `true`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub struct GroupTree {
+    /// Name of the group within its parent, or the last component
+    /// of its URI if it is the root of the tree.
+    pub name: String,
+    pub uri: String,
+    pub members: Vec<GroupTreeMember>,
+}
+
+/// A member of a [GroupTree].
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub enum GroupTreeMember {
+    Array {
+        name: String,
+        uri: String,
+        array_type: ArrayType,
+    },
+    Group(GroupTree),
+    /// A group which is also an ancestor of this member.
+    /// Its members are not listed again.
+    Cycle {
+        name: String,
+        uri: String,
+    },
+}
+
+impl GroupTreeMember {
+    pub fn name(&self) -> &str {
+        match self {
+            Self::Array { name, .. } => name,
+            Self::Group(tree) => &tree.name,
+            Self::Cycle { name, .. } => name,
+        }
+    }
+
+    pub fn uri(&self) -> &str {
+        match self {
+            Self::Array { uri, .. } => uri,
+            Self::Group(tree) => &tree.uri,
+            Self::Cycle { uri, .. } => uri,
+        }
+    }
+}
+
+impl GroupTree {
+    /// Opens the group at `uri` and returns its tree.
+    pub fn load<S>(context: &Context, uri: S) -> TileDBResult<Self>
+    where
+        S: AsRef<str>,
+    {
+        Self::from_group(&Group::open(context, uri, QueryType::Read, None)?)
+    }
+
+    /// Returns the tree of an open `group`.
+    pub fn from_group(group: &Group) -> TileDBResult<Self> {
+        let uri = group.uri()?;
+        let name = uri
+            .trim_end_matches('/')
+            .rsplit('/')
+            .next()
+            .unwrap_or(&uri)
+            .to_owned();
+        let mut ancestors = vec![];
+        Self::walk(group, name, uri, &mut ancestors)
+    }
+
+    fn walk(
+        group: &Group,
+        name: String,
+        uri: String,
+        ancestors: &mut Vec<String>,
+    ) -> TileDBResult<Self> {
+        let context = group.context();
+        ancestors.push(uri.trim_end_matches('/').to_owned());
+
+        let mut members = vec![];
+        for member in group.members()? {
+            let member = member?;
+            let tree_member = match member.group_type {
+                ObjectType::Array => GroupTreeMember::Array {
+                    array_type: Schema::load(&context, &member.uri)?
+                        .array_type()?,
+                    name: member.name,
+                    uri: member.uri,
+                },
+                ObjectType::Group => {
+                    if ancestors
+                        .iter()
+                        .any(|a| a == member.uri.trim_end_matches('/'))
+                    {
+                        GroupTreeMember::Cycle {
+                            name: member.name,
+                            uri: member.uri,
+                        }
+                    } else {
+                        let child = Group::open(
+                            &context,
+                            &member.uri,
+                            QueryType::Read,
+                            None,
+                        )?;
+                        GroupTreeMember::Group(Self::walk(
+                            &child,
+                            member.name,
+                            member.uri,
+                            ancestors,
+                        )?)
+                    }
+                }
+            };
+            members.push(tree_member);
+        }
+
+        ancestors.pop();
+        Ok(GroupTree { name, uri, members })
+    }
+
+    /// Returns an iterator over all of the members of this tree,
+    /// depth-first, paired with their depth below the root.
+    pub fn descendants(
+        &self,
+    ) -> impl Iterator<Item = (usize, &GroupTreeMember)> {
+        let mut stack = self
+            .members
+            .iter()
+            .rev()
+            .map(|m| (1, m))
+            .collect::<Vec<_>>();
+        std::iter::from_fn(move || {
+            let (depth, member) = stack.pop()?;
+            if let GroupTreeMember::Group(tree) = member {
+                let children = tree.members.iter().rev();
+                stack.extend(children.map(|m| (depth + 1, m)));
+            }
+            Some((depth, member))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::array::Array;
+    use crate::error::Error;
+    use crate::tests::examples::quickstart::Builder as QuickstartBuilder;
+    use crate::Factory;
+    use uri::TestArrayUri;
+
+    #[test]
+    fn group_tree() -> TileDBResult<()> {
+        let test_uri = uri::get_uri_generator()
+            .map_err(|e| Error::Other(e.to_string()))?;
+        let ctx = Context::new()?;
+
+        let root_uri = test_uri
+            .with_path("root")
+            .map_err(|e| Error::Other(e.to_string()))?;
+        let child_uri = format!("{}/child", root_uri);
+        Group::create(&ctx, &root_uri)?;
+        Group::create(&ctx, &child_uri)?;
+
+        let dense_uri = format!("{}/dense", root_uri);
+        let sparse_uri = format!("{}/sparse", child_uri);
+        Array::create(
+            &ctx,
+            &dense_uri,
+            QuickstartBuilder::new(ArrayType::Dense)
+                .build()
+                .create(&ctx)?,
+        )?;
+        Array::create(
+            &ctx,
+            &sparse_uri,
+            QuickstartBuilder::new(ArrayType::Sparse)
+                .build()
+                .create(&ctx)?,
+        )?;
+
+        {
+            let mut root =
+                Group::open(&ctx, &root_uri, QueryType::Write, None)?;
+            root.add_member("child", true, Some("child"))?;
+            root.add_member("dense", true, Some("dense"))?;
+        }
+        {
+            let mut child =
+                Group::open(&ctx, &child_uri, QueryType::Write, None)?;
+            child.add_member(&root_uri, false, Some("parent"))?;
+            child.add_member("sparse", true, Some("sparse"))?;
+        }
+
+        let root = Group::open(&ctx, &root_uri, QueryType::Read, None)?;
+        let members = root.members()?.collect::<TileDBResult<Vec<_>>>()?;
+        assert_eq!(
+            vec!["child", "dense"],
+            members.iter().map(|m| m.name.as_str()).collect::<Vec<_>>()
+        );
+        assert_eq!(ObjectType::Group, members[0].group_type);
+        assert_eq!(ObjectType::Array, members[1].group_type);
+
+        let expect = GroupTree {
+            name: "root".to_owned(),
+            uri: root_uri.clone(),
+            members: vec![
+                GroupTreeMember::Group(GroupTree {
+                    name: "child".to_owned(),
+                    uri: child_uri.clone(),
+                    members: vec![
+                        GroupTreeMember::Cycle {
+                            name: "parent".to_owned(),
+                            uri: root_uri.clone(),
+                        },
+                        GroupTreeMember::Array {
+                            name: "sparse".to_owned(),
+                            uri: sparse_uri,
+                            array_type: ArrayType::Sparse,
+                        },
+                    ],
+                }),
+                GroupTreeMember::Array {
+                    name: "dense".to_owned(),
+                    uri: dense_uri,
+                    array_type: ArrayType::Dense,
+                },
+            ],
+        };
+        assert_eq!(expect, root.tree()?);
+        assert_eq!(expect, GroupTree::load(&ctx, &root_uri)?);
+
+        let descendants = expect
+            .descendants()
+            .map(|(depth, m)| (depth, m.name()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            vec![(1, "child"), (2, "parent"), (2, "sparse"), (1, "dense")],
+            descendants
+        );
+
+        Ok(())
+    }
+}
@@ -5,6 +5,20 @@ extern crate tiledb_sys as ffi;
 #[cfg(test)]
 extern crate tiledb_utils as utils;
 
+/*
+ * Thread safety of handles
+ *
+ * The TileDB C API is thread-safe: a handle may be used from any thread,
+ * and several threads may call functions which only read from the same
+ * handle at once. Functions which modify a handle must not run
+ * concurrently with other functions on that handle.
+ *
+ * Schema components (attributes, dimensions, domains, enumerations,
+ * filter lists, current domains and rectangles) are only modified by
+ * their builders, which own them exclusively. Once built, they are only
+ * read from, so their raw handles are `Send` and `Sync`.
+ */
+
 macro_rules! cstring {
     ($arg:expr) => {{
         let arg = $arg;
@@ -46,6 +60,7 @@ pub mod filesystem;
 pub mod filter;
 pub mod group;
 pub mod metadata;
+pub mod object;
 pub mod query;
 pub mod stats;
 pub mod string;
@@ -0,0 +1,376 @@
+use std::ops::{Deref, DerefMut};
+
+#[cfg(feature = "serde")]
+use anyhow::anyhow;
+#[cfg(feature = "serde")]
+use serde::{de::DeserializeOwned, Serialize};
+
+use super::{FromValueError, Metadata, Value};
+use crate::array::Array;
+use crate::group::Group;
+use crate::key::LookupKey;
+use crate::{Datatype, Result as TileDBResult};
+
+/// An object which holds metadata, i.e. an [Array] or a [Group].
+pub trait MetadataStore {
+    fn put_metadata(&mut self, metadata: Metadata) -> TileDBResult<()>;
+
+    fn delete_metadata(&mut self, key: &str) -> TileDBResult<()>;
+
+    fn num_metadata(&self) -> TileDBResult<u64>;
+
+    fn metadata(&self, key: LookupKey) -> TileDBResult<Metadata>;
+
+    fn has_metadata_key(&self, key: &str) -> TileDBResult<Option<Datatype>>;
+}
+
+impl MetadataStore for Array {
+    fn put_metadata(&mut self, metadata: Metadata) -> TileDBResult<()> {
+        Array::put_metadata(self, metadata)
+    }
+
+    fn delete_metadata(&mut self, key: &str) -> TileDBResult<()> {
+        Array::delete_metadata(self, key)
+    }
+
+    fn num_metadata(&self) -> TileDBResult<u64> {
+        Array::num_metadata(self)
+    }
+
+    fn metadata(&self, key: LookupKey) -> TileDBResult<Metadata> {
+        Array::metadata(self, key)
+    }
+
+    fn has_metadata_key(&self, key: &str) -> TileDBResult<Option<Datatype>> {
+        Array::has_metadata_key(self, key)
+    }
+}
+
+impl MetadataStore for Group {
+    fn put_metadata(&mut self, metadata: Metadata) -> TileDBResult<()> {
+        Group::put_metadata(self, metadata)
+    }
+
+    fn delete_metadata(&mut self, key: &str) -> TileDBResult<()> {
+        Group::delete_metadata(self, key)
+    }
+
+    fn num_metadata(&self) -> TileDBResult<u64> {
+        Group::num_metadata(self)
+    }
+
+    fn metadata(&self, key: LookupKey) -> TileDBResult<Metadata> {
+        Group::metadata(self, key)
+    }
+
+    fn has_metadata_key(&self, key: &str) -> TileDBResult<Option<Datatype>> {
+        Group::has_metadata_key(self, key)
+    }
+}
+
+/// A key-value view over the metadata of an [Array] or [Group].
+///
+/// Reading metadata requires the array or group to be open for reading,
+/// and `insert` and `remove` require it to be open for writing.
+pub struct MetadataMap<R> {
+    store: R,
+}
+
+impl<R> MetadataMap<R>
+where
+    R: Deref,
+    R::Target: MetadataStore,
+{
+    pub fn new(store: R) -> Self {
+        MetadataMap { store }
+    }
+
+    /// Returns the number of metadata entries.
+    pub fn len(&self) -> TileDBResult<usize> {
+        Ok(self.store.num_metadata()? as usize)
+    }
+
+    pub fn is_empty(&self) -> TileDBResult<bool> {
+        Ok(self.len()? == 0)
+    }
+
+    /// Returns whether there is a metadata entry with key `key`.
+    pub fn contains_key<S>(&self, key: S) -> TileDBResult<bool>
+    where
+        S: AsRef<str>,
+    {
+        Ok(self.store.has_metadata_key(key.as_ref())?.is_some())
+    }
+
+    /// Returns the datatype of the metadata entry with key `key`,
+    /// if there is one.
+    pub fn datatype<S>(&self, key: S) -> TileDBResult<Option<Datatype>>
+    where
+        S: AsRef<str>,
+    {
+        self.store.has_metadata_key(key.as_ref())
+    }
+
+    /// Returns the metadata entry with key `key`, if there is one.
+    pub fn get_metadata<S>(&self, key: S) -> TileDBResult<Option<Metadata>>
+    where
+        S: AsRef<str>,
+    {
+        let key = key.as_ref();
+        if self.store.has_metadata_key(key)?.is_none() {
+            return Ok(None);
+        }
+        self.store.metadata(LookupKey::from(key)).map(Some)
+    }
+
+    /// Returns the value of the metadata entry with key `key`,
+    /// converted into `T`, if there is one.
+    ///
+    /// Returns `Err` if the value cannot be converted into `T`.
+    pub fn get<T, S>(&self, key: S) -> TileDBResult<Option<T>>
+    where
+        T: TryFrom<Value, Error = FromValueError>,
+        S: AsRef<str>,
+    {
+        match self.get_metadata(key)? {
+            Some(metadata) => Ok(Some(T::try_from(metadata.value)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Returns the value of the metadata entry with key `key`,
+    /// deserialized from JSON, if there is one.
+    ///
+    /// See [MetadataMap::insert_json].
+    #[cfg(feature = "serde")]
+    pub fn get_json<T, S>(&self, key: S) -> TileDBResult<Option<T>>
+    where
+        T: DeserializeOwned,
+        S: AsRef<str>,
+    {
+        let key = key.as_ref();
+        let Some(json) = self.get::<String, _>(key)? else {
+            return Ok(None);
+        };
+        serde_json::from_str::<T>(&json).map(Some).map_err(|e| {
+            crate::error::Error::Deserialization(
+                format!("metadata '{}'", key),
+                anyhow!(e),
+            )
+        })
+    }
+
+    /// Returns an iterator over all of the metadata entries.
+    pub fn iter(
+        &self,
+    ) -> TileDBResult<impl Iterator<Item = TileDBResult<Metadata>> + '_> {
+        Ok((0..self.store.num_metadata()?)
+            .map(|m| self.store.metadata(LookupKey::Index(m as usize))))
+    }
+
+    /// Returns an iterator over the keys of all of the metadata entries.
+    pub fn keys(
+        &self,
+    ) -> TileDBResult<impl Iterator<Item = TileDBResult<String>> + '_> {
+        Ok(self.iter()?.map(|m| m.map(|m| m.key)))
+    }
+}
+
+impl<R> MetadataMap<R>
+where
+    R: DerefMut,
+    R::Target: MetadataStore,
+{
+    /// Writes a metadata entry with key `key` and value `value`.
+    /// The datatype of the entry is [Value::default_datatype].
+    ///
+    /// Use [MetadataMap::insert_metadata] to write an entry with
+    /// a different datatype.
+    pub fn insert<S, V>(&mut self, key: S, value: V) -> TileDBResult<()>
+    where
+        S: Into<String>,
+        V: Into<Value>,
+    {
+        let value = value.into();
+        self.insert_metadata(Metadata {
+            key: key.into(),
+            datatype: value.default_datatype(),
+            value,
+        })
+    }
+
+    /// Writes a metadata entry.
+    pub fn insert_metadata(&mut self, metadata: Metadata) -> TileDBResult<()> {
+        self.store.put_metadata(metadata)
+    }
+
+    /// Writes a metadata entry with key `key` whose value is
+    /// `value` serialized as a JSON string.
+    #[cfg(feature = "serde")]
+    pub fn insert_json<S, T>(&mut self, key: S, value: &T) -> TileDBResult<()>
+    where
+        S: Into<String>,
+        T: Serialize + ?Sized,
+    {
+        let key = key.into();
+        let json = serde_json::to_string(value).map_err(|e| {
+            crate::error::Error::Serialization(
+                format!("metadata '{}'", key),
+                anyhow!(e),
+            )
+        })?;
+        self.insert_metadata(Metadata::string(key, json))
+    }
+
+    /// Deletes the metadata entry with key `key`.
+    pub fn remove<S>(&mut self, key: S) -> TileDBResult<()>
+    where
+        S: AsRef<str>,
+    {
+        self.store.delete_metadata(key.as_ref())
+    }
+}
+
+impl Array {
+    /// Returns a [MetadataMap] for reading the metadata of this array.
+    pub fn metadata_map(&self) -> MetadataMap<&Self> {
+        MetadataMap::new(self)
+    }
+
+    /// Returns a [MetadataMap] for reading and writing the metadata
+    /// of this array.
+    pub fn metadata_map_mut(&mut self) -> MetadataMap<&mut Self> {
+        MetadataMap::new(self)
+    }
+}
+
+impl Group {
+    /// Returns a [MetadataMap] for reading the metadata of this group.
+    pub fn metadata_map(&self) -> MetadataMap<&Self> {
+        MetadataMap::new(self)
+    }
+
+    /// Returns a [MetadataMap] for reading and writing the metadata
+    /// of this group.
+    pub fn metadata_map_mut(&mut self) -> MetadataMap<&mut Self> {
+        MetadataMap::new(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::array::tests::create_quickstart_dense;
+    use crate::array::Mode;
+    use crate::error::Error;
+    use crate::group::QueryType;
+    use crate::Context;
+
+    #[test]
+    fn array_metadata_map() -> TileDBResult<()> {
+        let test_uri = uri::get_uri_generator()
+            .map_err(|e| Error::Other(e.to_string()))?;
+        let ctx = Context::new()?;
+        let uri = create_quickstart_dense(&test_uri, &ctx)?;
+
+        {
+            let mut array = Array::open(&ctx, &uri, Mode::Write)?;
+            let mut map = array.metadata_map_mut();
+            map.insert("scalar", 5i32)?;
+            map.insert("vector", vec![1.5f64, 2.5])?;
+            map.insert("name", "quickstart")?;
+            map.insert("flag", true)?;
+            map.insert("removed", 0u8)?;
+            map.insert_metadata(Metadata::new(
+                "bytes".to_owned(),
+                Datatype::Blob,
+                vec![1u8, 2, 3],
+            )?)?;
+        }
+        {
+            let mut array = Array::open(&ctx, &uri, Mode::Write)?;
+            array.metadata_map_mut().remove("removed")?;
+        }
+
+        let array = Array::open(&ctx, &uri, Mode::Read)?;
+        let map = array.metadata_map();
+        assert_eq!(5, map.len()?);
+        assert!(map.contains_key("scalar")?);
+        assert!(!map.contains_key("removed")?);
+        assert_eq!(Some(Datatype::Blob), map.datatype("bytes")?);
+
+        assert_eq!(Some(5), map.get::<i32, _>("scalar")?);
+        assert_eq!(Some(vec![5]), map.get::<Vec<i32>, _>("scalar")?);
+        assert_eq!(Some(vec![1.5, 2.5]), map.get::<Vec<f64>, _>("vector")?);
+        assert_eq!(
+            Some("quickstart".to_owned()),
+            map.get::<String, _>("name")?
+        );
+        assert_eq!(Some(true), map.get::<bool, _>("flag")?);
+        assert_eq!(Some(vec![1, 2, 3]), map.get::<Vec<u8>, _>("bytes")?);
+        assert_eq!(None, map.get::<i32, _>("removed")?);
+
+        assert!(matches!(
+            map.get::<f64, _>("vector"),
+            Err(Error::MetadataValue(FromValueError::UnexpectedLength(2)))
+        ));
+        assert!(matches!(
+            map.get::<i64, _>("scalar"),
+            Err(Error::MetadataValue(FromValueError::UnexpectedType { .. }))
+        ));
+
+        let mut keys = map.keys()?.collect::<TileDBResult<Vec<_>>>()?;
+        keys.sort();
+        assert_eq!(vec!["bytes", "flag", "name", "scalar", "vector"], keys);
+
+        let entries = map.iter()?.collect::<TileDBResult<Vec<_>>>()?;
+        assert_eq!(5, entries.len());
+        for entry in entries {
+            assert_eq!(Some(entry.clone()), map.get_metadata(&entry.key)?);
+        }
+
+        Ok(())
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn group_metadata_map_json() -> TileDBResult<()> {
+        use serde::Deserialize;
+        use uri::TestArrayUri;
+
+        #[derive(Debug, Deserialize, PartialEq, Serialize)]
+        struct Catalog {
+            owner: String,
+            tags: Vec<String>,
+            version: u32,
+        }
+
+        let test_uri = uri::get_uri_generator()
+            .map_err(|e| Error::Other(e.to_string()))?;
+        let ctx = Context::new()?;
+        let group_uri = test_uri
+            .with_path("metadata_map_group")
+            .map_err(|e| Error::Other(e.to_string()))?;
+        Group::create(&ctx, &group_uri)?;
+
+        let catalog = Catalog {
+            owner: "tiledb".to_owned(),
+            tags: vec!["a".to_owned(), "b".to_owned()],
+            version: 3,
+        };
+        {
+            let mut group =
+                Group::open(&ctx, &group_uri, QueryType::Write, None)?;
+            group.metadata_map_mut().insert_json("catalog", &catalog)?;
+        }
+
+        let group = Group::open(&ctx, &group_uri, QueryType::Read, None)?;
+        let map = group.metadata_map();
+        assert_eq!(Some(catalog), map.get_json::<Catalog, _>("catalog")?);
+        assert_eq!(Some(Datatype::StringUtf8), map.datatype("catalog")?);
+        assert_eq!(None, map.get_json::<Catalog, _>("missing")?);
+        assert!(map.get_json::<Vec<u32>, _>("catalog").is_err());
+
+        Ok(())
+    }
+}
@@ -1,3 +1,7 @@
+mod map;
+
+pub use self::map::{MetadataMap, MetadataStore};
+
 use tiledb_common::datatype::Datatype;
 use tiledb_common::physical_type_go;
 
@@ -34,7 +38,8 @@ pub(crate) fn metadata_from_ffi(
             }
         };
         Value::from(slice.to_vec())
-    });
+    })
+    .into_logical(datatype);
 
     Metadata {
         key,
@@ -0,0 +1,337 @@
+//! Provides functions for discovering and manipulating TileDB objects,
+//! i.e. arrays and groups, by URI.
+
+use anyhow::anyhow;
+
+use crate::context::{CApiInterface, Context};
+use crate::error::Error;
+use crate::Result as TileDBResult;
+
+pub use crate::context::ObjectType;
+
+/// Order in which [walk] visits the objects in a hierarchy.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum WalkOrder {
+    /// Each object is visited before any of the objects it contains.
+    #[default]
+    PreOrder,
+    /// Each object is visited after all of the objects it contains.
+    PostOrder,
+}
+
+impl From<WalkOrder> for ffi::tiledb_walk_order_t {
+    fn from(value: WalkOrder) -> Self {
+        match value {
+            WalkOrder::PreOrder => ffi::tiledb_walk_order_t_TILEDB_PREORDER,
+            WalkOrder::PostOrder => ffi::tiledb_walk_order_t_TILEDB_POSTORDER,
+        }
+    }
+}
+
+/// Iterator over the `(uri, ObjectType)` of the TileDB objects
+/// found by [walk] or [ls].
+pub struct Objects {
+    objects: std::vec::IntoIter<(String, ObjectType)>,
+}
+
+impl Iterator for Objects {
+    type Item = (String, ObjectType);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.objects.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.objects.size_hint()
+    }
+}
+
+impl DoubleEndedIterator for Objects {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.objects.next_back()
+    }
+}
+
+impl ExactSizeIterator for Objects {}
+
+impl std::iter::FusedIterator for Objects {}
+
+/// Returns an iterator over all of the TileDB objects contained
+/// (recursively) in the directory or group located at `uri`,
+/// in the requested order.
+///
+/// The object at `uri` itself is not included.
+pub fn walk<S>(
+    context: &Context,
+    uri: S,
+    order: WalkOrder,
+) -> TileDBResult<Objects>
+where
+    S: AsRef<str>,
+{
+    let c_uri = cstring!(uri.as_ref());
+    let c_order = ffi::tiledb_walk_order_t::from(order);
+
+    let mut objects: Vec<(String, ObjectType)> = vec![];
+    let c_objects = &mut objects as *mut Vec<(String, ObjectType)>;
+
+    context.capi_call(|ctx| unsafe {
+        ffi::tiledb_object_walk(
+            ctx,
+            c_uri.as_ptr(),
+            c_order,
+            Some(object_cb_handler),
+            c_objects as *mut std::ffi::c_void,
+        )
+    })?;
+
+    Ok(Objects {
+        objects: objects.into_iter(),
+    })
+}
+
+/// Returns an iterator over the TileDB objects contained
+/// directly in the directory or group located at `uri`.
+pub fn ls<S>(context: &Context, uri: S) -> TileDBResult<Objects>
+where
+    S: AsRef<str>,
+{
+    let c_uri = cstring!(uri.as_ref());
+
+    let mut objects: Vec<(String, ObjectType)> = vec![];
+    let c_objects = &mut objects as *mut Vec<(String, ObjectType)>;
+
+    context.capi_call(|ctx| unsafe {
+        ffi::tiledb_object_ls(
+            ctx,
+            c_uri.as_ptr(),
+            Some(object_cb_handler),
+            c_objects as *mut std::ffi::c_void,
+        )
+    })?;
+
+    Ok(Objects {
+        objects: objects.into_iter(),
+    })
+}
+
+/// Moves the TileDB object located at `old_uri` to `new_uri`,
+/// and returns the type of the object which was moved.
+///
+/// # Errors
+///
+/// Returns an error if there is no TileDB object at `old_uri`.
+pub fn move_object<S, T>(
+    context: &Context,
+    old_uri: S,
+    new_uri: T,
+) -> TileDBResult<ObjectType>
+where
+    S: AsRef<str>,
+    T: AsRef<str>,
+{
+    let object_type = expect_object(context, old_uri.as_ref())?;
+
+    let c_old_uri = cstring!(old_uri.as_ref());
+    let c_new_uri = cstring!(new_uri.as_ref());
+    context.capi_call(|ctx| unsafe {
+        ffi::tiledb_object_move(ctx, c_old_uri.as_ptr(), c_new_uri.as_ptr())
+    })?;
+
+    Ok(object_type)
+}
+
+/// Deletes the TileDB object located at `uri`, including all of its contents,
+/// and returns the type of the object which was removed.
+///
+/// # Errors
+///
+/// Returns an error if there is no TileDB object at `uri`.
+pub fn remove_object<S>(context: &Context, uri: S) -> TileDBResult<ObjectType>
+where
+    S: AsRef<str>,
+{
+    let object_type = expect_object(context, uri.as_ref())?;
+
+    let c_uri = cstring!(uri.as_ref());
+    context.capi_call(|ctx| unsafe {
+        ffi::tiledb_object_remove(ctx, c_uri.as_ptr())
+    })?;
+
+    Ok(object_type)
+}
+
+fn expect_object(context: &Context, uri: &str) -> TileDBResult<ObjectType> {
+    context.object_type(uri)?.ok_or_else(|| {
+        Error::InvalidArgument(anyhow!("No TileDB object found at '{}'", uri))
+    })
+}
+
+/// Collects each `(path, type)` visited by `tiledb_object_walk`
+/// or `tiledb_object_ls` into the `Vec` pointed to by `callback_data`.
+extern "C" fn object_cb_handler(
+    path: *const ::std::os::raw::c_char,
+    object_type: ffi::tiledb_object_t,
+    callback_data: *mut ::std::os::raw::c_void,
+) -> std::ffi::c_int {
+    let objects: &mut Vec<(String, ObjectType)> =
+        unsafe { &mut *(callback_data as *mut Vec<(String, ObjectType)>) };
+
+    let c_str: &std::ffi::CStr = unsafe { std::ffi::CStr::from_ptr(path) };
+    let Ok(path) = c_str.to_str() else {
+        return -1;
+    };
+
+    match ObjectType::from_capi(object_type) {
+        Ok(Some(object_type)) => {
+            objects.push((path.to_owned(), object_type));
+            1
+        }
+        Ok(None) => 1,
+        Err(_) => -1,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use uri::TestArrayUri;
+
+    use super::*;
+    use crate::array::tests::create_quickstart_dense;
+    use crate::array::Array;
+    use crate::group::Group;
+
+    /// Creates the hierarchy
+    /// ```text
+    /// root/
+    ///   quickstart_dense (array)
+    ///   outer/ (group)
+    ///     inner/ (group)
+    /// ```
+    /// and returns the URIs of the root directory and of `outer`.
+    fn create_hierarchy(
+        test_uri: &dyn TestArrayUri,
+        ctx: &Context,
+    ) -> TileDBResult<(String, String)> {
+        let root = test_uri
+            .base_dir()
+            .map_err(|e| Error::Other(e.to_string()))?;
+        let outer = test_uri
+            .with_path("outer")
+            .map_err(|e| Error::Other(e.to_string()))?;
+        let inner = test_uri
+            .with_paths(&["outer", "inner"])
+            .map_err(|e| Error::Other(e.to_string()))?;
+
+        create_quickstart_dense(test_uri, ctx)?;
+        Group::create(ctx, &outer)?;
+        Group::create(ctx, &inner)?;
+
+        Ok((root, outer))
+    }
+
+    fn names(objects: Objects) -> Vec<(String, ObjectType)> {
+        objects
+            .map(|(uri, object_type)| {
+                let name = uri
+                    .trim_end_matches('/')
+                    .rsplit('/')
+                    .next()
+                    .unwrap()
+                    .to_owned();
+                (name, object_type)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn walk_order() -> TileDBResult<()> {
+        let test_uri = uri::get_uri_generator()
+            .map_err(|e| Error::Other(e.to_string()))?;
+        let ctx = Context::new()?;
+        let (root, _) = create_hierarchy(&test_uri, &ctx)?;
+
+        let mut preorder = names(walk(&ctx, &root, WalkOrder::PreOrder)?);
+        let mut postorder = names(walk(&ctx, &root, WalkOrder::PostOrder)?);
+
+        // `outer` must come before `inner` in pre-order and after in post-order
+        let position = |objects: &[(String, ObjectType)], name: &str| {
+            objects.iter().position(|(n, _)| n == name).unwrap()
+        };
+        assert!(position(&preorder, "outer") < position(&preorder, "inner"));
+        assert!(position(&postorder, "outer") > position(&postorder, "inner"));
+
+        preorder.sort_by(|l, r| l.0.cmp(&r.0));
+        postorder.sort_by(|l, r| l.0.cmp(&r.0));
+        assert_eq!(preorder, postorder);
+
+        let expect = vec![
+            ("inner".to_owned(), ObjectType::Group),
+            ("outer".to_owned(), ObjectType::Group),
+            ("quickstart_dense".to_owned(), ObjectType::Array),
+        ];
+        assert_eq!(expect, preorder);
+
+        Ok(())
+    }
+
+    #[test]
+    fn ls_is_not_recursive() -> TileDBResult<()> {
+        let test_uri = uri::get_uri_generator()
+            .map_err(|e| Error::Other(e.to_string()))?;
+        let ctx = Context::new()?;
+        let (root, outer) = create_hierarchy(&test_uri, &ctx)?;
+
+        let mut objects = names(ls(&ctx, &root)?);
+        objects.sort_by(|l, r| l.0.cmp(&r.0));
+        assert_eq!(
+            vec![
+                ("outer".to_owned(), ObjectType::Group),
+                ("quickstart_dense".to_owned(), ObjectType::Array),
+            ],
+            objects
+        );
+
+        let objects = names(ls(&ctx, &outer)?);
+        assert_eq!(vec![("inner".to_owned(), ObjectType::Group)], objects);
+
+        Ok(())
+    }
+
+    #[test]
+    fn move_and_remove() -> TileDBResult<()> {
+        let test_uri = uri::get_uri_generator()
+            .map_err(|e| Error::Other(e.to_string()))?;
+        let ctx = Context::new()?;
+        let (_, outer) = create_hierarchy(&test_uri, &ctx)?;
+
+        let array_uri = test_uri
+            .with_path("quickstart_dense")
+            .map_err(|e| Error::Other(e.to_string()))?;
+        let moved_uri = test_uri
+            .with_path("moved")
+            .map_err(|e| Error::Other(e.to_string()))?;
+
+        assert_eq!(
+            ObjectType::Array,
+            move_object(&ctx, &array_uri, &moved_uri)?
+        );
+        assert!(!Array::exists(&ctx, &array_uri)?);
+        assert!(Array::exists(&ctx, &moved_uri)?);
+
+        assert_eq!(ObjectType::Group, remove_object(&ctx, &outer)?);
+        assert_eq!(None, ctx.object_type(&outer)?);
+
+        // there is nothing left at these locations
+        assert!(matches!(
+            move_object(&ctx, &array_uri, &moved_uri),
+            Err(Error::InvalidArgument(_))
+        ));
+        assert!(matches!(
+            remove_object(&ctx, &outer),
+            Err(Error::InvalidArgument(_))
+        ));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,1039 @@
+//! Evaluates query conditions against records in memory.
+
+use anyhow::anyhow;
+
+use super::*;
+
+/// The values of one field of a batch of records.
+#[derive(Clone, Debug)]
+pub enum CellValues<'a> {
+    UInt8(&'a [u8]),
+    UInt16(&'a [u16]),
+    UInt32(&'a [u32]),
+    UInt64(&'a [u64]),
+    Int8(&'a [i8]),
+    Int16(&'a [i16]),
+    Int32(&'a [i32]),
+    Int64(&'a [i64]),
+    Float32(&'a [f32]),
+    Float64(&'a [f64]),
+    /// Variable-length values, such as strings,
+    /// which are compared lexicographically by byte.
+    Bytes(Vec<&'a [u8]>),
+}
+
+impl CellValues<'_> {
+    pub fn len(&self) -> usize {
+        match self {
+            Self::UInt8(values) => values.len(),
+            Self::UInt16(values) => values.len(),
+            Self::UInt32(values) => values.len(),
+            Self::UInt64(values) => values.len(),
+            Self::Int8(values) => values.len(),
+            Self::Int16(values) => values.len(),
+            Self::Int32(values) => values.len(),
+            Self::Int64(values) => values.len(),
+            Self::Float32(values) => values.len(),
+            Self::Float64(values) => values.len(),
+            Self::Bytes(values) => values.len(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+macro_rules! cell_values_from {
+    ($($V:ident : $T:ty),+) => {
+        $(
+            impl<'a> From<&'a [$T]> for CellValues<'a> {
+                fn from(values: &'a [$T]) -> Self {
+                    Self::$V(values)
+                }
+            }
+        )+
+    };
+}
+
+cell_values_from!(
+    UInt8: u8,
+    UInt16: u16,
+    UInt32: u32,
+    UInt64: u64,
+    Int8: i8,
+    Int16: i16,
+    Int32: i32,
+    Int64: i64,
+    Float32: f32,
+    Float64: f64
+);
+
+/// The values of one field of a batch of records, and which of them are null.
+#[derive(Clone, Debug)]
+pub struct FieldValues<'a> {
+    pub values: CellValues<'a>,
+    /// Whether each value is non-null, if the field is nullable.
+    pub validity: Option<Vec<bool>>,
+}
+
+/// A batch of records which a [QueryConditionExpr] can be evaluated against.
+pub trait ConditionInput {
+    /// Returns the number of records in the batch.
+    fn num_records(&self) -> usize;
+
+    /// Returns the values of `field` for each record in the batch.
+    fn field_values(&self, field: &str) -> TileDBResult<FieldValues<'_>>;
+}
+
+impl QueryConditionExpr {
+    /// Evaluates this condition against each record of `input`, returning
+    /// whether each record satisfies it.
+    ///
+    /// The result matches that of applying the condition to a query:
+    /// * A negated condition is evaluated by negating each of its
+    ///   operations, so `NOT (x < 5)` is equivalent to `x >= 5`.
+    ///   Neither is satisfied if `x` is null or NaN.
+    /// * A null value satisfies only `<>`, `NOT IN`, and `IS NULL`.
+    /// * NaN satisfies only `<>` and `NOT IN`.
+    ///
+    /// * A comparison with an operand which is null, such as the sum
+    ///   of a null value, satisfies only `<>`.
+    ///
+    /// Predicates which compare enumeration values cannot be evaluated,
+    /// since the input contains only the enumeration keys.
+    pub fn evaluate<I>(&self, input: &I) -> TileDBResult<Vec<bool>>
+    where
+        I: ConditionInput + ?Sized,
+    {
+        self.evaluate_impl(input, false)
+    }
+
+    fn evaluate_impl<I>(
+        &self,
+        input: &I,
+        negate: bool,
+    ) -> TileDBResult<Vec<bool>>
+    where
+        I: ConditionInput + ?Sized,
+    {
+        match self {
+            Self::Cond(predicate) => predicate.evaluate(input, negate),
+            Self::Comb { lhs, rhs, op } => {
+                let lhs = lhs.evaluate_impl(input, negate)?;
+                let rhs = rhs.evaluate_impl(input, negate)?;
+                let op = if negate { op.negate() } else { *op };
+                Ok(lhs
+                    .into_iter()
+                    .zip(rhs)
+                    .map(|(l, r)| match op {
+                        CombinationOp::And => l && r,
+                        CombinationOp::Or => l || r,
+                    })
+                    .collect())
+            }
+            Self::Negate(expr) => expr.evaluate_impl(input, !negate),
+        }
+    }
+}
+
+impl Predicate {
+    fn evaluate<I>(&self, input: &I, negate: bool) -> TileDBResult<Vec<bool>>
+    where
+        I: ConditionInput + ?Sized,
+    {
+        let (field, use_enumeration) = match self {
+            Self::Equality(e) => (e.field(), e.use_enumeration()),
+            Self::SetMembership(s) => (s.field(), s.use_enumeration()),
+            Self::Nullness(n) => (n.field(), false),
+            Self::Comparison(c) => return c.evaluate(input, negate),
+        };
+        if use_enumeration {
+            return Err(Error::InvalidArgument(anyhow!(
+                "Cannot evaluate predicate on enumeration values of field '{}'",
+                field
+            )));
+        }
+        let values = field_values(input, field)?;
+
+        let num_records = input.num_records();
+        let is_null = |record: usize| {
+            values
+                .validity
+                .as_ref()
+                .is_some_and(|validity| !validity[record])
+        };
+
+        let (matches, null_matches) = match self {
+            Self::Equality(e) => {
+                let op = if negate { e.op.negate() } else { e.op };
+                let matches = compare(&values.values, op, &e.value)
+                    .ok_or_else(|| type_mismatch(field, &e.value))?;
+                (matches, op == EqualityOp::NotEqual)
+            }
+            Self::SetMembership(s) => {
+                let op = if negate { s.op.negate() } else { s.op };
+                let matches = contains(&values.values, &s.members)
+                    .ok_or_else(|| type_mismatch(field, &s.members))?;
+                let matches = match op {
+                    SetMembershipOp::In => matches,
+                    SetMembershipOp::NotIn => {
+                        matches.into_iter().map(|m| !m).collect()
+                    }
+                };
+                (matches, op == SetMembershipOp::NotIn)
+            }
+            Self::Nullness(n) => {
+                let op = if negate { n.op.negate() } else { n.op };
+                (
+                    vec![op == NullnessOp::NotNull; num_records],
+                    op == NullnessOp::IsNull,
+                )
+            }
+            Self::Comparison(_) => unreachable!(),
+        };
+
+        Ok(matches
+            .into_iter()
+            .enumerate()
+            .map(|(record, m)| if is_null(record) { null_matches } else { m })
+            .collect())
+    }
+}
+
+impl ComparisonPredicate {
+    fn evaluate<I>(&self, input: &I, negate: bool) -> TileDBResult<Vec<bool>>
+    where
+        I: ConditionInput + ?Sized,
+    {
+        let op = if negate { self.op.negate() } else { self.op };
+        let lhs = self.lhs.evaluate(input)?;
+        let rhs = self.rhs.evaluate(input)?;
+        lhs.into_iter()
+            .zip(rhs)
+            .map(|(lhs, rhs)| match (lhs, rhs) {
+                (Scalar::Null, _) | (_, Scalar::Null) => {
+                    Ok(op == EqualityOp::NotEqual)
+                }
+                (Scalar::Int(lhs), Scalar::Int(rhs)) => {
+                    Ok(apply(op, &lhs, &rhs))
+                }
+                (Scalar::Bytes(lhs), Scalar::Bytes(rhs)) => {
+                    Ok(apply(op, lhs, rhs))
+                }
+                (lhs, rhs) => match (lhs.as_f64(), rhs.as_f64()) {
+                    (Some(lhs), Some(rhs)) => Ok(apply(op, &lhs, &rhs)),
+                    _ => Err(Error::InvalidArgument(anyhow!(
+                        "Cannot evaluate '{}': mismatched types",
+                        self
+                    ))),
+                },
+            })
+            .collect()
+    }
+}
+
+/// The value of an [Operand] for a single record.
+#[derive(Clone, Copy, Debug)]
+enum Scalar<'a> {
+    Null,
+    Int(i128),
+    Float(f64),
+    Bytes(&'a [u8]),
+}
+
+impl Scalar<'_> {
+    fn as_f64(&self) -> Option<f64> {
+        match self {
+            Self::Int(value) => Some(*value as f64),
+            Self::Float(value) => Some(*value),
+            Self::Null | Self::Bytes(_) => None,
+        }
+    }
+}
+
+impl<'a> From<&'a Literal> for Scalar<'a> {
+    fn from(value: &'a Literal) -> Self {
+        match value {
+            Literal::UInt8(value) => Self::Int((*value).into()),
+            Literal::UInt16(value) => Self::Int((*value).into()),
+            Literal::UInt32(value) => Self::Int((*value).into()),
+            Literal::UInt64(value) => Self::Int((*value).into()),
+            Literal::Int8(value) => Self::Int((*value).into()),
+            Literal::Int16(value) => Self::Int((*value).into()),
+            Literal::Int32(value) => Self::Int((*value).into()),
+            Literal::Int64(value) => Self::Int((*value).into()),
+            Literal::Float32(value) => Self::Float((*value).into()),
+            Literal::Float64(value) => Self::Float(*value),
+            Literal::String(value) => Self::Bytes(value.as_bytes()),
+        }
+    }
+}
+
+impl Operand {
+    /// Returns the value of this operand for each record of `input`.
+    fn evaluate<'a, I>(&'a self, input: &'a I) -> TileDBResult<Vec<Scalar<'a>>>
+    where
+        I: ConditionInput + ?Sized,
+    {
+        match self {
+            Self::Field(field) => {
+                let values = field_values(input, field)?;
+
+                macro_rules! scalars {
+                    ($($V:ident => $S:ident),+) => {
+                        match values.values {
+                            $(
+                                CellValues::$V(values) => values
+                                    .iter()
+                                    .map(|v| Scalar::$S((*v).into()))
+                                    .collect::<Vec<_>>(),
+                            )+
+                            CellValues::Bytes(values) => {
+                                values.into_iter().map(Scalar::Bytes).collect()
+                            }
+                        }
+                    };
+                }
+                let mut scalars = scalars!(
+                    UInt8 => Int, UInt16 => Int, UInt32 => Int, UInt64 => Int,
+                    Int8 => Int, Int16 => Int, Int32 => Int, Int64 => Int,
+                    Float32 => Float, Float64 => Float
+                );
+                if let Some(validity) = values.validity {
+                    scalars
+                        .iter_mut()
+                        .zip(validity)
+                        .filter(|(_, valid)| !valid)
+                        .for_each(|(scalar, _)| *scalar = Scalar::Null);
+                }
+                Ok(scalars)
+            }
+            Self::Literal(value) => {
+                Ok(vec![Scalar::from(value); input.num_records()])
+            }
+            Self::Arithmetic { lhs, rhs, op } => {
+                let lhs = lhs.evaluate(input)?;
+                let rhs = rhs.evaluate(input)?;
+                lhs.into_iter()
+                    .zip(rhs)
+                    .map(|(lhs, rhs)| {
+                        arithmetic(*op, lhs, rhs).ok_or_else(|| {
+                            Error::InvalidArgument(anyhow!(
+                                "Cannot evaluate '{}': operands are not numbers",
+                                self
+                            ))
+                        })
+                    })
+                    .collect()
+            }
+        }
+    }
+}
+
+/// Returns the result of `lhs op rhs`, or `None` if either operand
+/// is not a number.
+fn arithmetic<'a>(
+    op: ArithmeticOp,
+    lhs: Scalar<'a>,
+    rhs: Scalar<'a>,
+) -> Option<Scalar<'a>> {
+    Some(match (lhs, rhs) {
+        (Scalar::Bytes(_), _) | (_, Scalar::Bytes(_)) => return None,
+        (Scalar::Null, _) | (_, Scalar::Null) => Scalar::Null,
+        (Scalar::Int(lhs), Scalar::Int(rhs)) => {
+            let value = match op {
+                ArithmeticOp::Add => lhs.checked_add(rhs),
+                ArithmeticOp::Subtract => lhs.checked_sub(rhs),
+                ArithmeticOp::Multiply => lhs.checked_mul(rhs),
+                ArithmeticOp::Divide => lhs.checked_div(rhs),
+            };
+            value.map(Scalar::Int).unwrap_or(Scalar::Null)
+        }
+        (lhs, rhs) => {
+            let (lhs, rhs) = (lhs.as_f64()?, rhs.as_f64()?);
+            Scalar::Float(match op {
+                ArithmeticOp::Add => lhs + rhs,
+                ArithmeticOp::Subtract => lhs - rhs,
+                ArithmeticOp::Multiply => lhs * rhs,
+                ArithmeticOp::Divide => lhs / rhs,
+            })
+        }
+    })
+}
+
+/// Returns the values of `field`, checking that there is one
+/// for each record of `input`.
+fn field_values<'a, I>(
+    input: &'a I,
+    field: &str,
+) -> TileDBResult<FieldValues<'a>>
+where
+    I: ConditionInput + ?Sized,
+{
+    let values = input.field_values(field)?;
+    let num_records = input.num_records();
+    if values.values.len() != num_records {
+        return Err(Error::InvalidArgument(anyhow!(
+            "Expected {} values for field '{}' but found {}",
+            num_records,
+            field,
+            values.values.len()
+        )));
+    }
+    Ok(values)
+}
+
+fn type_mismatch(field: &str, value: &dyn Display) -> Error {
+    Error::InvalidArgument(anyhow!(
+        "Field '{}' cannot be compared with {}: mismatched types",
+        field,
+        value
+    ))
+}
+
+fn apply<T>(op: EqualityOp, lhs: &T, rhs: &T) -> bool
+where
+    T: PartialOrd + ?Sized,
+{
+    match op {
+        EqualityOp::Less => lhs < rhs,
+        EqualityOp::LessEqual => lhs <= rhs,
+        EqualityOp::Equal => lhs == rhs,
+        EqualityOp::NotEqual => lhs != rhs,
+        EqualityOp::GreaterEqual => lhs >= rhs,
+        EqualityOp::Greater => lhs > rhs,
+    }
+}
+
+/// Returns whether each value satisfies `op` with `literal`,
+/// or `None` if the values have a different type than `literal`.
+fn compare(
+    values: &CellValues,
+    op: EqualityOp,
+    literal: &Literal,
+) -> Option<Vec<bool>> {
+    macro_rules! compare {
+        ($($V:ident),+) => {
+            match (values, literal) {
+                $(
+                    (CellValues::$V(values), Literal::$V(literal)) => Some(
+                        values.iter().map(|v| apply(op, v, literal)).collect(),
+                    ),
+                )+
+                (CellValues::Bytes(values), Literal::String(literal)) => Some(
+                    values
+                        .iter()
+                        .map(|v| apply(op, *v, literal.as_bytes()))
+                        .collect(),
+                ),
+                _ => None,
+            }
+        };
+    }
+    compare!(
+        UInt8, UInt16, UInt32, UInt64, Int8, Int16, Int32, Int64, Float32,
+        Float64
+    )
+}
+
+/// Returns whether each value is one of `members`,
+/// or `None` if the values have a different type than `members`.
+fn contains(values: &CellValues, members: &SetMembers) -> Option<Vec<bool>> {
+    macro_rules! contains {
+        ($($V:ident),+) => {
+            match (values, members) {
+                $(
+                    (CellValues::$V(values), SetMembers::$V(members)) => Some(
+                        values.iter().map(|v| members.contains(v)).collect(),
+                    ),
+                )+
+                (CellValues::Bytes(values), SetMembers::String(members)) => {
+                    Some(
+                        values
+                            .iter()
+                            .map(|v| members.iter().any(|m| m.as_bytes() == *v))
+                            .collect(),
+                    )
+                }
+                _ => None,
+            }
+        };
+    }
+    contains!(
+        UInt8, UInt16, UInt32, UInt64, Int8, Int16, Int32, Int64, Float32,
+        Float64
+    )
+}
+
+#[cfg(any(test, feature = "proptest-strategies"))]
+impl ConditionInput for cells::Cells {
+    fn num_records(&self) -> usize {
+        self.fields().values().next().map(|f| f.len()).unwrap_or(0)
+    }
+
+    fn field_values(&self, field: &str) -> TileDBResult<FieldValues<'_>> {
+        use cells::FieldData;
+
+        let Some(data) = self.fields().get(field) else {
+            return Err(Error::InvalidArgument(anyhow!(
+                "No such field: {}",
+                field
+            )));
+        };
+        let values = match data {
+            FieldData::UInt8(values) => CellValues::UInt8(values),
+            FieldData::UInt16(values) => CellValues::UInt16(values),
+            FieldData::UInt32(values) => CellValues::UInt32(values),
+            FieldData::UInt64(values) => CellValues::UInt64(values),
+            FieldData::Int8(values) => CellValues::Int8(values),
+            FieldData::Int16(values) => CellValues::Int16(values),
+            FieldData::Int32(values) => CellValues::Int32(values),
+            FieldData::Int64(values) => CellValues::Int64(values),
+            FieldData::Float32(values) => CellValues::Float32(values),
+            FieldData::Float64(values) => CellValues::Float64(values),
+            FieldData::VecUInt8(values) => {
+                CellValues::Bytes(values.iter().map(|v| v.as_slice()).collect())
+            }
+            _ => {
+                return Err(Error::InvalidArgument(anyhow!(
+                    "Field '{}' has multiple values per cell, \
+                     which query conditions do not support",
+                    field
+                )))
+            }
+        };
+        Ok(FieldValues {
+            values,
+            validity: None,
+        })
+    }
+}
+
+#[cfg(feature = "arrow")]
+mod record_batch {
+    use arrow::array::{
+        Array as ArrowArray, AsArray, GenericByteArray, GenericListArray,
+        OffsetSizeTrait,
+    };
+    use arrow::datatypes::{
+        self as adt, ArrowNativeType, ByteArrayType, DataType, TimeUnit,
+    };
+    use arrow::record_batch::RecordBatch;
+
+    use super::*;
+
+    impl ConditionInput for RecordBatch {
+        fn num_records(&self) -> usize {
+            self.num_rows()
+        }
+
+        fn field_values(&self, field: &str) -> TileDBResult<FieldValues<'_>> {
+            let Some(column) = self.column_by_name(field) else {
+                return Err(Error::InvalidArgument(anyhow!(
+                    "No such field: {}",
+                    field
+                )));
+            };
+            Ok(FieldValues {
+                values: cell_values(field, column.as_ref())?,
+                validity: column
+                    .logical_nulls()
+                    .map(|nulls| nulls.iter().collect()),
+            })
+        }
+    }
+
+    fn cell_values<'a>(
+        field: &str,
+        array: &'a dyn ArrowArray,
+    ) -> TileDBResult<CellValues<'a>> {
+        macro_rules! primitive {
+            ($V:ident, $T:ty) => {
+                CellValues::$V(&array.as_primitive::<$T>().values()[..])
+            };
+        }
+
+        Ok(match array.data_type() {
+            DataType::UInt8 => primitive!(UInt8, adt::UInt8Type),
+            DataType::UInt16 => primitive!(UInt16, adt::UInt16Type),
+            DataType::UInt32 => primitive!(UInt32, adt::UInt32Type),
+            DataType::UInt64 => primitive!(UInt64, adt::UInt64Type),
+            DataType::Int8 => primitive!(Int8, adt::Int8Type),
+            DataType::Int16 => primitive!(Int16, adt::Int16Type),
+            DataType::Int32 => primitive!(Int32, adt::Int32Type),
+            DataType::Int64 => primitive!(Int64, adt::Int64Type),
+            DataType::Float32 => primitive!(Float32, adt::Float32Type),
+            DataType::Float64 => primitive!(Float64, adt::Float64Type),
+            DataType::Date32 => primitive!(Int32, adt::Date32Type),
+            DataType::Date64 => primitive!(Int64, adt::Date64Type),
+            DataType::Time32(TimeUnit::Second) => {
+                primitive!(Int32, adt::Time32SecondType)
+            }
+            DataType::Time32(TimeUnit::Millisecond) => {
+                primitive!(Int32, adt::Time32MillisecondType)
+            }
+            DataType::Time64(TimeUnit::Microsecond) => {
+                primitive!(Int64, adt::Time64MicrosecondType)
+            }
+            DataType::Time64(TimeUnit::Nanosecond) => {
+                primitive!(Int64, adt::Time64NanosecondType)
+            }
+            DataType::Timestamp(TimeUnit::Second, _) => {
+                primitive!(Int64, adt::TimestampSecondType)
+            }
+            DataType::Timestamp(TimeUnit::Millisecond, _) => {
+                primitive!(Int64, adt::TimestampMillisecondType)
+            }
+            DataType::Timestamp(TimeUnit::Microsecond, _) => {
+                primitive!(Int64, adt::TimestampMicrosecondType)
+            }
+            DataType::Timestamp(TimeUnit::Nanosecond, _) => {
+                primitive!(Int64, adt::TimestampNanosecondType)
+            }
+            DataType::Utf8 => byte_values(array.as_string::<i32>()),
+            DataType::LargeUtf8 => byte_values(array.as_string::<i64>()),
+            DataType::Binary => byte_values(array.as_binary::<i32>()),
+            DataType::LargeBinary => byte_values(array.as_binary::<i64>()),
+            DataType::List(item) if *item.data_type() == DataType::UInt8 => {
+                list_values(array.as_list::<i32>())
+            }
+            DataType::LargeList(item)
+                if *item.data_type() == DataType::UInt8 =>
+            {
+                list_values(array.as_list::<i64>())
+            }
+            dt => {
+                return Err(Error::InvalidArgument(anyhow!(
+                    "Field '{}' has arrow data type {}, \
+                     which query conditions do not support",
+                    field,
+                    dt
+                )))
+            }
+        })
+    }
+
+    fn byte_values<T>(array: &GenericByteArray<T>) -> CellValues<'_>
+    where
+        T: ByteArrayType,
+    {
+        let data = array.value_data();
+        CellValues::Bytes(
+            array
+                .value_offsets()
+                .windows(2)
+                .map(|w| &data[w[0].as_usize()..w[1].as_usize()])
+                .collect(),
+        )
+    }
+
+    /// Returns the values of a list of `UInt8`, which is how
+    /// TileDB strings are represented in arrow.
+    fn list_values<O>(array: &GenericListArray<O>) -> CellValues<'_>
+    where
+        O: OffsetSizeTrait,
+    {
+        let data =
+            &array.values().as_primitive::<adt::UInt8Type>().values()[..];
+        CellValues::Bytes(
+            array
+                .value_offsets()
+                .windows(2)
+                .map(|w| &data[w[0].as_usize()..w[1].as_usize()])
+                .collect(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::rc::Rc;
+
+    use cells::write::strategy::SparseWriteParameters;
+    use cells::write::SparseWriteInput;
+    use cells::{Cells, FieldData};
+    use proptest::bits::{BitSetLike, VarBitSet};
+    use proptest::prelude::*;
+
+    use super::QueryConditionExpr as QC;
+    use super::*;
+    use crate::query::condition::strategy::prop_query_condition;
+    use crate::query::{ToReadQuery, ToWriteQuery};
+    use crate::tests::prelude::*;
+
+    fn cells() -> Cells {
+        Cells::new(HashMap::from([
+            ("i".to_owned(), FieldData::from(vec![1i32, 2, 3, 4, 5])),
+            (
+                "f".to_owned(),
+                FieldData::from(vec![
+                    0.5f64,
+                    f64::NAN,
+                    -1.0,
+                    2.5,
+                    f64::INFINITY,
+                ]),
+            ),
+            (
+                "s".to_owned(),
+                FieldData::from(
+                    ["", "a", "ab", "b", "ba"]
+                        .into_iter()
+                        .map(|s| s.to_owned())
+                        .collect::<Vec<String>>(),
+                ),
+            ),
+        ]))
+    }
+
+    fn evaluate(qc: QC) -> TileDBResult<Vec<bool>> {
+        qc.evaluate(&cells())
+    }
+
+    #[test]
+    fn evaluate_equality() -> TileDBResult<()> {
+        let (t, f) = (true, false);
+
+        assert_eq!(vec![t, t, f, f, f], evaluate(QC::field("i").lt(3))?);
+        assert_eq!(vec![t, t, t, f, f], evaluate(QC::field("i").le(3))?);
+        assert_eq!(vec![f, f, t, f, f], evaluate(QC::field("i").eq(3))?);
+        assert_eq!(vec![t, t, f, t, t], evaluate(QC::field("i").ne(3))?);
+        assert_eq!(vec![f, f, t, t, t], evaluate(QC::field("i").ge(3))?);
+        assert_eq!(vec![f, f, f, t, t], evaluate(QC::field("i").gt(3))?);
+
+        assert_eq!(vec![t, f, t, f, f], evaluate(QC::field("f").lt(1.0))?);
+        assert_eq!(vec![f, f, f, t, t], evaluate(QC::field("f").ge(1.0))?);
+        assert_eq!(
+            vec![t, t, t, t, f],
+            evaluate(QC::field("f").ne(f64::INFINITY))?
+        );
+        assert_eq!(vec![f; 5], evaluate(QC::field("f").eq(f64::NAN))?);
+
+        assert_eq!(vec![t, t, t, f, f], evaluate(QC::field("s").lt("b"))?);
+        assert_eq!(vec![f, f, t, t, t], evaluate(QC::field("s").gt("a"))?);
+        assert_eq!(vec![t, f, f, f, f], evaluate(QC::field("s").eq(""))?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn evaluate_set_membership() -> TileDBResult<()> {
+        let (t, f) = (true, false);
+
+        assert_eq!(
+            vec![t, f, t, f, t],
+            evaluate(QC::field("i").is_in(&[1, 3, 5][..]))?
+        );
+        assert_eq!(
+            vec![f, t, f, t, f],
+            evaluate(QC::field("i").not_in(&[1, 3, 5][..]))?
+        );
+        assert_eq!(
+            vec![f, f, f, f, t],
+            evaluate(QC::field("f").is_in(&[f64::NAN, f64::INFINITY][..]))?
+        );
+        assert_eq!(
+            vec![f, t, f, f, t],
+            evaluate(QC::field("s").is_in(&["a", "ba", "c"][..]))?
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn evaluate_negation() -> TileDBResult<()> {
+        let (t, f) = (true, false);
+
+        assert_eq!(vec![f, f, t, t, t], evaluate(!QC::field("i").lt(3))?);
+        assert_eq!(
+            vec![f, t, t, t, f],
+            evaluate(!(QC::field("i").lt(2) | QC::field("i").gt(4)))?
+        );
+        assert_eq!(vec![t, t, t, t, f], evaluate(!!QC::field("i").le(4))?);
+
+        // negation is not logical complement for NaN
+        assert_eq!(vec![f, f, t, f, f], evaluate(!QC::field("f").ge(0.0))?);
+
+        // nothing is null
+        assert_eq!(vec![f; 5], evaluate(QC::field("i").is_null())?);
+        assert_eq!(vec![t; 5], evaluate(!QC::field("i").is_null())?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn evaluate_comparison() -> TileDBResult<()> {
+        let (t, f) = (true, false);
+
+        assert_eq!(
+            vec![t, f, t, t, f],
+            evaluate(Operand::field("f").lt(Operand::field("i")))?
+        );
+        assert_eq!(
+            vec![t; 5],
+            evaluate(Operand::field("f").ne(Operand::field("i")))?
+        );
+        assert_eq!(
+            vec![f, f, f, f, t],
+            evaluate(!Operand::field("f").lt(Operand::field("i")))?
+        );
+        assert_eq!(
+            vec![f, f, t, t, t],
+            evaluate((QC::field("i") * 2).gt(Operand::field("i") + 2))?
+        );
+        assert_eq!(
+            vec![f, f, t, t, t],
+            evaluate(Operand::from("a").lt(Operand::field("s")))?
+        );
+
+        // integer division truncates, unless an operand is floating-point
+        assert_eq!(vec![f, t, t, f, f], evaluate((QC::field("i") / 2).eq(1))?);
+        assert_eq!(
+            vec![f, f, t, f, f],
+            evaluate((QC::field("i") / 2.0).eq(1.5))?
+        );
+
+        // division by zero is null
+        assert_eq!(vec![f; 5], evaluate((QC::field("i") / 0).eq(0))?);
+        assert_eq!(vec![t; 5], evaluate((QC::field("i") / 0).ne(0))?);
+
+        assert!(matches!(
+            evaluate(Operand::field("s").lt(Operand::field("i"))),
+            Err(Error::InvalidArgument(_))
+        ));
+        assert!(matches!(
+            evaluate((QC::field("s") + 1).gt(0)),
+            Err(Error::InvalidArgument(_))
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn evaluate_errors() {
+        assert!(matches!(
+            evaluate(QC::field("x").eq(1)),
+            Err(Error::InvalidArgument(_))
+        ));
+        assert!(matches!(
+            evaluate(QC::field("i").eq(1u32)),
+            Err(Error::InvalidArgument(_))
+        ));
+        assert!(matches!(
+            evaluate(QC::field("s").is_in(&[1u8, 2][..])),
+            Err(Error::InvalidArgument(_))
+        ));
+        assert!(matches!(
+            evaluate(QC::field("i").use_enumeration().eq(1)),
+            Err(Error::InvalidArgument(_))
+        ));
+    }
+
+    #[cfg(feature = "arrow")]
+    #[test]
+    fn evaluate_record_batch() -> TileDBResult<()> {
+        use std::sync::Arc;
+
+        use arrow::array::{ArrayRef, Int32Array, LargeStringArray};
+        use arrow::record_batch::RecordBatch;
+
+        let (t, f) = (true, false);
+
+        let batch = RecordBatch::try_from_iter([
+            (
+                "i",
+                Arc::new(Int32Array::from(vec![Some(1), None, Some(3)]))
+                    as ArrayRef,
+            ),
+            (
+                "s",
+                Arc::new(LargeStringArray::from(vec![
+                    Some("a"),
+                    Some("b"),
+                    None,
+                ])) as ArrayRef,
+            ),
+        ])
+        .map_err(|e| Error::Other(e.to_string()))?;
+
+        assert_eq!(vec![t, f, f], QC::field("i").lt(3).evaluate(&batch)?);
+        assert_eq!(vec![f, f, t], QC::field("i").ge(3).evaluate(&batch)?);
+        assert_eq!(vec![t, t, f], QC::field("i").ne(3).evaluate(&batch)?);
+        assert_eq!(vec![f, t, f], QC::field("i").is_null().evaluate(&batch)?);
+        assert_eq!(
+            vec![f, t, t],
+            QC::field("s").not_in(&["a"][..]).evaluate(&batch)?
+        );
+        assert_eq!(vec![f, t, t], (!QC::field("s").eq("a")).evaluate(&batch)?);
+        assert_eq!(vec![t, f, f], (QC::field("i") + 1).le(2).evaluate(&batch)?);
+
+        Ok(())
+    }
+
+    /// Writes `input` into a new quickstart array, reads it back using
+    /// `condition`, and checks that TileDB selects the cells for which
+    /// `condition` evaluates to `true`.
+    fn do_evaluate_readback(
+        schema: Rc<SchemaData>,
+        input: SparseWriteInput,
+        condition: QC,
+    ) -> TileDBResult<()> {
+        let mut array = TestArray::new("evaluate_readback", schema)?;
+        {
+            let w = input
+                .attach_write(WriteBuilder::new(array.for_write()?)?)?
+                .build();
+            w.submit()?;
+            w.finalize()?;
+        }
+
+        let expect = {
+            let matches = condition.evaluate(&input.data)?;
+            let mut keep = VarBitSet::new_bitset(matches.len());
+            matches
+                .into_iter()
+                .enumerate()
+                .filter(|(_, m)| *m)
+                .for_each(|(i, _)| keep.set(i));
+            input.data.filter(&keep)
+        };
+
+        let mut read = input
+            .attach_read(ReadBuilder::new(array.for_read()?)?)?
+            .query_condition(condition)?
+            .build();
+        let (cells, _): (Cells, _) = read.execute()?;
+
+        let sort_keys = vec!["rows".to_owned(), "cols".to_owned()];
+        assert_eq!(expect.sorted(&sort_keys), cells.sorted(&sort_keys));
+
+        Ok(())
+    }
+
+    /// Returns a strategy for conditions on the attribute `a`
+    /// of the quickstart array, mostly comparing against `values`.
+    fn prop_condition(values: Vec<i32>) -> impl Strategy<Value = QC> {
+        let value = if values.is_empty() {
+            any::<i32>().boxed()
+        } else {
+            prop_oneof![
+                3 => proptest::sample::select(values),
+                1 => any::<i32>()
+            ]
+            .boxed()
+        };
+
+        let equality = (value.clone(), 0..6).prop_map(|(value, op)| {
+            let a = QC::field("a");
+            match op {
+                0 => a.lt(value),
+                1 => a.le(value),
+                2 => a.eq(value),
+                3 => a.ne(value),
+                4 => a.ge(value),
+                _ => a.gt(value),
+            }
+        });
+        let set_membership =
+            (proptest::collection::vec(value, 1..=4), any::<bool>()).prop_map(
+                |(members, is_in)| {
+                    if is_in {
+                        QC::field("a").is_in(members.as_slice())
+                    } else {
+                        QC::field("a").not_in(members.as_slice())
+                    }
+                },
+            );
+
+        prop_oneof![equality, set_membership].prop_recursive(3, 8, 2, |inner| {
+            prop_oneof![
+                (inner.clone(), inner.clone()).prop_map(|(l, r)| l & r),
+                (inner.clone(), inner.clone()).prop_map(|(l, r)| l | r),
+                inner.prop_map(|e| !e)
+            ]
+        })
+    }
+
+    #[test]
+    fn proptest_evaluate_readback() {
+        let schema = Rc::new(
+            crate::tests::examples::quickstart::Builder::new(ArrayType::Sparse)
+                .build(),
+        );
+
+        let strategy = any_with::<SparseWriteInput>(SparseWriteParameters {
+            schema: Some(Rc::clone(&schema)),
+            ..Default::default()
+        })
+        .prop_flat_map(|input| {
+            let values = match input.data.fields().get("a") {
+                Some(FieldData::Int32(a)) => a.clone(),
+                _ => vec![],
+            };
+            (Just(input), prop_condition(values))
+        });
+
+        proptest!(|((input, condition) in strategy)| {
+            do_evaluate_readback(Rc::clone(&schema), input, condition)?;
+        });
+    }
+
+    /// Returns whether TileDB accepts each set membership predicate
+    /// of `condition`, which requires at least one member.
+    fn has_set_members(condition: &QC) -> bool {
+        match condition {
+            QC::Cond(Predicate::SetMembership(s)) => s.members().len() > 0,
+            QC::Cond(_) => true,
+            QC::Comb { lhs, rhs, .. } => {
+                has_set_members(lhs) && has_set_members(rhs)
+            }
+            QC::Negate(e) => has_set_members(e),
+        }
+    }
+
+    /// Evaluates generated conditions over nullable fixed-size,
+    /// floating-point and var-sized string attributes.
+    #[test]
+    fn proptest_evaluate_readback_attributes() {
+        let schema = Rc::new({
+            let mut b = crate::tests::examples::quickstart::Builder::new(
+                ArrayType::Sparse,
+            );
+            b.schema.attributes[0].nullability = Some(true);
+            b.schema.attributes.push(AttributeData {
+                name: "f".to_owned(),
+                datatype: Datatype::Float64,
+                nullability: Some(true),
+                ..Default::default()
+            });
+            b.schema.attributes.push(AttributeData {
+                name: "s".to_owned(),
+                datatype: Datatype::StringUtf8,
+                nullability: Some(true),
+                cell_val_num: Some(CellValNum::Var),
+                ..Default::default()
+            });
+            b.build()
+        });
+
+        let fields = vec![
+            ("a".to_owned(), Datatype::Int32),
+            ("f".to_owned(), Datatype::Float64),
+            ("s".to_owned(), Datatype::StringUtf8),
+        ];
+        let strategy = (
+            any_with::<SparseWriteInput>(SparseWriteParameters {
+                schema: Some(Rc::clone(&schema)),
+                ..Default::default()
+            }),
+            prop_query_condition(fields)
+                .prop_filter("Empty set membership", has_set_members),
+        );
+
+        proptest!(|((input, condition) in strategy)| {
+            do_evaluate_readback(Rc::clone(&schema), input, condition)?;
+        });
+    }
+}
@@ -1,9 +1,17 @@
-#[cfg(feature = "proptest-strategies")]
+#[cfg(any(test, feature = "proptest-strategies"))]
 pub mod strategy;
 
+mod eval;
+mod parse;
+mod simplify;
+
+pub use eval::{CellValues, ConditionInput, FieldValues};
+pub use parse::ParseError;
+pub use simplify::SimplifiedCondition;
+
 use std::fmt::{Display, Formatter, Result as FmtResult};
 use std::hash::{Hash, Hasher};
-use std::ops::{BitAnd, BitOr, Deref, Not};
+use std::ops::{Add, BitAnd, BitOr, Deref, Div, Mul, Not, Sub};
 
 use anyhow::anyhow;
 
@@ -37,6 +45,32 @@ impl EqualityOp {
             Self::Greater => ffi::tiledb_query_condition_op_t_TILEDB_GT,
         }
     }
+
+    /// Returns the operation which is satisfied by a value exactly when
+    /// this operation is not, as TileDB does when negating a condition.
+    pub fn negate(&self) -> Self {
+        match self {
+            Self::Less => Self::GreaterEqual,
+            Self::LessEqual => Self::Greater,
+            Self::Equal => Self::NotEqual,
+            Self::NotEqual => Self::Equal,
+            Self::GreaterEqual => Self::Less,
+            Self::Greater => Self::LessEqual,
+        }
+    }
+
+    /// Returns the operation which is satisfied with its operands swapped,
+    /// i.e. `a < b` is equivalent to `b > a`.
+    pub fn reverse(&self) -> Self {
+        match self {
+            Self::Less => Self::Greater,
+            Self::LessEqual => Self::GreaterEqual,
+            Self::Equal => Self::Equal,
+            Self::NotEqual => Self::NotEqual,
+            Self::GreaterEqual => Self::LessEqual,
+            Self::Greater => Self::Less,
+        }
+    }
 }
 
 impl Display for EqualityOp {
@@ -66,6 +100,13 @@ impl SetMembershipOp {
             Self::NotIn => ffi::tiledb_query_condition_op_t_TILEDB_NOT_IN,
         }
     }
+
+    pub fn negate(&self) -> Self {
+        match self {
+            Self::In => Self::NotIn,
+            Self::NotIn => Self::In,
+        }
+    }
 }
 
 impl Display for SetMembershipOp {
@@ -91,6 +132,13 @@ impl NullnessOp {
             Self::NotNull => ffi::tiledb_query_condition_op_t_TILEDB_NE,
         }
     }
+
+    pub fn negate(&self) -> Self {
+        match self {
+            Self::IsNull => Self::NotNull,
+            Self::NotNull => Self::IsNull,
+        }
+    }
 }
 
 impl Display for NullnessOp {
@@ -120,6 +168,14 @@ impl CombinationOp {
             Self::Or => ffi::tiledb_query_condition_combination_op_t_TILEDB_OR,
         }
     }
+
+    /// Returns the dual of this operation, for applying De Morgan's laws.
+    pub fn negate(&self) -> Self {
+        match self {
+            Self::And => Self::Or,
+            Self::Or => Self::And,
+        }
+    }
 }
 
 impl Display for CombinationOp {
@@ -131,6 +187,26 @@ impl Display for CombinationOp {
     }
 }
 
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub enum ArithmeticOp {
+    Add,
+    Subtract,
+    Multiply,
+    Divide,
+}
+
+impl Display for ArithmeticOp {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        match self {
+            Self::Add => write!(f, "+"),
+            Self::Subtract => write!(f, "-"),
+            Self::Multiply => write!(f, "*"),
+            Self::Divide => write!(f, "/"),
+        }
+    }
+}
+
 #[derive(Clone, Debug)]
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub enum Literal {
@@ -148,7 +224,7 @@ pub enum Literal {
 }
 
 impl Literal {
-    fn to_bytes(&self) -> Vec<u8> {
+    pub(crate) fn to_bytes(&self) -> Vec<u8> {
         match self {
             Self::UInt8(val) => val.to_le_bytes().to_vec(),
             Self::UInt16(val) => val.to_le_bytes().to_vec(),
@@ -471,6 +547,8 @@ pub struct EqualityPredicate {
     field: String,
     op: EqualityOp,
     value: Literal,
+    #[cfg_attr(feature = "serde", serde(default))]
+    use_enumeration: bool,
 }
 
 impl EqualityPredicate {
@@ -486,6 +564,12 @@ impl EqualityPredicate {
         &self.value
     }
 
+    /// Returns whether the value is compared with the values of the
+    /// field's enumeration rather than with its keys.
+    pub fn use_enumeration(&self) -> bool {
+        self.use_enumeration
+    }
+
     fn build(&self, ctx: &Context) -> TileDBResult<RawQueryCondition> {
         let mut c_cond: *mut ffi::tiledb_query_condition_t = out_ptr!();
         ctx.capi_call(|ctx| unsafe {
@@ -510,11 +594,14 @@ impl EqualityPredicate {
                 c_op,
             )
         })?;
+        raw.set_use_enumeration(ctx, self.use_enumeration)?;
 
         Ok(raw)
     }
 }
 
+/// The text does not record whether the predicate uses the enumeration
+/// of its field; see [Field::use_enumeration].
 impl Display for EqualityPredicate {
     fn fmt(&self, f: &mut Formatter) -> FmtResult {
         write!(f, "{} {} {}", self.field, self.op, self.value)
@@ -527,6 +614,8 @@ pub struct SetMembershipPredicate {
     field: String,
     op: SetMembershipOp,
     members: SetMembers,
+    #[cfg_attr(feature = "serde", serde(default))]
+    use_enumeration: bool,
 }
 
 impl SetMembershipPredicate {
@@ -542,6 +631,12 @@ impl SetMembershipPredicate {
         &self.members
     }
 
+    /// Returns whether the members are compared with the values of the
+    /// field's enumeration rather than with its keys.
+    pub fn use_enumeration(&self) -> bool {
+        self.use_enumeration
+    }
+
     fn build(&self, ctx: &Context) -> TileDBResult<RawQueryCondition> {
         // First things first, sets require a non-zero length vector. I would
         // prefer if we couldn't even create SetMemberValues with zero length
@@ -635,10 +730,15 @@ impl SetMembershipPredicate {
             })?;
         }
 
-        Ok(RawQueryCondition::Owned(c_cond))
+        let raw = RawQueryCondition::Owned(c_cond);
+        raw.set_use_enumeration(ctx, self.use_enumeration)?;
+
+        Ok(raw)
     }
 }
 
+/// The text does not record whether the predicate uses the enumeration
+/// of its field; see [Field::use_enumeration].
 impl Display for SetMembershipPredicate {
     fn fmt(&self, f: &mut Formatter) -> FmtResult {
         write!(f, "{} {} {}", self.field, self.op, self.members)
@@ -693,12 +793,254 @@ impl Display for NullnessPredicate {
     }
 }
 
+/// A value computed for each cell from its fields and literals,
+/// which can be compared with another operand.
+///
+/// Arithmetic on integers is exact, and produces null if the result
+/// overflows or divides by zero. Arithmetic which involves a
+/// floating-point operand is done in double precision.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub enum Operand {
+    Field(String),
+    Literal(Literal),
+    Arithmetic {
+        lhs: Box<Operand>,
+        rhs: Box<Operand>,
+        op: ArithmeticOp,
+    },
+}
+
+impl Operand {
+    pub fn field<F: AsRef<str>>(field: F) -> Self {
+        Self::Field(field.as_ref().to_owned())
+    }
+
+    pub fn lt<V: Into<Operand>>(self, value: V) -> QueryConditionExpr {
+        self.compare(EqualityOp::Less, value.into())
+    }
+
+    pub fn le<V: Into<Operand>>(self, value: V) -> QueryConditionExpr {
+        self.compare(EqualityOp::LessEqual, value.into())
+    }
+
+    pub fn eq<V: Into<Operand>>(self, value: V) -> QueryConditionExpr {
+        self.compare(EqualityOp::Equal, value.into())
+    }
+
+    pub fn ne<V: Into<Operand>>(self, value: V) -> QueryConditionExpr {
+        self.compare(EqualityOp::NotEqual, value.into())
+    }
+
+    pub fn ge<V: Into<Operand>>(self, value: V) -> QueryConditionExpr {
+        self.compare(EqualityOp::GreaterEqual, value.into())
+    }
+
+    pub fn gt<V: Into<Operand>>(self, value: V) -> QueryConditionExpr {
+        self.compare(EqualityOp::Greater, value.into())
+    }
+
+    /// Returns the predicate `self op rhs`. Comparisons of a field with a
+    /// literal are [EqualityPredicate]s, so that TileDB can apply them.
+    fn compare(self, op: EqualityOp, rhs: Operand) -> QueryConditionExpr {
+        let predicate = match (self, rhs) {
+            (Self::Field(field), Self::Literal(value)) => {
+                Predicate::Equality(EqualityPredicate {
+                    field,
+                    op,
+                    value,
+                    use_enumeration: false,
+                })
+            }
+            (Self::Literal(value), Self::Field(field)) => {
+                Predicate::Equality(EqualityPredicate {
+                    field,
+                    op: op.reverse(),
+                    value,
+                    use_enumeration: false,
+                })
+            }
+            (lhs, rhs) => {
+                Predicate::Comparison(ComparisonPredicate { lhs, op, rhs })
+            }
+        };
+        QueryConditionExpr::Cond(predicate)
+    }
+
+    /// Writes this operand as an operand of an arithmetic operation.
+    fn fmt_nested(&self, f: &mut Formatter) -> FmtResult {
+        if matches!(self, Self::Arithmetic { .. }) {
+            write!(f, "({})", self)
+        } else {
+            write!(f, "{}", self)
+        }
+    }
+
+    fn collect_fields<'a>(&'a self, fields: &mut Vec<&'a str>) {
+        match self {
+            Self::Field(ref field) => collect_field(fields, field),
+            Self::Literal(_) => {}
+            Self::Arithmetic {
+                ref lhs, ref rhs, ..
+            } => {
+                lhs.collect_fields(fields);
+                rhs.collect_fields(fields);
+            }
+        }
+    }
+}
+
+/// Literals are written so that parsing the text produces the same
+/// operand. `Int64` and `Float64` numbers are written as such, and other
+/// numbers have a suffix for their type, such as `5u8` or `1.5f32`.
+/// Infinite and NaN values have a sign, so that they are not field names.
+impl Display for Operand {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        match self {
+            Self::Field(ref field) => write!(f, "{}", field),
+            Self::Literal(Literal::UInt8(value)) => write!(f, "{}u8", value),
+            Self::Literal(Literal::UInt16(value)) => write!(f, "{}u16", value),
+            Self::Literal(Literal::UInt32(value)) => write!(f, "{}u32", value),
+            Self::Literal(Literal::UInt64(value)) => write!(f, "{}u64", value),
+            Self::Literal(Literal::Int8(value)) => write!(f, "{}i8", value),
+            Self::Literal(Literal::Int16(value)) => write!(f, "{}i16", value),
+            Self::Literal(Literal::Int32(value)) => write!(f, "{}i32", value),
+            Self::Literal(Literal::Int64(value)) => write!(f, "{}", value),
+            Self::Literal(Literal::Float32(value)) if !value.is_finite() => {
+                fmt_non_finite(f, f64::from(*value))?;
+                write!(f, "f32")
+            }
+            Self::Literal(Literal::Float64(value)) if !value.is_finite() => {
+                fmt_non_finite(f, *value)
+            }
+            // keep the decimal point so that the text is not an integer
+            Self::Literal(Literal::Float32(value)) => {
+                write!(f, "{:?}f32", value)
+            }
+            Self::Literal(Literal::Float64(value)) => write!(f, "{:?}", value),
+            Self::Literal(ref value @ Literal::String(_)) => {
+                write!(f, "{}", value)
+            }
+            Self::Arithmetic {
+                ref lhs,
+                ref rhs,
+                op,
+            } => {
+                lhs.fmt_nested(f)?;
+                write!(f, " {} ", op)?;
+                rhs.fmt_nested(f)
+            }
+        }
+    }
+}
+
+/// Writes an infinite or NaN value with a sign, so that its text
+/// is a number rather than a field name.
+fn fmt_non_finite(f: &mut Formatter, value: f64) -> FmtResult {
+    if value.is_nan() {
+        write!(f, "+NaN")
+    } else {
+        write!(f, "{:+}", value)
+    }
+}
+
+impl From<Field> for Operand {
+    fn from(value: Field) -> Operand {
+        Operand::Field(value.field)
+    }
+}
+
+impl From<Literal> for Operand {
+    fn from(value: Literal) -> Operand {
+        Operand::Literal(value)
+    }
+}
+
+macro_rules! operand_from_impl {
+    ($($ty:ty),+) => {
+        $(
+            impl From<$ty> for Operand {
+                fn from(value: $ty) -> Operand {
+                    Operand::Literal(Literal::from(value))
+                }
+            }
+        )+
+    };
+}
+
+operand_from_impl!(u8, u16, u32, u64, i8, i16, i32, i64, f32, f64, String);
+operand_from_impl!(&str);
+
+macro_rules! operand_arithmetic_impl {
+    ($Trait:ident, $method:ident, $op:expr) => {
+        impl<V: Into<Operand>> $Trait<V> for Operand {
+            type Output = Operand;
+            fn $method(self, rhs: V) -> Self::Output {
+                Operand::Arithmetic {
+                    lhs: Box::new(self),
+                    rhs: Box::new(rhs.into()),
+                    op: $op,
+                }
+            }
+        }
+
+        impl<V: Into<Operand>> $Trait<V> for Field {
+            type Output = Operand;
+            fn $method(self, rhs: V) -> Self::Output {
+                Operand::from(self).$method(rhs)
+            }
+        }
+    };
+}
+
+operand_arithmetic_impl!(Add, add, ArithmeticOp::Add);
+operand_arithmetic_impl!(Sub, sub, ArithmeticOp::Subtract);
+operand_arithmetic_impl!(Mul, mul, ArithmeticOp::Multiply);
+operand_arithmetic_impl!(Div, div, ArithmeticOp::Divide);
+
+/// Compares two operands which are computed from the fields of each cell,
+/// such as `end_time > start_time` or `a + b < 10`.
+///
+/// TileDB cannot apply these predicates itself, so a condition which
+/// contains them is evaluated on the results of each step of a read
+/// (see [QueryBuilder::query_condition]).
+///
+/// [QueryBuilder::query_condition]: crate::query::QueryBuilder::query_condition
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub struct ComparisonPredicate {
+    lhs: Operand,
+    op: EqualityOp,
+    rhs: Operand,
+}
+
+impl ComparisonPredicate {
+    pub fn lhs(&self) -> &Operand {
+        &self.lhs
+    }
+
+    pub fn operation(&self) -> EqualityOp {
+        self.op
+    }
+
+    pub fn rhs(&self) -> &Operand {
+        &self.rhs
+    }
+}
+
+impl Display for ComparisonPredicate {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        write!(f, "{} {} {}", self.lhs, self.op, self.rhs)
+    }
+}
+
 #[derive(Clone, Debug, Eq, Hash, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub enum Predicate {
     Equality(EqualityPredicate),
     SetMembership(SetMembershipPredicate),
     Nullness(NullnessPredicate),
+    Comparison(ComparisonPredicate),
 }
 
 impl Predicate {
@@ -707,16 +1049,39 @@ impl Predicate {
             Self::Equality(pred) => pred.build(ctx),
             Self::SetMembership(pred) => pred.build(ctx),
             Self::Nullness(pred) => pred.build(ctx),
+            Self::Comparison(pred) => Err(Error::InvalidArgument(anyhow!(
+                "Predicate '{}' cannot be applied by TileDB",
+                pred
+            ))),
+        }
+    }
+
+    fn collect_fields<'a>(&'a self, fields: &mut Vec<&'a str>) {
+        match self {
+            Self::Equality(ref e) => collect_field(fields, &e.field),
+            Self::SetMembership(ref s) => collect_field(fields, &s.field),
+            Self::Nullness(ref n) => collect_field(fields, &n.field),
+            Self::Comparison(ref c) => {
+                c.lhs.collect_fields(fields);
+                c.rhs.collect_fields(fields);
+            }
         }
     }
 }
 
+fn collect_field<'a>(fields: &mut Vec<&'a str>, field: &'a str) {
+    if !fields.contains(&field) {
+        fields.push(field)
+    }
+}
+
 impl Display for Predicate {
     fn fmt(&self, f: &mut Formatter) -> FmtResult {
         match self {
             Self::Equality(ref e) => write!(f, "{}", e),
             Self::SetMembership(ref m) => write!(f, "{}", m),
             Self::Nullness(ref n) => write!(f, "{}", n),
+            Self::Comparison(ref c) => write!(f, "{}", c),
         }
     }
 }
@@ -728,72 +1093,50 @@ pub struct Field {
 }
 
 impl Field {
+    /// Returns a field which is compared with the values of its
+    /// enumeration rather than with its keys.
+    ///
+    /// For example, if attribute `color` uses an enumeration with values
+    /// `["red", "green", "blue"]`, then `color = 'green'` using the
+    /// enumeration selects the cells whose key is `1`.
+    ///
+    /// The text of the resulting condition does not record that the
+    /// enumeration is used, so parsing that text produces a condition
+    /// which compares the keys.
+    pub fn use_enumeration(self) -> EnumField {
+        EnumField(self)
+    }
+
     pub fn lt<V: Into<Literal>>(self, value: V) -> QueryConditionExpr {
-        QueryConditionExpr::Cond(Predicate::Equality(EqualityPredicate {
-            field: self.field,
-            op: EqualityOp::Less,
-            value: value.into(),
-        }))
+        self.equality(EqualityOp::Less, value.into(), false)
     }
 
     pub fn le<V: Into<Literal>>(self, value: V) -> QueryConditionExpr {
-        QueryConditionExpr::Cond(Predicate::Equality(EqualityPredicate {
-            field: self.field,
-            op: EqualityOp::LessEqual,
-            value: value.into(),
-        }))
+        self.equality(EqualityOp::LessEqual, value.into(), false)
     }
 
     pub fn eq<V: Into<Literal>>(self, value: V) -> QueryConditionExpr {
-        QueryConditionExpr::Cond(Predicate::Equality(EqualityPredicate {
-            field: self.field,
-            op: EqualityOp::Equal,
-            value: value.into(),
-        }))
+        self.equality(EqualityOp::Equal, value.into(), false)
     }
 
     pub fn ne<V: Into<Literal>>(self, value: V) -> QueryConditionExpr {
-        QueryConditionExpr::Cond(Predicate::Equality(EqualityPredicate {
-            field: self.field,
-            op: EqualityOp::NotEqual,
-            value: value.into(),
-        }))
+        self.equality(EqualityOp::NotEqual, value.into(), false)
     }
 
     pub fn ge<V: Into<Literal>>(self, value: V) -> QueryConditionExpr {
-        QueryConditionExpr::Cond(Predicate::Equality(EqualityPredicate {
-            field: self.field,
-            op: EqualityOp::GreaterEqual,
-            value: value.into(),
-        }))
+        self.equality(EqualityOp::GreaterEqual, value.into(), false)
     }
 
     pub fn gt<V: Into<Literal>>(self, value: V) -> QueryConditionExpr {
-        QueryConditionExpr::Cond(Predicate::Equality(EqualityPredicate {
-            field: self.field,
-            op: EqualityOp::Greater,
-            value: value.into(),
-        }))
+        self.equality(EqualityOp::Greater, value.into(), false)
     }
 
     pub fn is_in<V: Into<SetMembers>>(self, value: V) -> QueryConditionExpr {
-        QueryConditionExpr::Cond(Predicate::SetMembership(
-            SetMembershipPredicate {
-                field: self.field,
-                op: SetMembershipOp::In,
-                members: value.into(),
-            },
-        ))
+        self.set_membership(SetMembershipOp::In, value.into(), false)
     }
 
     pub fn not_in<V: Into<SetMembers>>(self, value: V) -> QueryConditionExpr {
-        QueryConditionExpr::Cond(Predicate::SetMembership(
-            SetMembershipPredicate {
-                field: self.field,
-                op: SetMembershipOp::NotIn,
-                members: value.into(),
-            },
-        ))
+        self.set_membership(SetMembershipOp::NotIn, value.into(), false)
     }
 
     pub fn is_null(self) -> QueryConditionExpr {
@@ -809,6 +1152,86 @@ impl Field {
             op: NullnessOp::NotNull,
         }))
     }
+
+    fn equality(
+        self,
+        op: EqualityOp,
+        value: Literal,
+        use_enumeration: bool,
+    ) -> QueryConditionExpr {
+        QueryConditionExpr::Cond(Predicate::Equality(EqualityPredicate {
+            field: self.field,
+            op,
+            value,
+            use_enumeration,
+        }))
+    }
+
+    fn set_membership(
+        self,
+        op: SetMembershipOp,
+        members: SetMembers,
+        use_enumeration: bool,
+    ) -> QueryConditionExpr {
+        QueryConditionExpr::Cond(Predicate::SetMembership(
+            SetMembershipPredicate {
+                field: self.field,
+                op,
+                members,
+                use_enumeration,
+            },
+        ))
+    }
+}
+
+/// A field which is compared with the values of its enumeration.
+/// See [Field::use_enumeration].
+///
+/// An `EnumField` can only be compared with literals.
+///
+/// ```compile_fail
+/// # use tiledb_api::query::condition::QueryConditionExpr as QC;
+/// let _ = QC::field("color").use_enumeration().eq(QC::field("shade"));
+/// ```
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub struct EnumField(Field);
+
+impl EnumField {
+    pub fn lt<V: Into<Literal>>(self, value: V) -> QueryConditionExpr {
+        self.0.equality(EqualityOp::Less, value.into(), true)
+    }
+
+    pub fn le<V: Into<Literal>>(self, value: V) -> QueryConditionExpr {
+        self.0.equality(EqualityOp::LessEqual, value.into(), true)
+    }
+
+    pub fn eq<V: Into<Literal>>(self, value: V) -> QueryConditionExpr {
+        self.0.equality(EqualityOp::Equal, value.into(), true)
+    }
+
+    pub fn ne<V: Into<Literal>>(self, value: V) -> QueryConditionExpr {
+        self.0.equality(EqualityOp::NotEqual, value.into(), true)
+    }
+
+    pub fn ge<V: Into<Literal>>(self, value: V) -> QueryConditionExpr {
+        self.0
+            .equality(EqualityOp::GreaterEqual, value.into(), true)
+    }
+
+    pub fn gt<V: Into<Literal>>(self, value: V) -> QueryConditionExpr {
+        self.0.equality(EqualityOp::Greater, value.into(), true)
+    }
+
+    pub fn is_in<V: Into<SetMembers>>(self, value: V) -> QueryConditionExpr {
+        self.0
+            .set_membership(SetMembershipOp::In, value.into(), true)
+    }
+
+    pub fn not_in<V: Into<SetMembers>>(self, value: V) -> QueryConditionExpr {
+        self.0
+            .set_membership(SetMembershipOp::NotIn, value.into(), true)
+    }
 }
 
 #[derive(Clone, Debug, Eq, Hash, PartialEq)]
@@ -830,6 +1253,77 @@ impl QueryConditionExpr {
         }
     }
 
+    /// Returns the names of the fields which this condition refers to,
+    /// in order of their first appearance.
+    pub fn fields(&self) -> Vec<&str> {
+        let mut fields = vec![];
+        self.collect_fields(&mut fields);
+        fields
+    }
+
+    fn collect_fields<'a>(&'a self, fields: &mut Vec<&'a str>) {
+        match self {
+            Self::Cond(ref predicate) => predicate.collect_fields(fields),
+            Self::Comb {
+                ref lhs, ref rhs, ..
+            } => {
+                lhs.collect_fields(fields);
+                rhs.collect_fields(fields);
+            }
+            Self::Negate(ref expr) => expr.collect_fields(fields),
+        }
+    }
+
+    /// Returns whether TileDB can apply this condition when reading,
+    /// which is the case unless it contains a [ComparisonPredicate].
+    pub fn is_pushdown(&self) -> bool {
+        match self {
+            Self::Cond(ref predicate) => {
+                !matches!(predicate, Predicate::Comparison(_))
+            }
+            Self::Comb {
+                ref lhs, ref rhs, ..
+            } => lhs.is_pushdown() && rhs.is_pushdown(),
+            Self::Negate(ref expr) => expr.is_pushdown(),
+        }
+    }
+
+    /// Splits this condition into a condition which TileDB can apply
+    /// when reading, and a residual condition which must be evaluated on
+    /// the results of the read. A cell satisfies this condition if and
+    /// only if it satisfies both parts.
+    ///
+    /// Only the top-level chain of `AND` is split, so for example
+    /// all of `(a < b) OR (c < 5)` is residual.
+    pub fn split_pushdown(self) -> (Option<Self>, Option<Self>) {
+        fn and(
+            lhs: Option<QueryConditionExpr>,
+            rhs: Option<QueryConditionExpr>,
+        ) -> Option<QueryConditionExpr> {
+            match (lhs, rhs) {
+                (Some(lhs), Some(rhs)) => Some(lhs & rhs),
+                (lhs, rhs) => lhs.or(rhs),
+            }
+        }
+
+        match self {
+            Self::Comb {
+                lhs,
+                rhs,
+                op: CombinationOp::And,
+            } => {
+                let (lhs_pushdown, lhs_residual) = lhs.split_pushdown();
+                let (rhs_pushdown, rhs_residual) = rhs.split_pushdown();
+                (
+                    and(lhs_pushdown, rhs_pushdown),
+                    and(lhs_residual, rhs_residual),
+                )
+            }
+            expr if expr.is_pushdown() => (Some(expr), None),
+            expr => (None, Some(expr)),
+        }
+    }
+
     pub(crate) fn build(
         &self,
         ctx: &Context,
@@ -932,6 +1426,25 @@ impl Deref for RawQueryCondition {
     }
 }
 
+impl RawQueryCondition {
+    fn set_use_enumeration(
+        &self,
+        ctx: &Context,
+        use_enumeration: bool,
+    ) -> TileDBResult<()> {
+        let c_cond = **self;
+        let c_use_enumeration = if use_enumeration { 1 } else { 0 };
+        ctx.capi_call(|ctx| unsafe {
+            ffi::tiledb_query_condition_set_use_enumeration(
+                ctx,
+                c_cond,
+                c_use_enumeration,
+            )
+        })?;
+        Ok(())
+    }
+}
+
 impl Drop for RawQueryCondition {
     fn drop(&mut self) {
         let RawQueryCondition::Owned(ref mut ffi) = *self;
@@ -943,8 +1456,15 @@ impl Drop for RawQueryCondition {
 
 #[cfg(test)]
 mod tests {
+    use std::rc::Rc;
+
+    use tiledb_pod::array::EnumerationData;
+
     use super::QueryConditionExpr as QC;
     use super::*;
+    use crate::query::ReadQueryBuilder;
+    use crate::tests::examples::quickstart;
+    use crate::tests::prelude::*;
 
     #[test]
     fn basic_op_test() -> TileDBResult<()> {
@@ -988,6 +1508,94 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn basic_enumeration_test() -> TileDBResult<()> {
+        let ctx = Context::new()?;
+
+        let qc = QC::field("color").use_enumeration().eq("red");
+        if let QC::Cond(Predicate::Equality(ref e)) = qc {
+            assert!(e.use_enumeration());
+        } else {
+            unreachable!()
+        }
+        assert!(qc.build(&ctx).is_ok());
+
+        let qc = QC::field("color")
+            .use_enumeration()
+            .not_in(&["red", "blue"][..]);
+        if let QC::Cond(Predicate::SetMembership(ref s)) = qc {
+            assert!(s.use_enumeration());
+        } else {
+            unreachable!()
+        }
+        assert!(qc.build(&ctx).is_ok());
+
+        let qc = QC::field("color").eq(1);
+        if let QC::Cond(Predicate::Equality(ref e)) = qc {
+            assert!(!e.use_enumeration());
+        } else {
+            unreachable!()
+        }
+
+        // the keys of `color` index ["red", "green", "blue"]
+        let mut array = TestArray::new("basic_enumeration_test", {
+            let mut b = quickstart::Builder::new(ArrayType::Sparse);
+            b.schema.attributes[0].name = "color".to_owned();
+            b.schema.attributes[0].enumeration = Some("colors".to_owned());
+            b.schema.enumerations.push(EnumerationData {
+                name: "colors".to_owned(),
+                datatype: Datatype::StringAscii,
+                cell_val_num: Some(CellValNum::Var),
+                ordered: None,
+                data: "redgreenblue".as_bytes().to_vec().into_boxed_slice(),
+                offsets: Some(vec![0, 3, 8].into_boxed_slice()),
+            });
+            Rc::new(b.build())
+        })?;
+        {
+            let w = WriteBuilder::new(array.for_write()?)?
+                .data_typed("rows", &vec![1, 2, 3, 4])?
+                .data_typed("cols", &vec![1, 2, 3, 4])?
+                .data_typed("color", &vec![0, 1, 2, 1])?
+                .build();
+            w.submit()?;
+            w.finalize()?;
+        }
+
+        let read = |qc: QC| -> TileDBResult<(Vec<i32>, Vec<i32>)> {
+            let mut q = ReadBuilder::new(array.for_read()?)?
+                .layout(QueryLayout::RowMajor)?
+                .query_condition(qc)?
+                .register_constructor::<_, Vec<i32>>(
+                    "rows",
+                    Default::default(),
+                )?
+                .register_constructor::<_, Vec<i32>>(
+                    "color",
+                    Default::default(),
+                )?
+                .build();
+            let (color, (rows, _)) = q.execute()?;
+            Ok((rows, color))
+        };
+
+        assert_eq!(
+            (vec![2, 4], vec![1, 1]),
+            read(QC::field("color").use_enumeration().eq("green"))?
+        );
+        assert_eq!(
+            (vec![2, 4], vec![1, 1]),
+            read(
+                QC::field("color")
+                    .use_enumeration()
+                    .not_in(&["red", "blue"][..])
+            )?
+        );
+        assert_eq!((vec![3], vec![2]), read(QC::field("color").eq(2))?);
+
+        Ok(())
+    }
+
     #[test]
     fn basic_combine_test() -> TileDBResult<()> {
         let qc1 = QC::field("x").lt(5);
@@ -1047,6 +1655,130 @@ mod tests {
         );
     }
 
+    #[test]
+    fn comparison_test() -> TileDBResult<()> {
+        // a field compared with a literal is an equality predicate
+        assert_eq!(QC::field("x").lt(5), Operand::field("x").lt(5));
+        assert_eq!(QC::field("x").gt(5), Operand::from(5).lt(QC::field("x")));
+
+        let qc = Operand::field("end").gt(Operand::field("start"));
+        assert!(matches!(qc, QC::Cond(Predicate::Comparison(_))));
+        assert!(!qc.is_pushdown());
+
+        let ctx = Context::new()?;
+        assert!(matches!(qc.build(&ctx), Err(Error::InvalidArgument(_))));
+
+        Ok(())
+    }
+
+    #[test]
+    fn comparison_read_test() -> TileDBResult<()> {
+        use crate::query::read::AggregateQueryBuilder;
+
+        let mut array = TestArray::new(
+            "comparison_read_test",
+            Rc::new(quickstart::Builder::new(ArrayType::Sparse).build()),
+        )?;
+        {
+            let w = WriteBuilder::new(array.for_write()?)?
+                .data_typed("rows", &vec![1, 1, 2, 3, 3, 4])?
+                .data_typed("cols", &vec![1, 4, 2, 1, 3, 4])?
+                .data_typed("a", &vec![10, 11, 12, 13, 14, 15])?
+                .build();
+            w.submit()?;
+            w.finalize()?;
+        }
+
+        // `rows` and `cols` are read to evaluate the conditions,
+        // but are not part of the results
+        let read = |conditions: &[QC]| -> TileDBResult<Vec<i32>> {
+            let mut b = ReadBuilder::new(array.for_read()?)?
+                .layout(QueryLayout::RowMajor)?;
+            for qc in conditions {
+                b = b.query_condition(qc.clone())?;
+            }
+            let mut q = b
+                .register_constructor::<_, Vec<i32>>("a", Default::default())?
+                .build();
+            let (a, _) = q.execute()?;
+            Ok(a)
+        };
+
+        let rows = || Operand::field("rows");
+        let cols = || Operand::field("cols");
+
+        assert_eq!(vec![11], read(&[rows().lt(cols())])?);
+        assert_eq!(
+            vec![10, 13],
+            read(&[rows().gt(cols()) | QC::field("a").eq(10)])?
+        );
+        assert_eq!(
+            vec![11, 12, 14],
+            read(&[
+                rows().le(cols()) & QC::field("a").ge(11),
+                QC::field("a").lt(15)
+            ])?
+        );
+
+        // aggregates do not see the cells which TileDB reads
+        let aggregate = ReadBuilder::new(array.for_read()?)?
+            .query_condition(rows().lt(cols()))?
+            .count()?
+            .build()
+            .execute();
+        assert!(matches!(aggregate, Err(Error::InvalidArgument(_))));
+
+        // dense reads do not remove cells which do not satisfy a condition
+        let dense = dense_array("comparison_read_test_dense", 4, 4, 2)?;
+        assert!(matches!(
+            ReadBuilder::new(dense.for_read()?)?
+                .query_condition(rows().lt(cols())),
+            Err(Error::InvalidArgument(_))
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn split_pushdown_test() {
+        let a = || QC::field("a").lt(5);
+        let b = || QC::field("b").is_null();
+        let c = || (QC::field("a") + QC::field("c")).ge(QC::field("b"));
+
+        assert_eq!((Some(a() & b()), None), (a() & b()).split_pushdown());
+        assert_eq!((None, Some(c())), c().split_pushdown());
+        assert_eq!(
+            (Some(a() & b()), Some(c())),
+            ((a() & c()) & b()).split_pushdown()
+        );
+        assert_eq!((None, Some(a() | c())), (a() | c()).split_pushdown());
+        assert_eq!(
+            (Some(b()), Some(!(a() & c()))),
+            (b() & !(a() & c())).split_pushdown()
+        );
+
+        assert_eq!(vec!["a", "b", "c"], (a() & b() & c()).fields());
+    }
+
+    #[test]
+    fn display_operand() {
+        let a = || Operand::field("a");
+
+        assert_eq!("a > b", a().gt(Operand::field("b")).to_string());
+        assert_eq!("a + 1i32 < 10i32", (a() + 1).lt(10).to_string());
+        assert_eq!("a + 1 < 10u8", (a() + 1i64).lt(10u8).to_string());
+        assert_eq!("a * 2.0 = a", (a() * 2.0).eq(a()).to_string());
+        assert_eq!("a * 2.0f32 = a", (a() * 2.0f32).eq(a()).to_string());
+        assert_eq!(
+            "a * +inf < -inff32",
+            (a() * f64::INFINITY).lt(f32::NEG_INFINITY).to_string()
+        );
+        assert_eq!(
+            "(a - -1i32) * (a / 'x') <> a",
+            ((a() - -1) * (a() / "x")).ne(a()).to_string()
+        );
+    }
+
     #[test]
     fn display_literal() {
         assert_eq!("'foo'", Literal::String("foo".to_owned()).to_string());
@@ -0,0 +1,972 @@
+//! Parses query condition expressions from text.
+//!
+//! The grammar accepts the text produced by the [Display] implementation
+//! of [QueryConditionExpr], as well as the same expressions written with
+//! fewer parentheses. Keywords are case-insensitive.
+//!
+//! ```text
+//! expr      := term ( OR term )*
+//! term      := factor ( AND factor )*
+//! factor    := NOT factor | '(' expr ')' | predicate
+//! predicate := operand ( '<' | '<=' | '=' | '<>' | '!=' | '>=' | '>' ) operand
+//!            | field [ NOT ] IN '(' [ literal ( ',' literal )* ] ')'
+//!            | field IS [ NOT ] NULL
+//! operand   := product ( ( '+' | '-' ) product )*
+//! product   := atom ( ( '*' | '/' ) atom )*
+//! atom      := field | literal | '(' operand ')'
+//! ```
+//!
+//! Literals are typed using the datatype of the field which they are
+//! compared with. Other literals, such as those in arithmetic, are
+//! typed by a suffix such as `5u8` or `1.5f32`, or else are `Int64`,
+//! `UInt64` or `Float64` numbers, or strings.
+//! String literals are enclosed in single quotes and may contain
+//! the escape sequences produced by [str::escape_default].
+//!
+//! Parentheses and negations can be nested at most 64 levels deep.
+//!
+//! [Display]: std::fmt::Display
+
+use std::str::FromStr;
+
+use anyhow::anyhow;
+
+use super::*;
+use crate::array::Schema;
+use crate::datatype::Datatype;
+use crate::physical_type_go;
+
+/// An error in the text of a query condition.
+#[derive(Clone, Debug, Eq, PartialEq, thiserror::Error)]
+pub enum ParseError {
+    #[error("Unexpected end of query condition")]
+    UnexpectedEnd,
+    #[error("Unexpected '{1}' at position {0}")]
+    UnexpectedToken(usize, String),
+    #[error("Unterminated string literal at position {0}")]
+    UnterminatedString(usize),
+    #[error("Invalid escape sequence at position {0}")]
+    InvalidEscape(usize),
+    #[error("Query condition is nested too deeply at position {0}")]
+    TooDeeplyNested(usize),
+    #[error("Invalid literal '{literal}' for field '{field}' of type {datatype} at position {position}")]
+    InvalidLiteral {
+        position: usize,
+        literal: String,
+        field: String,
+        datatype: Datatype,
+    },
+}
+
+impl From<ParseError> for Error {
+    fn from(value: ParseError) -> Self {
+        Error::InvalidArgument(anyhow!(value))
+    }
+}
+
+impl QueryConditionExpr {
+    /// Parses a query condition from `text`, such as the text
+    /// produced by the `Display` implementation of `QueryConditionExpr`.
+    ///
+    /// The type of each literal is the datatype of its field in `schema`.
+    pub fn parse(text: &str, schema: &Schema) -> TileDBResult<Self> {
+        Self::parse_with(text, |field| schema.field(field)?.datatype())
+    }
+
+    /// Parses a query condition from `text`, using `datatype` to
+    /// look up the type of the literals for each field.
+    pub fn parse_with<F>(text: &str, datatype: F) -> TileDBResult<Self>
+    where
+        F: Fn(&str) -> TileDBResult<Datatype>,
+    {
+        let mut parser = Parser {
+            tokens: tokenize(text)?,
+            cursor: 0,
+            depth: 0,
+            datatype,
+        };
+        let expr = parser.expr()?;
+        if let Some((position, token)) = parser.tokens.get(parser.cursor) {
+            return Err(ParseError::UnexpectedToken(
+                *position,
+                token.to_string(),
+            )
+            .into());
+        }
+        Ok(expr)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Token {
+    /// A field name, keyword, or non-finite floating-point value
+    Word(String),
+    Number(String),
+    String(String),
+    Op(EqualityOp),
+    Arithmetic(ArithmeticOp),
+    LeftParen,
+    RightParen,
+    Comma,
+}
+
+impl Token {
+    fn is_keyword(&self, keyword: &str) -> bool {
+        matches!(self, Self::Word(w) if w.eq_ignore_ascii_case(keyword))
+    }
+}
+
+impl Display for Token {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        match self {
+            Self::Word(w) => write!(f, "{}", w),
+            Self::Number(n) => write!(f, "{}", n),
+            Self::String(s) => write!(f, "'{}'", escape_string_literal(s)),
+            Self::Op(op) => write!(f, "{}", op),
+            Self::Arithmetic(op) => write!(f, "{}", op),
+            Self::LeftParen => write!(f, "("),
+            Self::RightParen => write!(f, ")"),
+            Self::Comma => write!(f, ","),
+        }
+    }
+}
+
+fn tokenize(text: &str) -> Result<Vec<(usize, Token)>, ParseError> {
+    let mut tokens = vec![];
+    let mut chars = text.char_indices().peekable();
+
+    while let Some((position, c)) = chars.next() {
+        let token = match c {
+            c if c.is_whitespace() => continue,
+            '(' => Token::LeftParen,
+            ')' => Token::RightParen,
+            ',' => Token::Comma,
+            '*' => Token::Arithmetic(ArithmeticOp::Multiply),
+            '/' => Token::Arithmetic(ArithmeticOp::Divide),
+            '+' | '-' if follows_operand(&tokens) => {
+                Token::Arithmetic(if c == '+' {
+                    ArithmeticOp::Add
+                } else {
+                    ArithmeticOp::Subtract
+                })
+            }
+            '=' => Token::Op(EqualityOp::Equal),
+            '<' => match chars.next_if(|(_, c)| *c == '=' || *c == '>') {
+                Some((_, '=')) => Token::Op(EqualityOp::LessEqual),
+                Some(_) => Token::Op(EqualityOp::NotEqual),
+                None => Token::Op(EqualityOp::Less),
+            },
+            '>' => match chars.next_if(|(_, c)| *c == '=') {
+                Some(_) => Token::Op(EqualityOp::GreaterEqual),
+                None => Token::Op(EqualityOp::Greater),
+            },
+            '!' => match chars.next_if(|(_, c)| *c == '=') {
+                Some(_) => Token::Op(EqualityOp::NotEqual),
+                None => {
+                    return Err(ParseError::UnexpectedToken(
+                        position,
+                        c.to_string(),
+                    ))
+                }
+            },
+            '\'' => {
+                let mut s = String::new();
+                loop {
+                    match chars.next() {
+                        None => {
+                            return Err(ParseError::UnterminatedString(
+                                position,
+                            ))
+                        }
+                        Some((_, '\'')) => break,
+                        Some((escape, '\\')) => {
+                            s.push(unescape(escape, &mut chars)?)
+                        }
+                        Some((_, c)) => s.push(c),
+                    }
+                }
+                Token::String(s)
+            }
+            c if c.is_ascii_digit() || matches!(c, '-' | '+' | '.') => {
+                let mut n = String::from(c);
+                while let Some((_, c)) = chars.next_if(|(_, c)| {
+                    c.is_ascii_alphanumeric()
+                        || *c == '.'
+                        || (matches!(*c, '-' | '+') && n.ends_with(['e', 'E']))
+                }) {
+                    n.push(c);
+                }
+                Token::Number(n)
+            }
+            c if c.is_alphabetic() || c == '_' => {
+                let mut w = String::from(c);
+                while let Some((_, c)) =
+                    chars.next_if(|(_, c)| c.is_alphanumeric() || *c == '_')
+                {
+                    w.push(c);
+                }
+                Token::Word(w)
+            }
+            c => {
+                return Err(ParseError::UnexpectedToken(
+                    position,
+                    c.to_string(),
+                ))
+            }
+        };
+        tokens.push((position, token));
+    }
+
+    Ok(tokens)
+}
+
+/// Returns whether the next token follows an operand, in which case
+/// a sign is an arithmetic operation rather than part of a number.
+fn follows_operand(tokens: &[(usize, Token)]) -> bool {
+    match tokens.last() {
+        Some((_, Token::Number(_) | Token::String(_) | Token::RightParen)) => {
+            true
+        }
+        Some((_, token @ Token::Word(_))) => !["AND", "OR", "NOT", "IN", "IS"]
+            .iter()
+            .any(|keyword| token.is_keyword(keyword)),
+        _ => false,
+    }
+}
+
+/// Returns the character of an escape sequence produced by
+/// [char::escape_default], whose leading backslash is at `position`.
+fn unescape(
+    position: usize,
+    chars: &mut std::iter::Peekable<std::str::CharIndices>,
+) -> Result<char, ParseError> {
+    match chars.next() {
+        Some((_, 't')) => Ok('\t'),
+        Some((_, 'r')) => Ok('\r'),
+        Some((_, 'n')) => Ok('\n'),
+        Some((_, c @ ('\'' | '"' | '\\'))) => Ok(c),
+        Some((_, 'u')) => {
+            if chars.next().map(|(_, c)| c) != Some('{') {
+                return Err(ParseError::InvalidEscape(position));
+            }
+            let mut hex = String::new();
+            loop {
+                match chars.next() {
+                    Some((_, '}')) => break,
+                    Some((_, c)) if c.is_ascii_hexdigit() && hex.len() < 6 => {
+                        hex.push(c)
+                    }
+                    _ => return Err(ParseError::InvalidEscape(position)),
+                }
+            }
+            u32::from_str_radix(&hex, 16)
+                .ok()
+                .and_then(char::from_u32)
+                .ok_or(ParseError::InvalidEscape(position))
+        }
+        _ => Err(ParseError::InvalidEscape(position)),
+    }
+}
+
+/// The deepest nesting of parentheses and negations which the parser
+/// accepts. Each level is parsed by a recursive call, so this prevents
+/// deeply nested text from overflowing the stack.
+const MAX_DEPTH: usize = 64;
+
+struct Parser<F> {
+    tokens: Vec<(usize, Token)>,
+    cursor: usize,
+    /// The number of enclosing parentheses and negations
+    depth: usize,
+    datatype: F,
+}
+
+impl<F> Parser<F>
+where
+    F: Fn(&str) -> TileDBResult<Datatype>,
+{
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.cursor).map(|(_, token)| token)
+    }
+
+    fn next(&mut self) -> Result<(usize, Token), ParseError> {
+        let token = self
+            .tokens
+            .get(self.cursor)
+            .cloned()
+            .ok_or(ParseError::UnexpectedEnd)?;
+        self.cursor += 1;
+        Ok(token)
+    }
+
+    fn next_if_keyword(&mut self, keyword: &str) -> bool {
+        if self.peek().is_some_and(|t| t.is_keyword(keyword)) {
+            self.cursor += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, expect: Token) -> Result<(), ParseError> {
+        let (position, token) = self.next()?;
+        if token == expect {
+            Ok(())
+        } else {
+            Err(ParseError::UnexpectedToken(position, token.to_string()))
+        }
+    }
+
+    /// Runs `parse` one level of nesting deeper than the current level.
+    /// `position` is the position of the token which opens the level.
+    fn nested<T>(
+        &mut self,
+        position: usize,
+        parse: impl FnOnce(&mut Self) -> TileDBResult<T>,
+    ) -> TileDBResult<T> {
+        if self.depth >= MAX_DEPTH {
+            return Err(ParseError::TooDeeplyNested(position).into());
+        }
+        self.depth += 1;
+        let result = parse(self);
+        self.depth -= 1;
+        result
+    }
+
+    fn expect_keyword(&mut self, keyword: &str) -> Result<(), ParseError> {
+        let (position, token) = self.next()?;
+        if token.is_keyword(keyword) {
+            Ok(())
+        } else {
+            Err(ParseError::UnexpectedToken(position, token.to_string()))
+        }
+    }
+
+    fn expr(&mut self) -> TileDBResult<QueryConditionExpr> {
+        let mut expr = self.term()?;
+        while self.next_if_keyword("OR") {
+            expr = expr | self.term()?;
+        }
+        Ok(expr)
+    }
+
+    fn term(&mut self) -> TileDBResult<QueryConditionExpr> {
+        let mut expr = self.factor()?;
+        while self.next_if_keyword("AND") {
+            expr = expr & self.factor()?;
+        }
+        Ok(expr)
+    }
+
+    fn factor(&mut self) -> TileDBResult<QueryConditionExpr> {
+        /*
+         * A field may be named "NOT", so this is a negation only
+         * if it is not followed by an operator.
+         */
+        let is_negation = self.peek().is_some_and(|t| t.is_keyword("NOT"))
+            && match self.tokens.get(self.cursor + 1) {
+                Some((_, Token::LeftParen)) => true,
+                Some((_, t @ Token::Word(_))) => {
+                    !t.is_keyword("IN") && !t.is_keyword("IS")
+                }
+                _ => false,
+            };
+        if is_negation {
+            let position = self.tokens[self.cursor].0;
+            self.cursor += 1;
+            return Ok(!self.nested(position, Self::factor)?);
+        }
+
+        if self.peek() == Some(&Token::LeftParen) {
+            /*
+             * The parentheses enclose either a condition or the operand
+             * of a comparison, as in `(a + b) < c`. If neither parses,
+             * report the error which occurs further into the text.
+             */
+            let start = self.cursor;
+            let position = self.tokens[start].0;
+            let expr_error = match self.nested(position, Self::parenthesized) {
+                Ok(expr) => return Ok(expr),
+                Err(e) => e,
+            };
+            let expr_end = self.cursor;
+            self.cursor = start;
+            return match self.predicate() {
+                Ok(predicate) => Ok(QueryConditionExpr::Cond(predicate)),
+                Err(_) if self.cursor <= expr_end => Err(expr_error),
+                Err(e) => Err(e),
+            };
+        }
+
+        Ok(QueryConditionExpr::Cond(self.predicate()?))
+    }
+
+    fn parenthesized(&mut self) -> TileDBResult<QueryConditionExpr> {
+        self.expect(Token::LeftParen)?;
+        let expr = self.expr()?;
+        self.expect(Token::RightParen)?;
+        Ok(expr)
+    }
+
+    fn predicate(&mut self) -> TileDBResult<Predicate> {
+        let field = match (self.peek(), self.tokens.get(self.cursor + 1)) {
+            (Some(Token::Word(field)), Some((_, t)))
+                if t.is_keyword("IS")
+                    || t.is_keyword("IN")
+                    || t.is_keyword("NOT") =>
+            {
+                field.clone()
+            }
+            _ => return self.comparison(),
+        };
+        self.cursor += 1;
+
+        let (position, token) = self.next()?;
+        match token {
+            Token::Word(ref w) if w.eq_ignore_ascii_case("IS") => {
+                let op = if self.next_if_keyword("NOT") {
+                    NullnessOp::NotNull
+                } else {
+                    NullnessOp::IsNull
+                };
+                self.expect_keyword("NULL")?;
+                Ok(Predicate::Nullness(NullnessPredicate { field, op }))
+            }
+            Token::Word(ref w)
+                if w.eq_ignore_ascii_case("IN")
+                    || w.eq_ignore_ascii_case("NOT") =>
+            {
+                let op = if w.eq_ignore_ascii_case("NOT") {
+                    self.expect_keyword("IN")?;
+                    SetMembershipOp::NotIn
+                } else {
+                    SetMembershipOp::In
+                };
+                let datatype = (self.datatype)(&field)?;
+                let members = self.set_members(&field, datatype)?;
+                Ok(Predicate::SetMembership(SetMembershipPredicate {
+                    field,
+                    op,
+                    members,
+                    use_enumeration: false,
+                }))
+            }
+            token => {
+                Err(ParseError::UnexpectedToken(position, token.to_string())
+                    .into())
+            }
+        }
+    }
+
+    fn comparison(&mut self) -> TileDBResult<Predicate> {
+        let lhs = self.operand()?;
+        let op = match self.next()? {
+            (_, Token::Op(op)) => op,
+            (position, token) => {
+                return Err(ParseError::UnexpectedToken(
+                    position,
+                    token.to_string(),
+                )
+                .into())
+            }
+        };
+        let rhs = self.operand()?;
+
+        /*
+         * A field compared with a literal is an equality predicate,
+         * whose literal has the type of the field. A word such as `inf`
+         * is a literal if it is a value of the field's type.
+         */
+        let (field, op, position, token) = match (lhs, rhs) {
+            (Untyped::Field(_, field), Untyped::Literal(position, token)) => {
+                (field, op, position, token)
+            }
+            (Untyped::Literal(position, token), Untyped::Field(_, field)) => {
+                (field, op.reverse(), position, token)
+            }
+            (Untyped::Field(_, field), Untyped::Field(position, ref word))
+                if self.is_literal(&field, word) =>
+            {
+                (field, op, position, Token::Word(word.clone()))
+            }
+            (Untyped::Field(position, ref word), Untyped::Field(_, field))
+                if self.is_literal(&field, word) =>
+            {
+                (field, op.reverse(), position, Token::Word(word.clone()))
+            }
+            (lhs, rhs) => {
+                return Ok(Predicate::Comparison(ComparisonPredicate {
+                    lhs: lhs.typed()?,
+                    op,
+                    rhs: rhs.typed()?,
+                }))
+            }
+        };
+        let datatype = (self.datatype)(&field)?;
+        let value = literal(&field, datatype, position, token)?;
+        Ok(Predicate::Equality(EqualityPredicate {
+            field,
+            op,
+            value,
+            use_enumeration: false,
+        }))
+    }
+
+    /// Returns whether `word` is a literal of the type of `field`.
+    /// This is `false` if `field` is not actually a field, since then
+    /// `word` is the field instead.
+    fn is_literal(&self, field: &str, word: &str) -> bool {
+        let Ok(datatype) = (self.datatype)(field) else {
+            return false;
+        };
+        !datatype.is_string_type()
+            && physical_type_go!(datatype, DT, word.parse::<DT>().is_ok())
+    }
+
+    fn operand(&mut self) -> TileDBResult<Untyped> {
+        let mut operand = self.product()?;
+        while let Some(Token::Arithmetic(
+            op @ (ArithmeticOp::Add | ArithmeticOp::Subtract),
+        )) = self.peek().cloned()
+        {
+            self.cursor += 1;
+            operand = Untyped::Arithmetic(
+                Box::new(operand),
+                op,
+                Box::new(self.product()?),
+            );
+        }
+        Ok(operand)
+    }
+
+    fn product(&mut self) -> TileDBResult<Untyped> {
+        let mut operand = self.atom()?;
+        while let Some(Token::Arithmetic(
+            op @ (ArithmeticOp::Multiply | ArithmeticOp::Divide),
+        )) = self.peek().cloned()
+        {
+            self.cursor += 1;
+            operand = Untyped::Arithmetic(
+                Box::new(operand),
+                op,
+                Box::new(self.atom()?),
+            );
+        }
+        Ok(operand)
+    }
+
+    fn atom(&mut self) -> TileDBResult<Untyped> {
+        match self.next()? {
+            (position, Token::LeftParen) => self.nested(position, |p| {
+                let operand = p.operand()?;
+                p.expect(Token::RightParen)?;
+                Ok(operand)
+            }),
+            (position, Token::Word(field)) => {
+                Ok(Untyped::Field(position, field))
+            }
+            (position, token @ (Token::Number(_) | Token::String(_))) => {
+                Ok(Untyped::Literal(position, token))
+            }
+            (position, token) => {
+                Err(ParseError::UnexpectedToken(position, token.to_string())
+                    .into())
+            }
+        }
+    }
+
+    fn set_members(
+        &mut self,
+        field: &str,
+        datatype: Datatype,
+    ) -> TileDBResult<SetMembers> {
+        self.expect(Token::LeftParen)?;
+
+        let mut members = vec![];
+        if self.peek() == Some(&Token::RightParen) {
+            self.cursor += 1;
+        } else {
+            loop {
+                members.push(self.next()?);
+                match self.next()? {
+                    (_, Token::Comma) => continue,
+                    (_, Token::RightParen) => break,
+                    (position, token) => {
+                        return Err(ParseError::UnexpectedToken(
+                            position,
+                            token.to_string(),
+                        )
+                        .into())
+                    }
+                }
+            }
+        }
+
+        if datatype.is_string_type() {
+            Ok(SetMembers::String(
+                members
+                    .into_iter()
+                    .map(|(position, token)| match token {
+                        Token::String(s) => Ok(s),
+                        token => Err(invalid_literal(
+                            field, datatype, position, token,
+                        )),
+                    })
+                    .collect::<Result<Vec<String>, ParseError>>()?,
+            ))
+        } else {
+            physical_type_go!(datatype, DT, {
+                let members = members
+                    .into_iter()
+                    .map(|(position, token)| {
+                        number::<DT>(field, datatype, position, token)
+                    })
+                    .collect::<Result<Vec<DT>, ParseError>>()?;
+                Ok(SetMembers::from(members.as_slice()))
+            })
+        }
+    }
+}
+
+/// An operand whose literals are not yet typed.
+enum Untyped {
+    Field(usize, String),
+    Literal(usize, Token),
+    Arithmetic(Box<Untyped>, ArithmeticOp, Box<Untyped>),
+}
+
+impl Untyped {
+    /// Returns the operand, typing each literal by its form.
+    fn typed(self) -> Result<Operand, ParseError> {
+        match self {
+            Self::Field(_, field) => Ok(Operand::Field(field)),
+            Self::Literal(position, token) => match token {
+                Token::String(s) => Ok(Operand::Literal(Literal::String(s))),
+                Token::Number(ref n) => {
+                    untyped_number(n).map(Operand::Literal).ok_or_else(|| {
+                        ParseError::UnexpectedToken(position, token.to_string())
+                    })
+                }
+                token => Err(ParseError::UnexpectedToken(
+                    position,
+                    token.to_string(),
+                )),
+            },
+            Self::Arithmetic(lhs, op, rhs) => Ok(Operand::Arithmetic {
+                lhs: Box::new(lhs.typed()?),
+                rhs: Box::new(rhs.typed()?),
+                op,
+            }),
+        }
+    }
+}
+
+/// Returns the literal represented by the text of a number which is not
+/// compared with a field. The type of the literal is given by its suffix,
+/// such as `5u8` or `1.5f32`, or else is the first of `Int64`, `UInt64`
+/// or `Float64` which can represent the number.
+fn untyped_number(n: &str) -> Option<Literal> {
+    macro_rules! suffixed {
+        ($($suffix:literal => $ty:ty),+) => {
+            $(
+                if let Some(n) = n.strip_suffix($suffix) {
+                    return n.parse::<$ty>().ok().map(Literal::from);
+                }
+            )+
+        };
+    }
+    suffixed!(
+        "u8" => u8, "u16" => u16, "u32" => u32, "u64" => u64,
+        "i8" => i8, "i16" => i16, "i32" => i32, "i64" => i64,
+        "f32" => f32, "f64" => f64
+    );
+
+    n.parse::<i64>()
+        .map(Literal::from)
+        .or_else(|_| n.parse::<u64>().map(Literal::from))
+        .or_else(|_| n.parse::<f64>().map(Literal::from))
+        .ok()
+}
+
+/// Returns the literal of type `datatype` represented by `token`.
+fn literal(
+    field: &str,
+    datatype: Datatype,
+    position: usize,
+    token: Token,
+) -> Result<Literal, ParseError> {
+    if datatype.is_string_type() {
+        match token {
+            Token::String(s) => Ok(Literal::String(s)),
+            token => Err(invalid_literal(field, datatype, position, token)),
+        }
+    } else {
+        physical_type_go!(datatype, DT, {
+            number::<DT>(field, datatype, position, token).map(Literal::from)
+        })
+    }
+}
+
+fn number<T>(
+    field: &str,
+    datatype: Datatype,
+    position: usize,
+    token: Token,
+) -> Result<T, ParseError>
+where
+    T: FromStr,
+{
+    match token {
+        Token::Number(ref n) | Token::Word(ref n) => {
+            n.parse::<T>().map_err(|_| {
+                invalid_literal(field, datatype, position, token.clone())
+            })
+        }
+        token => Err(invalid_literal(field, datatype, position, token)),
+    }
+}
+
+fn invalid_literal(
+    field: &str,
+    datatype: Datatype,
+    position: usize,
+    token: Token,
+) -> ParseError {
+    ParseError::InvalidLiteral {
+        position,
+        literal: token.to_string(),
+        field: field.to_owned(),
+        datatype,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use proptest::prelude::*;
+
+    use super::QueryConditionExpr as QC;
+    use super::*;
+    use crate::query::condition::strategy::{
+        prop_condition_fields, prop_query_condition,
+    };
+
+    fn datatype(field: &str) -> TileDBResult<Datatype> {
+        Ok(match field {
+            "s" | "NOT" => Datatype::StringUtf8,
+            "f" => Datatype::Float64,
+            "u" => Datatype::UInt8,
+            _ => Datatype::Int32,
+        })
+    }
+
+    fn parse(text: &str) -> TileDBResult<QC> {
+        QC::parse_with(text, datatype)
+    }
+
+    #[test]
+    fn parse_predicates() -> TileDBResult<()> {
+        assert_eq!(QC::field("x").lt(5), parse("x < 5")?);
+        assert_eq!(QC::field("x").le(-5), parse("x<=-5")?);
+        assert_eq!(QC::field("x").eq(5), parse("x = 5")?);
+        assert_eq!(QC::field("x").ne(5), parse("x <> 5")?);
+        assert_eq!(QC::field("x").ne(5), parse("x != 5")?);
+        assert_eq!(QC::field("x").ge(5), parse("x >= 5")?);
+        assert_eq!(QC::field("x").gt(5), parse("x>5")?);
+        assert_eq!(QC::field("u").eq(5u8), parse("u = 5")?);
+        assert_eq!(QC::field("f").lt(1.5e-3f64), parse("f < 1.5e-3")?);
+        assert_eq!(QC::field("f").lt(f64::NEG_INFINITY), parse("f < -inf")?);
+        assert_eq!(QC::field("s").eq("it's"), parse(r"s = 'it\'s'")?);
+        assert_eq!(QC::field("s").eq("\u{e9}\n"), parse(r"s = '\u{e9}\n'")?);
+
+        assert_eq!(
+            QC::field("x").is_in(&[1, 2, 3][..]),
+            parse("x IN (1, 2, 3)")?
+        );
+        assert_eq!(
+            QC::field("s").not_in(&["a", "b"][..]),
+            parse("s not in ('a','b')")?
+        );
+        assert_eq!(
+            QC::field("x").is_in(&Vec::<i32>::new()[..]),
+            parse("x IN ()")?
+        );
+
+        assert_eq!(QC::field("x").is_null(), parse("x IS NULL")?);
+        assert_eq!(QC::field("x").not_null(), parse("x is not null")?);
+        assert_eq!(QC::field("NOT").eq("NOT"), parse("NOT = 'NOT'")?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn parse_precedence() -> TileDBResult<()> {
+        let a = || QC::field("a").lt(1);
+        let b = || QC::field("b").lt(2);
+        let c = || QC::field("c").lt(3);
+
+        assert_eq!(a() | (b() & c()), parse("a < 1 OR b < 2 AND c < 3")?);
+        assert_eq!((a() & b()) | c(), parse("a < 1 AND b < 2 OR c < 3")?);
+        assert_eq!(a() & (b() | c()), parse("a < 1 AND (b < 2 OR c < 3)")?);
+        assert_eq!((a() & b()) & c(), parse("a < 1 AND b < 2 AND c < 3")?);
+        assert_eq!(!a() & b(), parse("NOT a < 1 AND b < 2")?);
+        assert_eq!(!(a() & b()), parse("NOT (a < 1 AND b < 2)")?);
+        assert_eq!(!!a(), parse("not not ((a < 1))")?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn parse_comparisons() -> TileDBResult<()> {
+        let field = Operand::field;
+
+        assert_eq!(field("x").lt(field("y")), parse("x < y")?);
+        assert_eq!(QC::field("x").gt(5), parse("5 < x")?);
+        assert_eq!(QC::field("f").lt(f64::INFINITY), parse("f < inf")?);
+        assert_eq!(QC::field("f").gt(f64::INFINITY), parse("inf < f")?);
+        assert_eq!((field("x") + 1i64).lt(field("y")), parse("x+1 < y")?);
+        assert_eq!(
+            (field("x") - -1i64).ge(field("y") * 2.5),
+            parse("x - -1 >= y * 2.5")?
+        );
+        assert_eq!(
+            (field("x") + field("y") * 2i64).eq(0i64),
+            parse("x + y * 2 = 0")?
+        );
+        assert_eq!(
+            ((field("x") + field("y")) * 2i64).eq(0i64),
+            parse("(x + y) * 2 = 0")?
+        );
+        assert_eq!(
+            (field("x") - field("y") - field("z")).eq(0i64),
+            parse("x - y - z = 0")?
+        );
+        assert_eq!(
+            field("s").ne(field("x") / field("y")) & QC::field("x").lt(1),
+            parse("(s <> x / y) AND ((x) < 1)")?
+        );
+        assert_eq!(
+            !((field("x") + field("y")).lt(1i64)),
+            parse("NOT (x + y) < 1")?
+        );
+        assert_eq!(
+            (field("x") * 2u8).lt(field("y") - 1.5f32),
+            parse("x * 2u8 < y - 1.5f32")?
+        );
+        assert_eq!(
+            (field("x") * f64::INFINITY).ge(f32::NEG_INFINITY),
+            parse("x * +inf >= -inff32")?
+        );
+        assert_eq!(
+            (field("x") + u64::MAX).lt(field("y")),
+            parse("x + 18446744073709551615 < y")?
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn parse_errors() {
+        let parse_error = |text| match parse(text) {
+            Err(Error::InvalidArgument(e)) => e.downcast::<ParseError>().ok(),
+            _ => None,
+        };
+
+        assert_eq!(Some(ParseError::UnexpectedEnd), parse_error("x <"));
+        assert_eq!(Some(ParseError::UnexpectedEnd), parse_error("(x < 5"));
+        assert_eq!(
+            Some(ParseError::UnexpectedToken(6, ")".to_owned())),
+            parse_error("x < 5 )")
+        );
+        assert_eq!(
+            Some(ParseError::UnexpectedToken(2, "LIKE".to_owned())),
+            parse_error("x LIKE 'foo'")
+        );
+        assert_eq!(
+            Some(ParseError::UnterminatedString(4)),
+            parse_error("s = 'foo")
+        );
+        assert_eq!(
+            Some(ParseError::InvalidEscape(7)),
+            parse_error(r"s = 'fo\o'")
+        );
+        assert!(matches!(
+            parse_error("u = -1"),
+            Some(ParseError::InvalidLiteral { position: 4, .. })
+        ));
+        assert!(matches!(
+            parse_error("s = 1"),
+            Some(ParseError::InvalidLiteral { position: 4, .. })
+        ));
+        assert!(matches!(
+            parse_error("x IN (1, 'two')"),
+            Some(ParseError::InvalidLiteral { position: 9, .. })
+        ));
+        assert_eq!(
+            Some(ParseError::UnexpectedToken(8, ")".to_owned())),
+            parse_error("(x + 1) ) < 2")
+        );
+        assert_eq!(
+            Some(ParseError::UnexpectedToken(4, "*".to_owned())),
+            parse_error("x < * 2")
+        );
+    }
+
+    #[test]
+    fn parse_nesting() -> TileDBResult<()> {
+        let parse_error = |text: &str| match parse(text) {
+            Err(Error::InvalidArgument(e)) => e.downcast::<ParseError>().ok(),
+            _ => None,
+        };
+
+        let nested = |open: &str, inner: &str, close: &str, depth: usize| {
+            format!("{}{}{}", open.repeat(depth), inner, close.repeat(depth))
+        };
+
+        // nesting up to the limit is accepted
+        let a = QC::field("a").lt(1);
+        assert_eq!(a.clone(), parse(&nested("(", "a < 1", ")", MAX_DEPTH))?);
+        assert_eq!(
+            (Operand::field("a") + 1i64).lt(2i64),
+            parse(&nested("(", "a + 1", ")", MAX_DEPTH) + " < 2")?
+        );
+        let mut negated = a;
+        for _ in 0..MAX_DEPTH {
+            negated = !negated;
+        }
+        assert_eq!(negated, parse(&nested("NOT ", "a < 1", "", MAX_DEPTH))?);
+
+        // deeper nesting is an error rather than a stack overflow
+        assert_eq!(
+            Some(ParseError::TooDeeplyNested(MAX_DEPTH)),
+            parse_error(&nested("(", "a < 1", ")", 100_000))
+        );
+        assert_eq!(
+            Some(ParseError::TooDeeplyNested(4 * MAX_DEPTH)),
+            parse_error(&nested("NOT ", "a < 1", "", 100_000))
+        );
+        assert_eq!(
+            Some(ParseError::TooDeeplyNested(MAX_DEPTH + 4)),
+            parse_error(&format!("a < {}", nested("(", "1", ")", 100_000)))
+        );
+
+        Ok(())
+    }
+
+    proptest! {
+        #[test]
+        fn display_parse_roundtrip(
+            (fields, qc) in prop_condition_fields().prop_flat_map(|fields| {
+                (Just(fields.clone()), prop_query_condition(fields))
+            })
+        ) {
+            let fields = fields.into_iter().collect::<HashMap<_, _>>();
+            let parsed = QC::parse_with(&qc.to_string(), |field| {
+                Ok(fields[field])
+            })
+            .expect("Error parsing query condition");
+            assert_eq!(qc, parsed);
+        }
+    }
+}
@@ -0,0 +1,907 @@
+//! Simplifies query conditions.
+//!
+//! A condition is simplified by first converting it into a normal form
+//! where negations are applied to the predicates, and chains of the same
+//! combination operation are flattened. Then each chain is simplified
+//! by removing duplicate predicates, merging range predicates on the same
+//! field, and folding predicates which are always or never satisfied.
+//!
+//! Each step preserves the result of evaluating the condition, including
+//! for null and NaN values (see [QueryConditionExpr::evaluate]).
+
+use std::cmp::Ordering;
+use std::collections::HashMap;
+
+use super::*;
+use crate::array::Schema;
+use crate::physical_type_go;
+
+/// The result of simplifying a [QueryConditionExpr].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum SimplifiedCondition {
+    /// The condition is satisfied by every cell.
+    AlwaysTrue,
+    /// The condition is not satisfied by any cell.
+    AlwaysFalse,
+    /// The condition may be satisfied by some cells but not others.
+    Expr(QueryConditionExpr),
+}
+
+impl QueryConditionExpr {
+    /// Returns an equivalent condition with negations pushed down
+    /// into the predicates, flattened `AND` and `OR` chains,
+    /// and merged range predicates.
+    pub fn simplify(&self) -> SimplifiedCondition {
+        self.simplify_impl(&HashMap::new())
+    }
+
+    /// Returns an equivalent condition for cells of an array with `schema`.
+    ///
+    /// In addition to the simplifications made by [Self::simplify],
+    /// predicates on dimensions are folded if every cell in the
+    /// dimension's domain does or does not satisfy them.
+    pub fn simplify_with_schema(
+        &self,
+        schema: &Schema,
+    ) -> TileDBResult<SimplifiedCondition> {
+        let domain = schema.domain()?;
+
+        let mut domains = HashMap::new();
+        for d in 0..domain.num_dimensions()? {
+            let dimension = domain.dimension(d)?;
+            if dimension.is_var_sized()? {
+                continue;
+            }
+            let bounds = physical_type_go!(dimension.datatype()?, DT, {
+                dimension.domain::<DT>()?.map(|[lower, upper]| {
+                    [Literal::from(lower), Literal::from(upper)]
+                })
+            });
+            if let Some(bounds) = bounds {
+                domains.insert(dimension.name()?, bounds);
+            }
+        }
+
+        Ok(self.simplify_impl(&domains))
+    }
+
+    fn simplify_impl(
+        &self,
+        domains: &HashMap<String, [Literal; 2]>,
+    ) -> SimplifiedCondition {
+        let simplifier = Simplifier { domains };
+        match simplifier.term(Term::new(self, false)) {
+            Term::True => SimplifiedCondition::AlwaysTrue,
+            Term::False => SimplifiedCondition::AlwaysFalse,
+            term => SimplifiedCondition::Expr(term.into_expr()),
+        }
+    }
+}
+
+/// A query condition in normal form.
+#[derive(Clone, Debug, PartialEq)]
+enum Term {
+    True,
+    False,
+    Predicate(Predicate),
+    Comb(CombinationOp, Vec<Term>),
+}
+
+impl Term {
+    fn new(expr: &QueryConditionExpr, negate: bool) -> Self {
+        match expr {
+            QueryConditionExpr::Cond(predicate) => Term::Predicate(if negate {
+                predicate.negate()
+            } else {
+                predicate.clone()
+            }),
+            QueryConditionExpr::Comb { lhs, rhs, op } => Term::Comb(
+                if negate { op.negate() } else { *op },
+                vec![Self::new(lhs, negate), Self::new(rhs, negate)],
+            ),
+            QueryConditionExpr::Negate(expr) => Self::new(expr, !negate),
+        }
+    }
+
+    /// Returns the expression for a simplified term.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `self` is or contains `True` or `False`, which are always
+    /// folded away from a simplified term which is not itself a constant.
+    fn into_expr(self) -> QueryConditionExpr {
+        match self {
+            Term::Predicate(predicate) => QueryConditionExpr::Cond(predicate),
+            Term::Comb(op, terms) => terms
+                .into_iter()
+                .map(Self::into_expr)
+                .reduce(|lhs, rhs| QueryConditionExpr::Comb {
+                    lhs: Box::new(lhs),
+                    rhs: Box::new(rhs),
+                    op,
+                })
+                .unwrap(),
+            Term::True | Term::False => unreachable!(),
+        }
+    }
+}
+
+impl Predicate {
+    /// Returns the predicate which TileDB applies when this one is negated.
+    fn negate(&self) -> Self {
+        match self {
+            Self::Equality(e) => Self::Equality(EqualityPredicate {
+                field: e.field.clone(),
+                op: e.op.negate(),
+                value: e.value.clone(),
+                use_enumeration: e.use_enumeration,
+            }),
+            Self::SetMembership(s) => {
+                Self::SetMembership(SetMembershipPredicate {
+                    field: s.field.clone(),
+                    op: s.op.negate(),
+                    members: s.members.clone(),
+                    use_enumeration: s.use_enumeration,
+                })
+            }
+            Self::Nullness(n) => Self::Nullness(NullnessPredicate {
+                field: n.field.clone(),
+                op: n.op.negate(),
+            }),
+            Self::Comparison(c) => Self::Comparison(ComparisonPredicate {
+                lhs: c.lhs.clone(),
+                op: c.op.negate(),
+                rhs: c.rhs.clone(),
+            }),
+        }
+    }
+
+    /// Returns whether every value satisfies exactly one of this predicate
+    /// and its negation. This is not the case for ranges, which are not
+    /// satisfied by null or NaN.
+    fn is_complement_of_negation(&self) -> bool {
+        match self {
+            Self::Equality(e) => {
+                matches!(e.op, EqualityOp::Equal | EqualityOp::NotEqual)
+            }
+            Self::Comparison(c) => {
+                matches!(c.op, EqualityOp::Equal | EqualityOp::NotEqual)
+            }
+            Self::SetMembership(_) | Self::Nullness(_) => true,
+        }
+    }
+}
+
+struct Simplifier<'a> {
+    /// Domain of each fixed-size dimension
+    domains: &'a HashMap<String, [Literal; 2]>,
+}
+
+impl Simplifier<'_> {
+    fn term(&self, term: Term) -> Term {
+        match term {
+            Term::Predicate(predicate) => self.predicate(predicate),
+            Term::Comb(op, terms) => self.combination(
+                op,
+                terms.into_iter().map(|t| self.term(t)).collect(),
+            ),
+            constant => constant,
+        }
+    }
+
+    fn combination(&self, op: CombinationOp, terms: Vec<Term>) -> Term {
+        let (identity, absorbing) = match op {
+            CombinationOp::And => (Term::True, Term::False),
+            CombinationOp::Or => (Term::False, Term::True),
+        };
+
+        let mut flat = vec![];
+        for term in terms {
+            match term {
+                Term::Comb(child_op, terms) if child_op == op => {
+                    flat.extend(terms)
+                }
+                term if term == absorbing => return absorbing,
+                term if term == identity => continue,
+                term => flat.push(term),
+            }
+        }
+
+        let mut out: Vec<Term> = vec![];
+        for term in merge_ranges(op, flat) {
+            let term = match term {
+                Term::Predicate(predicate) => self.predicate(predicate),
+                term => term,
+            };
+            if term == absorbing {
+                return absorbing;
+            } else if term != identity && !out.contains(&term) {
+                out.push(term);
+            }
+        }
+
+        let has_complement = out.iter().any(|term| {
+            matches!(term, Term::Predicate(p)
+                if p.is_complement_of_negation()
+                    && out.contains(&Term::Predicate(p.negate())))
+        });
+        if has_complement {
+            return absorbing;
+        }
+
+        match out.len() {
+            0 => identity,
+            1 => out.pop().unwrap(),
+            _ => Term::Comb(op, out),
+        }
+    }
+
+    /// Folds a predicate which is always or never satisfied.
+    fn predicate(&self, predicate: Predicate) -> Term {
+        match predicate {
+            Predicate::Equality(ref e) if e.use_enumeration => {}
+            Predicate::Equality(ref e) => {
+                if is_nan(&e.value) {
+                    // NaN is not ordered or equal to anything
+                    return constant(e.op == EqualityOp::NotEqual);
+                }
+                match e.op {
+                    EqualityOp::Less if is_min(&e.value) => return Term::False,
+                    EqualityOp::Greater if is_max(&e.value) => {
+                        return Term::False
+                    }
+                    _ => {}
+                }
+                if let Some(t) = self
+                    .domains
+                    .get(&e.field)
+                    .and_then(|[lower, upper]| fold_domain(e, lower, upper))
+                {
+                    return t;
+                }
+            }
+            Predicate::SetMembership(ref s) => {
+                let members = s.members.literals();
+                let is_outside_domain = match self.domains.get(&s.field) {
+                    Some([lower, upper]) => members.iter().all(|m| {
+                        literal_cmp(m, lower) == Some(Ordering::Less)
+                            || literal_cmp(m, upper) == Some(Ordering::Greater)
+                    }),
+                    None => members.is_empty(),
+                };
+                if is_outside_domain {
+                    return constant(s.op == SetMembershipOp::NotIn);
+                }
+            }
+            Predicate::Nullness(ref n) => {
+                if self.domains.contains_key(&n.field) {
+                    // dimensions are never null
+                    return constant(n.op == NullnessOp::NotNull);
+                }
+            }
+            Predicate::Comparison(_) => {}
+        }
+        Term::Predicate(predicate)
+    }
+}
+
+fn constant(value: bool) -> Term {
+    if value {
+        Term::True
+    } else {
+        Term::False
+    }
+}
+
+/// Returns a constant if every value in the domain `[lower, upper]`
+/// does or does not satisfy the predicate.
+fn fold_domain(
+    e: &EqualityPredicate,
+    lower: &Literal,
+    upper: &Literal,
+) -> Option<Term> {
+    use Ordering::{Equal, Greater, Less};
+
+    let lower = literal_cmp(&e.value, lower)?;
+    let upper = literal_cmp(&e.value, upper)?;
+
+    let (always, never) = match e.op {
+        EqualityOp::Less => (upper == Greater, lower != Greater),
+        EqualityOp::LessEqual => (upper != Less, lower == Less),
+        EqualityOp::Greater => (lower == Less, upper != Less),
+        EqualityOp::GreaterEqual => (lower != Greater, upper == Greater),
+        EqualityOp::Equal => (
+            lower == Equal && upper == Equal,
+            lower == Less || upper == Greater,
+        ),
+        EqualityOp::NotEqual => (
+            lower == Less || upper == Greater,
+            lower == Equal && upper == Equal,
+        ),
+    };
+    if always {
+        Some(Term::True)
+    } else if never {
+        Some(Term::False)
+    } else {
+        None
+    }
+}
+
+#[derive(Clone, Debug)]
+struct Bound {
+    value: Literal,
+    inclusive: bool,
+}
+
+/// The range predicates on a single field.
+///
+/// For `AND` these are the tightest bounds which all of the predicates
+/// satisfy. For `OR` these are the loosest bounds which any of the
+/// predicates satisfy.
+#[derive(Debug, Default)]
+struct Bounds {
+    lower: Option<Bound>,
+    upper: Option<Bound>,
+    /// Whether the first predicate was an upper bound, to preserve
+    /// the order of the predicates.
+    upper_first: bool,
+}
+
+impl Bounds {
+    /// Adds a range predicate to these bounds.
+    /// Returns `false` if the predicate cannot be merged with the bounds.
+    fn add(
+        &mut self,
+        comb: CombinationOp,
+        op: EqualityOp,
+        value: &Literal,
+    ) -> bool {
+        if self.lower.is_none() && self.upper.is_none() {
+            self.upper_first =
+                matches!(op, EqualityOp::Less | EqualityOp::LessEqual);
+        }
+        let (is_lower, inclusive) = match op {
+            EqualityOp::Less => (false, false),
+            EqualityOp::LessEqual => (false, true),
+            EqualityOp::GreaterEqual => (true, true),
+            EqualityOp::Greater => (true, false),
+            EqualityOp::Equal => {
+                let lower = self.replace(comb, true, value, true);
+                let upper = self.replace(comb, false, value, true);
+                return match (lower, upper) {
+                    (Some(lower), Some(upper)) => {
+                        if lower {
+                            self.lower = Some(Bound {
+                                value: value.clone(),
+                                inclusive: true,
+                            });
+                        }
+                        if upper {
+                            self.upper = Some(Bound {
+                                value: value.clone(),
+                                inclusive: true,
+                            });
+                        }
+                        true
+                    }
+                    _ => false,
+                };
+            }
+            EqualityOp::NotEqual => return false,
+        };
+        match self.replace(comb, is_lower, value, inclusive) {
+            Some(replace) => {
+                if replace {
+                    let bound = Some(Bound {
+                        value: value.clone(),
+                        inclusive,
+                    });
+                    if is_lower {
+                        self.lower = bound;
+                    } else {
+                        self.upper = bound;
+                    }
+                }
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Returns whether the new bound should replace the current one,
+    /// or `None` if they cannot be compared.
+    fn replace(
+        &self,
+        comb: CombinationOp,
+        is_lower: bool,
+        value: &Literal,
+        inclusive: bool,
+    ) -> Option<bool> {
+        let current = if is_lower { &self.lower } else { &self.upper };
+        let Some(current) = current else {
+            return Some(true);
+        };
+        // whether the new bound is tighter than the current bound
+        let tighter = match literal_cmp(value, &current.value)? {
+            Ordering::Less => !is_lower,
+            Ordering::Greater => is_lower,
+            Ordering::Equal => current.inclusive && !inclusive,
+        };
+        let looser = match literal_cmp(value, &current.value)? {
+            Ordering::Less => is_lower,
+            Ordering::Greater => !is_lower,
+            Ordering::Equal => !current.inclusive && inclusive,
+        };
+        Some(match comb {
+            CombinationOp::And => tighter,
+            CombinationOp::Or => looser,
+        })
+    }
+
+    fn into_terms(self, comb: CombinationOp, field: String) -> Vec<Term> {
+        let predicate = |op, value| {
+            Term::Predicate(Predicate::Equality(EqualityPredicate {
+                field: field.clone(),
+                op,
+                value,
+                use_enumeration: false,
+            }))
+        };
+
+        if let (CombinationOp::And, Some(lower), Some(upper)) =
+            (comb, &self.lower, &self.upper)
+        {
+            match literal_cmp(&lower.value, &upper.value) {
+                Some(Ordering::Greater) => return vec![Term::False],
+                Some(Ordering::Equal) => {
+                    return if lower.inclusive && upper.inclusive {
+                        vec![predicate(EqualityOp::Equal, lower.value.clone())]
+                    } else {
+                        vec![Term::False]
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        let upper_first = self.upper_first;
+        let lower = self.lower.map(|b| {
+            let op = if b.inclusive {
+                EqualityOp::GreaterEqual
+            } else {
+                EqualityOp::Greater
+            };
+            predicate(op, b.value)
+        });
+        let upper = self.upper.map(|b| {
+            let op = if b.inclusive {
+                EqualityOp::LessEqual
+            } else {
+                EqualityOp::Less
+            };
+            predicate(op, b.value)
+        });
+        if upper_first {
+            upper.into_iter().chain(lower).collect()
+        } else {
+            lower.into_iter().chain(upper).collect()
+        }
+    }
+}
+
+/// Merges the range predicates on each field in a chain of `comb`.
+fn merge_ranges(comb: CombinationOp, terms: Vec<Term>) -> Vec<Term> {
+    enum Slot {
+        Term(Term),
+        Bounds(String),
+    }
+
+    let mut slots = vec![];
+    let mut bounds = HashMap::<String, Bounds>::new();
+    for term in terms {
+        if let Term::Predicate(Predicate::Equality(ref e)) = term {
+            // enumeration values are not necessarily ordered like their keys
+            let is_range = !e.use_enumeration
+                && match e.op {
+                    EqualityOp::Less
+                    | EqualityOp::LessEqual
+                    | EqualityOp::GreaterEqual
+                    | EqualityOp::Greater => true,
+                    EqualityOp::Equal => comb == CombinationOp::And,
+                    EqualityOp::NotEqual => false,
+                };
+            if is_range {
+                match bounds.get_mut(&e.field) {
+                    Some(b) => {
+                        if b.add(comb, e.op, &e.value) {
+                            continue;
+                        }
+                    }
+                    None => {
+                        let mut b = Bounds::default();
+                        b.add(comb, e.op, &e.value);
+                        bounds.insert(e.field.clone(), b);
+                        slots.push(Slot::Bounds(e.field.clone()));
+                        continue;
+                    }
+                }
+            }
+        }
+        slots.push(Slot::Term(term));
+    }
+
+    slots
+        .into_iter()
+        .flat_map(|slot| match slot {
+            Slot::Term(term) => vec![term],
+            Slot::Bounds(field) => {
+                let b = bounds.remove(&field).unwrap();
+                b.into_terms(comb, field)
+            }
+        })
+        .collect()
+}
+
+/// Compares two literals of the same type.
+fn literal_cmp(left: &Literal, right: &Literal) -> Option<Ordering> {
+    macro_rules! literal_cmp {
+        ($($V:ident),+) => {
+            match (left, right) {
+                $(
+                    (Literal::$V(left), Literal::$V(right)) => {
+                        left.partial_cmp(right)
+                    }
+                )+
+                (Literal::String(left), Literal::String(right)) => {
+                    Some(left.as_bytes().cmp(right.as_bytes()))
+                }
+                _ => None,
+            }
+        };
+    }
+    literal_cmp!(
+        UInt8, UInt16, UInt32, UInt64, Int8, Int16, Int32, Int64, Float32,
+        Float64
+    )
+}
+
+fn is_nan(literal: &Literal) -> bool {
+    match literal {
+        Literal::Float32(f) => f.is_nan(),
+        Literal::Float64(f) => f.is_nan(),
+        _ => false,
+    }
+}
+
+/// Returns whether no value is less than `literal`.
+fn is_min(literal: &Literal) -> bool {
+    macro_rules! is_min {
+        ($($V:ident : $T:ty),+) => {
+            match literal {
+                $(Literal::$V(value) => *value == <$T>::MIN,)+
+                Literal::Float32(value) => *value == f32::NEG_INFINITY,
+                Literal::Float64(value) => *value == f64::NEG_INFINITY,
+                Literal::String(value) => value.is_empty(),
+            }
+        };
+    }
+    is_min!(
+        UInt8: u8, UInt16: u16, UInt32: u32, UInt64: u64,
+        Int8: i8, Int16: i16, Int32: i32, Int64: i64
+    )
+}
+
+/// Returns whether no value is greater than `literal`.
+fn is_max(literal: &Literal) -> bool {
+    macro_rules! is_max {
+        ($($V:ident : $T:ty),+) => {
+            match literal {
+                $(Literal::$V(value) => *value == <$T>::MAX,)+
+                Literal::Float32(value) => *value == f32::INFINITY,
+                Literal::Float64(value) => *value == f64::INFINITY,
+                Literal::String(_) => false,
+            }
+        };
+    }
+    is_max!(
+        UInt8: u8, UInt16: u16, UInt32: u32, UInt64: u64,
+        Int8: i8, Int16: i16, Int32: i32, Int64: i64
+    )
+}
+
+impl SetMembers {
+    fn literals(&self) -> Vec<Literal> {
+        macro_rules! literals {
+            ($($V:ident),+) => {
+                match self {
+                    $(
+                        Self::$V(members) => members
+                            .iter()
+                            .map(|m| Literal::from(m.clone()))
+                            .collect(),
+                    )+
+                }
+            };
+        }
+        literals!(
+            UInt8, UInt16, UInt32, UInt64, Int8, Int16, Int32, Int64, Float32,
+            Float64, String
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use cells::{Cells, FieldData};
+    use proptest::bits::{BitSetLike, VarBitSet};
+    use proptest::prelude::*;
+
+    use super::QueryConditionExpr as QC;
+    use super::SimplifiedCondition as SC;
+    use super::*;
+    use crate::datatype::Datatype;
+    use crate::query::condition::strategy::prop_query_condition;
+
+    fn simplify(qc: QC) -> SC {
+        qc.simplify()
+    }
+
+    #[test]
+    fn simplify_negation() {
+        let a = || QC::field("a");
+
+        assert_eq!(SC::Expr(a().ge(5)), simplify(!a().lt(5)));
+        assert_eq!(SC::Expr(a().lt(5)), simplify(!!a().lt(5)));
+        assert_eq!(SC::Expr(a().ne(5)), simplify(!a().eq(5)));
+        assert_eq!(
+            SC::Expr(a().not_in(&[1, 2][..])),
+            simplify(!a().is_in(&[1, 2][..]))
+        );
+        assert_eq!(SC::Expr(a().not_null()), simplify(!a().is_null()));
+        assert_eq!(
+            SC::Expr(a().ne(1) | a().is_null()),
+            simplify(!(a().eq(1) & a().not_null()))
+        );
+
+        let b = || Operand::field("b");
+        assert_eq!(SC::Expr(b().ge(a())), simplify(!b().lt(a())));
+        assert_eq!(
+            SC::Expr((b() + 1).le(a()) & a().lt(5)),
+            simplify(!((b() + 1).gt(a()) | a().ge(5)))
+        );
+        assert_eq!(SC::AlwaysTrue, simplify(b().eq(a()) | b().ne(a())));
+        assert_eq!(SC::Expr(b().lt(a())), simplify(b().lt(a()) & b().lt(a())));
+    }
+
+    #[test]
+    fn simplify_flatten() {
+        let a = || QC::field("a").eq(1);
+        let b = || QC::field("b").eq(2);
+        let c = || QC::field("c").eq(3);
+
+        assert_eq!(SC::Expr(a() & b() & c()), simplify(a() & (b() & c())));
+        assert_eq!(SC::Expr(a() | b() | c()), simplify((a() | b()) | c()));
+        assert_eq!(
+            SC::Expr(a() & (b() | c())),
+            simplify(a() & (b() | (c() | b())))
+        );
+        assert_eq!(SC::Expr(a() & b()), simplify(a() & (b() & a())));
+        assert_eq!(SC::Expr(a()), simplify(a() | a()));
+    }
+
+    #[test]
+    fn simplify_ranges() {
+        let x = || QC::field("x");
+
+        assert_eq!(
+            SC::Expr(x().gt(1) & x().le(5)),
+            simplify(x().gt(0) & x().le(5) & x().gt(1) & x().lt(10))
+        );
+        assert_eq!(SC::Expr(x().eq(3)), simplify(x().ge(3) & x().le(3)));
+        assert_eq!(SC::Expr(x().eq(3)), simplify(x().eq(3) & x().lt(4)));
+        assert_eq!(SC::AlwaysFalse, simplify(x().eq(3) & x().gt(3)));
+        assert_eq!(SC::AlwaysFalse, simplify(x().gt(5) & x().lt(4)));
+        assert_eq!(SC::AlwaysFalse, simplify(x().eq(3) & x().eq(4)));
+        assert_eq!(
+            SC::Expr(x().lt(10) | x().ge(20)),
+            simplify(x().lt(5) | x().ge(20) | x().le(7) | x().lt(10))
+        );
+        assert_eq!(
+            SC::Expr(x().eq(1) | x().eq(2)),
+            simplify(x().eq(1) | x().eq(2))
+        );
+        assert_eq!(
+            SC::Expr(x().gt("abc") & x().lt("b")),
+            simplify(x().ge("a") & x().lt("b") & x().gt("abc"))
+        );
+
+        // literals of different types are not merged
+        assert_eq!(
+            SC::Expr(x().lt(5) & x().lt(4u32)),
+            simplify(x().lt(5) & x().lt(4u32))
+        );
+    }
+
+    #[test]
+    fn simplify_constants() {
+        let x = || QC::field("x");
+        let y = || QC::field("y").eq(1);
+
+        assert_eq!(SC::AlwaysFalse, simplify(x().lt(f64::NAN)));
+        assert_eq!(SC::AlwaysTrue, simplify(x().ne(f32::NAN)));
+        assert_eq!(SC::AlwaysTrue, simplify(!x().eq(f32::NAN)));
+        assert_eq!(SC::AlwaysFalse, simplify(x().lt(i32::MIN)));
+        assert_eq!(SC::AlwaysFalse, simplify(x().gt(u8::MAX)));
+        assert_eq!(SC::AlwaysFalse, simplify(x().lt("")));
+        assert_eq!(SC::Expr(x().le(u8::MAX)), simplify(x().le(u8::MAX)));
+
+        let empty = || &Vec::<i32>::new()[..];
+        assert_eq!(SC::AlwaysFalse, simplify(x().is_in(empty())));
+        assert_eq!(SC::AlwaysTrue, simplify(x().not_in(empty())));
+        assert_eq!(SC::Expr(y()), simplify(y() & x().not_in(empty())));
+        assert_eq!(SC::Expr(y()), simplify(y() | x().is_in(empty())));
+        assert_eq!(SC::AlwaysFalse, simplify(y() & x().is_in(empty())));
+
+        assert_eq!(SC::AlwaysTrue, simplify(x().is_null() | x().not_null()));
+        assert_eq!(SC::AlwaysFalse, simplify(x().eq(1) & !x().eq(1)));
+        assert_eq!(SC::AlwaysTrue, simplify(y() | x().eq(1) | !x().eq(1)));
+
+        // null and NaN satisfy neither
+        assert_eq!(
+            SC::Expr(x().lt(1) | x().ge(1)),
+            simplify(x().lt(1) | !x().lt(1))
+        );
+    }
+
+    #[test]
+    fn simplify_domain() {
+        let domains = HashMap::from([(
+            "d".to_owned(),
+            [Literal::from(1i32), Literal::from(4i32)],
+        )]);
+        let simplify = |qc: QC| qc.simplify_impl(&domains);
+        let d = || QC::field("d");
+        let a = || QC::field("a").eq(1);
+
+        assert_eq!(SC::AlwaysTrue, simplify(d().ge(1)));
+        assert_eq!(SC::AlwaysTrue, simplify(d().lt(5)));
+        assert_eq!(SC::AlwaysTrue, simplify(d().ne(0)));
+        assert_eq!(SC::AlwaysTrue, simplify(d().not_null()));
+        assert_eq!(SC::AlwaysTrue, simplify(d().not_in(&[0, 5][..])));
+        assert_eq!(SC::AlwaysFalse, simplify(d().gt(4)));
+        assert_eq!(SC::AlwaysFalse, simplify(d().le(0)));
+        assert_eq!(SC::AlwaysFalse, simplify(d().eq(5)));
+        assert_eq!(SC::AlwaysFalse, simplify(d().is_null()));
+        assert_eq!(SC::AlwaysFalse, simplify(d().is_in(&[0, 5][..])));
+
+        assert_eq!(SC::Expr(d().gt(1)), simplify(d().gt(1)));
+        assert_eq!(SC::Expr(d().eq(2)), simplify(d().eq(2)));
+        assert_eq!(
+            SC::Expr(d().le(2) & a()),
+            simplify(d().ge(0) & d().le(2) & a())
+        );
+        assert_eq!(SC::Expr(a()), simplify(d().gt(4) | a()));
+        assert_eq!(SC::Expr(d().le(2)), simplify(d().le(2) | d().gt(7)));
+    }
+
+    #[test]
+    fn simplify_with_schema() -> TileDBResult<()> {
+        use crate::{Context, Factory};
+
+        let ctx = Context::new()?;
+        let schema = crate::tests::examples::quickstart::Builder::new(
+            crate::tests::prelude::ArrayType::Sparse,
+        )
+        .build()
+        .create(&ctx)?;
+
+        let rows = || QC::field("rows");
+        let a = || QC::field("a").lt(5);
+
+        assert_eq!(SC::AlwaysTrue, rows().ge(1).simplify_with_schema(&schema)?);
+        assert_eq!(
+            SC::Expr(a()),
+            (rows().gt(4) | a()).simplify_with_schema(&schema)?
+        );
+        assert_eq!(
+            SC::Expr(rows().le(2) & a()),
+            (rows().le(2) & rows().ge(0) & a())
+                .simplify_with_schema(&schema)?
+        );
+
+        Ok(())
+    }
+
+    fn condition_fields() -> Vec<(String, Datatype)> {
+        vec![
+            ("i".to_owned(), Datatype::Int8),
+            ("u".to_owned(), Datatype::UInt8),
+            ("f".to_owned(), Datatype::Float32),
+            ("s".to_owned(), Datatype::StringAscii),
+        ]
+    }
+
+    /// Returns cells containing each value of the integral fields.
+    fn condition_cells() -> Cells {
+        let i = (i8::MIN..=i8::MAX).collect::<Vec<_>>();
+        let u = (u8::MIN..=u8::MAX).collect::<Vec<_>>();
+        let f = i
+            .iter()
+            .map(|i| match i {
+                -128 => f32::NAN,
+                -127 => f32::NEG_INFINITY,
+                127 => f32::INFINITY,
+                -126 => -0.0,
+                -125 => f32::MIN_POSITIVE / 2.0,
+                i => *i as f32 * 1.5,
+            })
+            .collect::<Vec<_>>();
+        let s = u
+            .iter()
+            .map(|u| {
+                (0..(u % 4))
+                    .map(|n| char::from(b'a' + (u >> (2 * n)) % 4))
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>();
+
+        Cells::new(HashMap::from([
+            ("i".to_owned(), FieldData::from(i)),
+            ("u".to_owned(), FieldData::from(u)),
+            ("f".to_owned(), FieldData::from(f)),
+            ("s".to_owned(), FieldData::from(s)),
+        ]))
+    }
+
+    fn evaluate(condition: &SC, cells: &Cells) -> Vec<bool> {
+        match condition {
+            SC::AlwaysTrue => vec![true; cells.len()],
+            SC::AlwaysFalse => vec![false; cells.len()],
+            SC::Expr(qc) => qc.evaluate(cells).unwrap(),
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn simplify_equivalent(qc in prop_query_condition(condition_fields())) {
+            let cells = condition_cells();
+            let expect = qc.evaluate(&cells).unwrap();
+            assert_eq!(expect, evaluate(&qc.simplify(), &cells));
+        }
+
+        #[test]
+        fn simplify_domain_equivalent(
+            qc in prop_query_condition(condition_fields())
+        ) {
+            let domains = HashMap::from([(
+                "i".to_owned(),
+                [Literal::from(-10i8), Literal::from(10i8)],
+            )]);
+
+            let cells = {
+                let cells = condition_cells();
+                let Some(FieldData::Int8(i)) = cells.fields().get("i") else {
+                    unreachable!()
+                };
+                let mut in_domain = VarBitSet::new_bitset(i.len());
+                i.iter()
+                    .enumerate()
+                    .filter(|(_, i)| (-10i8..=10).contains(*i))
+                    .for_each(|(r, _)| in_domain.set(r));
+                cells.filter(&in_domain)
+            };
+
+            let expect = qc.evaluate(&cells).unwrap();
+            assert_eq!(expect, evaluate(&qc.simplify_impl(&domains), &cells));
+        }
+
+        #[test]
+        fn simplify_idempotent(qc in prop_query_condition(condition_fields())) {
+            let simplified = qc.simplify();
+            if let SC::Expr(ref qc) = simplified {
+                assert_eq!(simplified, qc.simplify());
+            }
+        }
+    }
+}
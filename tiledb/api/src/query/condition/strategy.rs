@@ -1,6 +1,285 @@
-use proptest::strategy::ValueTree;
+use proptest::prelude::*;
+use proptest::strategy::{NewTree, ValueTree};
+use proptest::test_runner::TestRunner;
 
 use super::*;
+use crate::datatype::Datatype;
+use crate::physical_type_go;
+
+/// Words which cannot be used as field names in the text of a
+/// query condition.
+const KEYWORDS: [&str; 6] = ["AND", "OR", "NOT", "IN", "IS", "NULL"];
+
+/// Returns a strategy for generating the names and datatypes of a
+/// non-empty set of fields which query conditions can refer to.
+///
+/// Names which are keywords or numbers, such as `inf`, are excluded.
+pub fn prop_condition_fields() -> impl Strategy<Value = Vec<(String, Datatype)>>
+{
+    proptest::collection::hash_map(
+        "[a-z_][a-z0-9_]{0,8}".prop_filter("Keyword", |field| {
+            !KEYWORDS.iter().any(|k| k.eq_ignore_ascii_case(field))
+                && field.parse::<f64>().is_err()
+        }),
+        any::<Datatype>(),
+        1..=8,
+    )
+    .prop_map(|fields| fields.into_iter().collect::<Vec<_>>())
+}
+
+/// Returns a strategy for generating query conditions over `fields`.
+///
+/// Floating-point literals are never NaN, so that generated query
+/// conditions can be compared with the result of parsing their text.
+pub fn prop_query_condition(
+    fields: Vec<(String, Datatype)>,
+) -> QueryConditionStrategy {
+    let predicate = proptest::sample::select(fields.clone())
+        .prop_flat_map(|(field, datatype)| prop_predicate(field, datatype));
+    let leaf = prop_oneof![
+        4 => predicate,
+        1 => prop_comparison(fields)
+    ]
+    .prop_map(QueryConditionExpr::Cond);
+
+    let strategy = leaf.prop_recursive(4, 16, 2, |inner| {
+        prop_oneof![
+            (
+                inner.clone(),
+                inner.clone(),
+                prop_oneof![Just(CombinationOp::And), Just(CombinationOp::Or)]
+            )
+                .prop_map(|(lhs, rhs, op)| {
+                    QueryConditionExpr::Comb {
+                        lhs: Box::new(lhs),
+                        rhs: Box::new(rhs),
+                        op,
+                    }
+                }),
+            inner.prop_map(|expr| QueryConditionExpr::Negate(Box::new(expr)))
+        ]
+    });
+
+    QueryConditionStrategy(strategy.boxed())
+}
+
+fn prop_equality_op() -> impl Strategy<Value = EqualityOp> {
+    prop_oneof![
+        Just(EqualityOp::Less),
+        Just(EqualityOp::LessEqual),
+        Just(EqualityOp::Equal),
+        Just(EqualityOp::NotEqual),
+        Just(EqualityOp::GreaterEqual),
+        Just(EqualityOp::Greater),
+    ]
+}
+
+fn prop_predicate(
+    field: String,
+    datatype: Datatype,
+) -> impl Strategy<Value = Predicate> {
+    let equality = (
+        Just(field.clone()),
+        prop_equality_op(),
+        prop_literal(datatype),
+    )
+        .prop_map(|(field, op, value)| {
+            Predicate::Equality(EqualityPredicate {
+                field,
+                op,
+                value,
+                use_enumeration: false,
+            })
+        });
+
+    let set_membership = (
+        Just(field.clone()),
+        prop_oneof![Just(SetMembershipOp::In), Just(SetMembershipOp::NotIn)],
+        prop_set_members(datatype),
+    )
+        .prop_map(|(field, op, members)| {
+            Predicate::SetMembership(SetMembershipPredicate {
+                field,
+                op,
+                members,
+                use_enumeration: false,
+            })
+        });
+
+    let nullness = (
+        Just(field),
+        prop_oneof![Just(NullnessOp::IsNull), Just(NullnessOp::NotNull)],
+    )
+        .prop_map(|(field, op)| {
+            Predicate::Nullness(NullnessPredicate { field, op })
+        });
+
+    prop_oneof![equality, set_membership, nullness]
+}
+
+/// Returns a strategy for generating comparisons of operands computed
+/// from `fields`, which TileDB cannot apply itself.
+///
+/// Numbers are compared with numbers and strings with strings, so that
+/// the comparisons can be evaluated. A field is never compared with
+/// a literal, since that is an [EqualityPredicate] instead.
+fn prop_comparison(
+    fields: Vec<(String, Datatype)>,
+) -> BoxedStrategy<Predicate> {
+    let (strings, numbers): (Vec<_>, Vec<_>) =
+        fields.into_iter().partition(|(_, dt)| dt.is_string_type());
+    let strings = strings.into_iter().map(|(f, _)| f).collect::<Vec<_>>();
+    let numbers = numbers.into_iter().map(|(f, _)| f).collect::<Vec<_>>();
+
+    let operand = prop_operand(numbers.clone());
+    let literal = prop_numeric_literal().prop_map(Operand::Literal).boxed();
+    let arithmetic = prop_arithmetic(operand.clone(), operand.clone());
+
+    let mut operands = vec![
+        (arithmetic, operand).boxed(),
+        (literal.clone(), literal).boxed(),
+    ];
+    for names in [numbers, strings] {
+        if !names.is_empty() {
+            let field = proptest::sample::select(names)
+                .prop_map(Operand::Field)
+                .boxed();
+            operands.push((field.clone(), field).boxed());
+        }
+    }
+
+    (
+        proptest::strategy::Union::new(operands),
+        prop_equality_op(),
+        any::<bool>(),
+    )
+        .prop_map(|((lhs, rhs), op, swap)| {
+            let (lhs, rhs) = if swap { (rhs, lhs) } else { (lhs, rhs) };
+            Predicate::Comparison(ComparisonPredicate { lhs, op, rhs })
+        })
+        .boxed()
+}
+
+/// Returns a strategy for generating numeric operands computed
+/// from the fields named in `numbers`.
+fn prop_operand(numbers: Vec<String>) -> BoxedStrategy<Operand> {
+    let literal = prop_numeric_literal().prop_map(Operand::Literal);
+    let leaf = if numbers.is_empty() {
+        literal.boxed()
+    } else {
+        prop_oneof![
+            proptest::sample::select(numbers).prop_map(Operand::Field),
+            literal
+        ]
+        .boxed()
+    };
+    leaf.prop_recursive(2, 8, 2, |inner| prop_arithmetic(inner.clone(), inner))
+        .boxed()
+}
+
+fn prop_arithmetic(
+    lhs: BoxedStrategy<Operand>,
+    rhs: BoxedStrategy<Operand>,
+) -> BoxedStrategy<Operand> {
+    (
+        lhs,
+        rhs,
+        prop_oneof![
+            Just(ArithmeticOp::Add),
+            Just(ArithmeticOp::Subtract),
+            Just(ArithmeticOp::Multiply),
+            Just(ArithmeticOp::Divide),
+        ],
+    )
+        .prop_map(|(lhs, rhs, op)| Operand::Arithmetic {
+            lhs: Box::new(lhs),
+            rhs: Box::new(rhs),
+            op,
+        })
+        .boxed()
+}
+
+fn prop_numeric_literal() -> BoxedStrategy<Literal> {
+    proptest::sample::select(vec![
+        Datatype::UInt8,
+        Datatype::UInt16,
+        Datatype::UInt32,
+        Datatype::UInt64,
+        Datatype::Int8,
+        Datatype::Int16,
+        Datatype::Int32,
+        Datatype::Int64,
+        Datatype::Float32,
+        Datatype::Float64,
+    ])
+    .prop_flat_map(prop_literal)
+    .boxed()
+}
+
+fn prop_f32() -> impl Strategy<Value = f32> {
+    use proptest::num::f32::*;
+    POSITIVE | NEGATIVE | NORMAL | SUBNORMAL | ZERO | INFINITE
+}
+
+fn prop_f64() -> impl Strategy<Value = f64> {
+    use proptest::num::f64::*;
+    POSITIVE | NEGATIVE | NORMAL | SUBNORMAL | ZERO | INFINITE
+}
+
+fn prop_literal(datatype: Datatype) -> BoxedStrategy<Literal> {
+    match datatype {
+        dt if dt.is_string_type() => {
+            any::<String>().prop_map(Literal::from).boxed()
+        }
+        Datatype::Float32 => prop_f32().prop_map(Literal::from).boxed(),
+        Datatype::Float64 => prop_f64().prop_map(Literal::from).boxed(),
+        dt => physical_type_go!(dt, DT, {
+            any::<DT>().prop_map(Literal::from).boxed()
+        }),
+    }
+}
+
+fn prop_set_members(datatype: Datatype) -> BoxedStrategy<SetMembers> {
+    const MAX_MEMBERS: usize = 8;
+    match datatype {
+        dt if dt.is_string_type() => {
+            proptest::collection::vec(any::<String>(), 0..=MAX_MEMBERS)
+                .prop_map(SetMembers::String)
+                .boxed()
+        }
+        Datatype::Float32 => {
+            proptest::collection::vec(prop_f32(), 0..=MAX_MEMBERS)
+                .prop_map(|members| SetMembers::from(members.as_slice()))
+                .boxed()
+        }
+        Datatype::Float64 => {
+            proptest::collection::vec(prop_f64(), 0..=MAX_MEMBERS)
+                .prop_map(|members| SetMembers::from(members.as_slice()))
+                .boxed()
+        }
+        dt => physical_type_go!(dt, DT, {
+            proptest::collection::vec(any::<DT>(), 0..=MAX_MEMBERS)
+                .prop_map(|members| SetMembers::from(members.as_slice()))
+                .boxed()
+        }),
+    }
+}
+
+/// Strategy for generating query conditions which shrink
+/// using [QueryConditionValueTree].
+#[derive(Debug)]
+pub struct QueryConditionStrategy(BoxedStrategy<QueryConditionExpr>);
+
+impl Strategy for QueryConditionStrategy {
+    type Tree = QueryConditionValueTree;
+    type Value = QueryConditionExpr;
+
+    fn new_tree(&self, runner: &mut TestRunner) -> NewTree<Self> {
+        Ok(QueryConditionValueTree::new(
+            self.0.new_tree(runner)?.current(),
+        ))
+    }
+}
 
 #[derive(Debug)]
 enum CombinationOpState {
@@ -158,6 +437,7 @@ enum PredicateValueTree {
     Equality(EqualityPredicateValueTree),
     SetMembership(SetMembershipValueTree),
     Nullness(NullnessPredicate),
+    Comparison(ComparisonPredicate),
 }
 
 impl PredicateValueTree {
@@ -170,6 +450,7 @@ impl PredicateValueTree {
                 Self::SetMembership(SetMembershipValueTree::new(s))
             }
             Predicate::Nullness(n) => Self::Nullness(n),
+            Predicate::Comparison(c) => Self::Comparison(c),
         }
     }
 }
@@ -182,6 +463,7 @@ impl ValueTree for PredicateValueTree {
             Self::Equality(e) => Predicate::Equality(e.current()),
             Self::SetMembership(m) => Predicate::SetMembership(m.current()),
             Self::Nullness(m) => Predicate::Nullness(m.clone()),
+            Self::Comparison(c) => Predicate::Comparison(c.clone()),
         }
     }
 
@@ -189,7 +471,7 @@ impl ValueTree for PredicateValueTree {
         match self {
             Self::Equality(e) => e.simplify(),
             Self::SetMembership(m) => m.simplify(),
-            Self::Nullness(_) => false,
+            Self::Nullness(_) | Self::Comparison(_) => false,
         }
     }
 
@@ -197,7 +479,7 @@ impl ValueTree for PredicateValueTree {
         match self {
             Self::Equality(e) => e.complicate(),
             Self::SetMembership(m) => m.complicate(),
-            Self::Nullness(_) => false,
+            Self::Nullness(_) | Self::Comparison(_) => false,
         }
     }
 }
@@ -0,0 +1,165 @@
+use super::*;
+
+/// A query which deletes the cells of an array matching a query condition.
+///
+/// Deletes are recorded in a new fragment and hide the matching cells
+/// from all subsequent reads of the array.
+/// The array must be opened in [`Mode::Delete`](crate::array::Mode::Delete),
+/// and deletes are only supported for sparse arrays.
+pub struct DeleteQuery {
+    base: QueryBase,
+}
+
+impl ContextBound for DeleteQuery {
+    fn context(&self) -> Context {
+        self.base.context()
+    }
+}
+
+impl Query for DeleteQuery {
+    fn base(&self) -> &QueryBase {
+        self.base.base()
+    }
+
+    fn finalize(self) -> TileDBResult<Array> {
+        self.base.finalize()
+    }
+}
+
+impl DeleteQuery {
+    pub fn submit(&self) -> TileDBResult<()> {
+        self.base.do_submit()
+    }
+}
+
+/// Builder for a [`DeleteQuery`].
+///
+/// The cells to delete are chosen using [`QueryBuilder::query_condition`],
+/// which must be called before the query is submitted.
+pub struct DeleteBuilder {
+    base: BuilderBase,
+}
+
+impl ContextBound for DeleteBuilder {
+    fn context(&self) -> Context {
+        self.base.context()
+    }
+}
+
+impl QueryBuilder for DeleteBuilder {
+    type Query = DeleteQuery;
+
+    fn base(&self) -> &BuilderBase {
+        &self.base
+    }
+
+    fn build(self) -> Self::Query {
+        DeleteQuery {
+            base: self.base.build(),
+        }
+    }
+}
+
+impl DeleteBuilder {
+    pub fn new(array: Array) -> TileDBResult<Self> {
+        Ok(DeleteBuilder {
+            base: BuilderBase::new(array, QueryType::Delete)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::rc::Rc;
+
+    use cells::write::strategy::SparseWriteParameters;
+    use cells::write::SparseWriteInput;
+    use cells::{Cells, FieldData};
+    use proptest::bits::{BitSetLike, VarBitSet};
+    use proptest::prelude::*;
+
+    use super::*;
+    use crate::array::ArrayOpener;
+    use crate::query::{ReadBuilder, ReadQuery, ToReadQuery, ToWriteQuery};
+    use crate::tests::prelude::*;
+
+    /// Writes `input` into a new quickstart array, deletes the cells where
+    /// `a < threshold`, and checks that only the remaining cells are read back.
+    fn do_delete_readback(
+        schema: Rc<SchemaData>,
+        input: SparseWriteInput,
+        threshold: i32,
+    ) -> TileDBResult<()> {
+        let array = TestArray::new("delete_readback", schema)?;
+
+        // write at timestamp 1 and delete at timestamp 2 so that
+        // the delete is guaranteed to apply to the written cells
+        {
+            let w = ArrayOpener::new(&array.context, &array.uri, Mode::Write)?
+                .end_timestamp(1)?
+                .open()?;
+            let w = input.attach_write(WriteBuilder::new(w)?)?.build();
+            w.submit()?;
+            w.finalize()?;
+        }
+        {
+            let d = ArrayOpener::new(&array.context, &array.uri, Mode::Delete)?
+                .end_timestamp(2)?
+                .open()?;
+            let d = DeleteBuilder::new(d)?
+                .query_condition(QueryConditionExpr::field("a").lt(threshold))?
+                .build();
+            d.submit()?;
+            d.finalize()?;
+        }
+
+        let expect = {
+            let Some(FieldData::Int32(a)) = input.data.fields().get("a") else {
+                unreachable!()
+            };
+            let mut keep = VarBitSet::new_bitset(a.len());
+            a.iter()
+                .enumerate()
+                .filter(|(_, a)| **a >= threshold)
+                .for_each(|(i, _)| keep.set(i));
+            input.data.filter(&keep)
+        };
+
+        let sort_keys = vec!["rows".to_owned(), "cols".to_owned()];
+
+        let mut read = input
+            .attach_read(ReadBuilder::new(array.for_read()?)?)?
+            .build();
+        let (cells, _): (Cells, _) = read.execute()?;
+
+        assert_eq!(expect.sorted(&sort_keys), cells.sorted(&sort_keys));
+
+        Ok(())
+    }
+
+    #[test]
+    fn proptest_delete_readback() {
+        let schema = Rc::new(
+            crate::tests::examples::quickstart::Builder::new(ArrayType::Sparse)
+                .build(),
+        );
+
+        let strategy = any_with::<SparseWriteInput>(SparseWriteParameters {
+            schema: Some(Rc::clone(&schema)),
+            ..Default::default()
+        })
+        .prop_flat_map(|input| {
+            let threshold = match input.data.fields().get("a") {
+                Some(FieldData::Int32(a)) if !a.is_empty() => {
+                    proptest::sample::select(a.clone()).boxed()
+                }
+                _ => any::<i32>().boxed(),
+            };
+            (Just(input), threshold)
+        });
+
+        proptest!(|((input, threshold) in strategy)| {
+            do_delete_readback(Rc::clone(&schema), input, threshold)?;
+        });
+    }
+}
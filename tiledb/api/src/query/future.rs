@@ -0,0 +1,511 @@
+//! Futures which run queries on a background thread pool.
+//!
+//! Submitting a query blocks the calling thread until libtiledb has
+//! filled the query buffers (for reads) or consumed them (for writes).
+//! The futures in this module instead send the submission to a pool of
+//! worker threads, leaving the caller free to poll other futures.
+//!
+//! Each future owns its query, and therefore also owns its buffers,
+//! so that they cannot be freed or moved while libtiledb is using them.
+//! The query is handed back to the caller when the future resolves.
+//!
+//! The futures are `Send`, and so can be run by multi-threaded executors.
+//! For reads, this requires the query itself to be `Send`, which rules out
+//! queries whose results are written into buffers registered through a
+//! [RawReadHandle](crate::query::read::RawReadHandle); those buffers
+//! are shared with the application or with a non-`Send` allocator.
+//! Aggregate queries, which own their result, can be run asynchronously.
+//!
+//! A pending submission can be aborted using `cancel`, which calls
+//! [Context::cancel_tasks]. This cancels *all* of the in-progress work
+//! of the query's context, not just the work of the query itself,
+//! so use a dedicated [Context] for queries which may be cancelled.
+//! The future then resolves to [Error::Cancelled], as it does if the
+//! query has a deadline which passes (see
+//! [`QueryBuilder::deadline`](crate::query::QueryBuilder::deadline)).
+//!
+//! Dropping a future whose query is being submitted blocks until
+//! the submission finishes.
+
+use std::cell::Cell;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::mpsc::{self, Sender};
+use std::sync::{Arc, Condvar, Mutex, OnceLock};
+use std::task::{ready, Context as TaskContext, Poll, Waker};
+use std::thread;
+
+use crate::context::{CApiError, CApiResult, Context, ContextBound, RawError};
+use crate::error::Error;
+use crate::query::watchdog::Watch;
+use crate::query::{Query, QueryBase, ReadQuery, ReadStepOutput, WriteQuery};
+use crate::Result as TileDBResult;
+
+type Job = Box<dyn FnOnce() + Send>;
+
+/// Returns the queue of the worker thread pool, starting the pool
+/// if it has not been started yet.
+fn pool() -> &'static Sender<Job> {
+    static POOL: OnceLock<Sender<Job>> = OnceLock::new();
+    POOL.get_or_init(|| {
+        let (sender, receiver) = mpsc::channel::<Job>();
+        let receiver = Arc::new(Mutex::new(receiver));
+
+        let nthreads = thread::available_parallelism()
+            .map(|n| n.get())
+            .unwrap_or(4);
+        for i in 0..nthreads {
+            let receiver = Arc::clone(&receiver);
+            thread::Builder::new()
+                .name(format!("tiledb-query-{}", i))
+                .spawn(move || loop {
+                    let job = receiver
+                        .lock()
+                        .unwrap_or_else(|e| e.into_inner())
+                        .recv();
+                    match job {
+                        Ok(job) => job(),
+                        Err(_) => break,
+                    }
+                })
+                .expect("Error spawning query worker thread");
+        }
+        sender
+    })
+}
+
+/// The pointers which a worker thread needs to submit a query.
+struct RawSubmit {
+    ctx: *mut ffi::tiledb_ctx_t,
+    query: *mut ffi::tiledb_query_t,
+}
+
+// SAFETY: libtiledb contexts and queries may be used from any thread.
+// The future which created the `RawSubmit` owns the query and does not
+// use it, or allow it to be dropped, until the worker has finished.
+unsafe impl Send for RawSubmit {}
+
+impl RawSubmit {
+    fn submit(self) -> CApiResult<()> {
+        let c_ret = unsafe { ffi::tiledb_query_submit(self.ctx, self.query) };
+        if c_ret == ffi::TILEDB_OK {
+            return Ok(());
+        }
+
+        /*
+         * The error must be retrieved on this thread, before
+         * another call on the same context replaces it.
+         */
+        let mut c_err: *mut ffi::tiledb_error_t = out_ptr!();
+        let c_ret =
+            unsafe { ffi::tiledb_ctx_get_last_error(self.ctx, &mut c_err) };
+        if c_ret == ffi::TILEDB_OK && !c_err.is_null() {
+            Err(CApiError::from(RawError::Owned(c_err)))
+        } else {
+            Err(CApiError::Internal)
+        }
+    }
+}
+
+#[derive(Default)]
+struct SubmissionState {
+    result: Option<CApiResult<()>>,
+    finished: bool,
+    waker: Option<Waker>,
+}
+
+#[derive(Default)]
+struct SubmissionShared {
+    state: Mutex<SubmissionState>,
+    finished: Condvar,
+}
+
+/// A call to `tiledb_query_submit` running on the worker thread pool.
+///
+/// Dropping a `Submission` blocks until the call has returned.
+struct Submission {
+    context: Context,
+    shared: Arc<SubmissionShared>,
+    cancelled: Cell<bool>,
+    watch: Option<Watch>,
+}
+
+impl Submission {
+    fn start(query: &QueryBase) -> TileDBResult<Self> {
+        let watch = query.watch()?;
+        let context = query.context();
+        let raw = RawSubmit {
+            ctx: context.capi(),
+            query: *query.raw,
+        };
+
+        let shared = Arc::new(SubmissionShared::default());
+        let worker_shared = Arc::clone(&shared);
+        let job: Job = Box::new(move || {
+            let result = raw.submit();
+
+            let mut state = worker_shared
+                .state
+                .lock()
+                .unwrap_or_else(|e| e.into_inner());
+            state.result = Some(result);
+            state.finished = true;
+            if let Some(waker) = state.waker.take() {
+                waker.wake();
+            }
+            worker_shared.finished.notify_all();
+        });
+        pool()
+            .send(job)
+            .expect("Query worker thread pool has shut down");
+
+        Ok(Submission {
+            context,
+            shared,
+            cancelled: Cell::new(false),
+            watch,
+        })
+    }
+
+    fn is_cancelled(&self) -> bool {
+        self.cancelled.get() || self.watch.as_ref().is_some_and(Watch::expired)
+    }
+
+    fn poll(&self, cx: &mut TaskContext<'_>) -> Poll<TileDBResult<()>> {
+        let mut state =
+            self.shared.state.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(result) = state.result.take() {
+            Poll::Ready(match result {
+                Ok(()) => Ok(()),
+                Err(_) if self.is_cancelled() => Err(Error::Cancelled),
+                Err(e) => Err(e.into()),
+            })
+        } else {
+            state.waker = Some(cx.waker().clone());
+            Poll::Pending
+        }
+    }
+
+    fn cancel(&self) -> TileDBResult<()> {
+        self.cancelled.set(true);
+        self.context.cancel_tasks()
+    }
+}
+
+impl Drop for Submission {
+    fn drop(&mut self) {
+        let mut state =
+            self.shared.state.lock().unwrap_or_else(|e| e.into_inner());
+        while !state.finished {
+            state = self
+                .shared
+                .finished
+                .wait(state)
+                .unwrap_or_else(|e| e.into_inner());
+        }
+    }
+}
+
+/// Future which runs a single step of a [ReadQuery].
+/// Resolves to the query and the output of the step.
+///
+/// See [ReadQuery::step_async].
+pub struct StepFuture<Q>
+where
+    Q: ReadQuery,
+{
+    // N.B. this must be declared before `query` so that it is dropped,
+    // and thus waits for libtiledb, before the query is dropped
+    submission: Option<Submission>,
+    // The query is boxed so that the buffers which it attaches
+    // do not move if the future moves while the query is submitted.
+    query: Option<Box<Q>>,
+}
+
+type StepResult<Q> = TileDBResult<
+    ReadStepOutput<<Q as ReadQuery>::Intermediate, <Q as ReadQuery>::Final>,
+>;
+
+impl<Q> StepFuture<Q>
+where
+    Q: ReadQuery,
+{
+    pub(crate) fn new(query: Q) -> Self {
+        Self::from_box(Box::new(query))
+    }
+
+    fn from_box(query: Box<Q>) -> Self {
+        StepFuture {
+            submission: None,
+            query: Some(query),
+        }
+    }
+
+    /// Cancels the step if the query is currently being submitted.
+    /// The future will then resolve to an error.
+    ///
+    /// This cancels all of the tasks of the query's context.
+    pub fn cancel(&self) -> TileDBResult<()> {
+        match self.submission.as_ref() {
+            Some(submission) => submission.cancel(),
+            None => Ok(()),
+        }
+    }
+
+    fn poll_boxed(
+        &mut self,
+        cx: &mut TaskContext<'_>,
+    ) -> Poll<(Box<Q>, StepResult<Q>)> {
+        if self.submission.is_none() {
+            let query = self
+                .query
+                .as_mut()
+                .expect("StepFuture polled after completion");
+            let submission = query
+                .prepare_step()
+                .and_then(|_| Submission::start(query.base()));
+            match submission {
+                Ok(submission) => self.submission = Some(submission),
+                Err(e) => {
+                    return Poll::Ready((self.query.take().unwrap(), Err(e)))
+                }
+            }
+        }
+
+        let submitted = ready!(self.submission.as_ref().unwrap().poll(cx));
+        self.submission = None;
+
+        let mut query = self.query.take().unwrap();
+        let result = submitted.and_then(|_| query.complete_step());
+        Poll::Ready((query, result))
+    }
+}
+
+impl<Q> Future for StepFuture<Q>
+where
+    Q: ReadQuery,
+{
+    type Output = (Q, StepResult<Q>);
+
+    fn poll(
+        self: Pin<&mut Self>,
+        cx: &mut TaskContext<'_>,
+    ) -> Poll<Self::Output> {
+        let (query, result) = ready!(self.get_mut().poll_boxed(cx));
+        Poll::Ready((*query, result))
+    }
+}
+
+/// Future which runs a [ReadQuery] to completion.
+/// Resolves to the query and its final result.
+///
+/// See [ReadQuery::execute_async].
+pub struct ExecuteFuture<Q>
+where
+    Q: ReadQuery,
+{
+    step: StepFuture<Q>,
+}
+
+impl<Q> ExecuteFuture<Q>
+where
+    Q: ReadQuery,
+{
+    pub(crate) fn new(query: Q) -> Self {
+        ExecuteFuture {
+            step: StepFuture::new(query),
+        }
+    }
+
+    /// Cancels the query if it is currently being submitted.
+    /// The future will then resolve to an error.
+    ///
+    /// This cancels all of the tasks of the query's context.
+    pub fn cancel(&self) -> TileDBResult<()> {
+        self.step.cancel()
+    }
+}
+
+impl<Q> Future for ExecuteFuture<Q>
+where
+    Q: ReadQuery,
+{
+    type Output = (Q, TileDBResult<Q::Final>);
+
+    fn poll(
+        self: Pin<&mut Self>,
+        cx: &mut TaskContext<'_>,
+    ) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        loop {
+            let (query, result) = ready!(this.step.poll_boxed(cx));
+            match result {
+                Ok(ReadStepOutput::Final(result)) => {
+                    return Poll::Ready((*query, Ok(result)))
+                }
+                Ok(_) => this.step = StepFuture::from_box(query),
+                Err(e) => return Poll::Ready((*query, Err(e))),
+            }
+        }
+    }
+}
+
+/// Future which submits a [WriteQuery].
+/// Resolves to the query and the result of the submission.
+///
+/// See [WriteQuery::submit_async].
+pub struct SubmitFuture {
+    // N.B. this must be declared before `query`, see `StepFuture`
+    submission: Option<Submission>,
+    query: Option<Box<WriteQuery<'static>>>,
+}
+
+impl SubmitFuture {
+    pub(crate) fn new(query: WriteQuery<'static>) -> Self {
+        SubmitFuture {
+            submission: None,
+            query: Some(Box::new(query)),
+        }
+    }
+
+    /// Cancels the submission if it is in progress.
+    /// The future will then resolve to an error.
+    ///
+    /// This cancels all of the tasks of the query's context.
+    pub fn cancel(&self) -> TileDBResult<()> {
+        match self.submission.as_ref() {
+            Some(submission) => submission.cancel(),
+            None => Ok(()),
+        }
+    }
+}
+
+impl Future for SubmitFuture {
+    type Output = (WriteQuery<'static>, TileDBResult<()>);
+
+    fn poll(
+        self: Pin<&mut Self>,
+        cx: &mut TaskContext<'_>,
+    ) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        if this.submission.is_none() {
+            let query = this
+                .query
+                .as_ref()
+                .expect("SubmitFuture polled after completion");
+            match Submission::start(query.base()) {
+                Ok(submission) => this.submission = Some(submission),
+                Err(e) => {
+                    return Poll::Ready((*this.query.take().unwrap(), Err(e)))
+                }
+            }
+        }
+
+        let result = ready!(this.submission.as_ref().unwrap().poll(cx));
+        this.submission = None;
+        Poll::Ready((*this.query.take().unwrap(), result))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::query::buffer::{Buffer, CellStructure, QueryBuffers};
+    use crate::query::read::{AggregateQuery, AggregateQueryBuilder};
+    use crate::tests::futures::thread_waker;
+    use crate::tests::prelude::*;
+
+    fn owned(values: Vec<i32>) -> QueryBuffers<'static, i32> {
+        QueryBuffers {
+            data: Buffer::from(values),
+            cell_structure: CellStructure::single(),
+            validity: None,
+        }
+    }
+
+    /// The futures can be run by multi-threaded executors.
+    #[test]
+    fn futures_are_send() {
+        fn is_send<T: Send>() {}
+
+        type Q = AggregateQuery<u64, QueryBase>;
+        is_send::<StepFuture<Q>>();
+        is_send::<ExecuteFuture<Q>>();
+        is_send::<SubmitFuture>();
+    }
+
+    #[test]
+    fn submit_and_execute_async() -> anyhow::Result<()> {
+        let mut array = TestArray::new(
+            "submit_and_execute_async",
+            std::rc::Rc::new(
+                crate::tests::examples::quickstart::Builder::new(
+                    ArrayType::Sparse,
+                )
+                .build(),
+            ),
+        )?;
+
+        {
+            let w = WriteBuilder::new(array.for_write()?)?
+                .buffers("rows", owned(vec![1, 2, 3, 4]).into())?
+                .buffers("cols", owned(vec![1, 2, 3, 4]).into())?
+                .buffers("a", owned(vec![10, 11, 12, 13]).into())?
+                .build();
+
+            let (w, result) = block_on(w.submit_async());
+            result?;
+            w.finalize()?;
+        }
+
+        let q = ReadBuilder::new(array.for_read()?)?
+            .sum::<i64>("a")?
+            .build();
+
+        let (mut q, result) = block_on(q.execute_async());
+        let (sum, _) = result?;
+        assert_eq!(Some(46), sum);
+
+        // the query is still usable synchronously afterwards
+        let (sum, _) = q.execute()?;
+        assert_eq!(Some(46), sum);
+
+        // cancelling a future which has not started is a no-op
+        let step = q.step_async();
+        step.cancel()?;
+        let (_, result) = block_on(step);
+        assert!(result?.is_final());
+
+        Ok(())
+    }
+
+    #[test]
+    fn cancel_in_flight() -> anyhow::Result<()> {
+        // N.B. the test array has its own context,
+        // so cancelling does not affect other tests
+        let array = sparse_array("cancel_in_flight")?;
+
+        let q = ReadBuilder::new(array.for_read()?)?.count()?.build();
+        let mut execute = std::pin::pin!(q.execute_async());
+
+        // the first poll starts submitting the query
+        let waker = thread_waker();
+        let first = execute.as_mut().poll(&mut TaskContext::from_waker(&waker));
+
+        let result = match first {
+            Poll::Ready((_, result)) => result,
+            Poll::Pending => {
+                execute.cancel()?;
+                block_on(execute).1
+            }
+        };
+
+        // the submission may have finished before it was cancelled
+        match result {
+            Ok((count, _)) => assert_eq!(Some(4), count),
+            Err(e) => assert!(matches!(e, Error::Cancelled), "{:?}", e),
+        }
+
+        Ok(())
+    }
+}
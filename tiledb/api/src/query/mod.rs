@@ -1,23 +1,44 @@
+use std::cell::Cell;
 use std::ops::Deref;
+use std::time::{Duration, Instant};
 
+use anyhow::anyhow;
+
+use crate::array::{ArrayType, RawArray};
 use crate::context::{CApiInterface, Context, ContextBound};
 use crate::error::Error;
-use crate::{array::RawArray, Array, Result as TileDBResult};
+use crate::{Array, Result as TileDBResult};
 
 pub mod buffer;
 pub mod condition;
+pub mod delete;
+#[cfg(feature = "async")]
+pub mod future;
 pub mod read;
+mod residual;
 pub mod subarray;
+pub mod update;
+mod watchdog;
 pub mod write;
 
 pub use self::condition::QueryConditionExpr;
+pub use self::delete::{DeleteBuilder, DeleteQuery};
+#[cfg(feature = "async")]
+pub use self::future::{ExecuteFuture, StepFuture, SubmitFuture};
+#[cfg(feature = "arrow")]
+pub use self::read::arrow::{ArrowReadBuilder, ArrowReadQuery};
 pub use self::read::{
     ReadBuilder, ReadQuery, ReadQueryBuilder, ReadStepOutput, TypedReadBuilder,
 };
 pub use self::subarray::{Builder as SubarrayBuilder, Subarray};
-pub use self::write::{WriteBuilder, WriteQuery};
+pub use self::update::{UpdateBuilder, UpdateQuery};
+pub use self::write::{
+    WriteBuilder, WriteQuery, WrittenFragment, WrittenFragments,
+};
 
+use self::residual::Residual;
 use self::subarray::RawSubarray;
+use self::watchdog::Watch;
 
 pub type QueryType = crate::array::Mode;
 pub type QueryLayout = crate::array::CellOrder;
@@ -44,6 +65,10 @@ pub enum RawQuery {
     Owned(*mut ffi::tiledb_query_t),
 }
 
+// SAFETY: a query may be used from any thread, but not from several
+// threads at once, so `RawQuery` is `Send` but not `Sync`.
+unsafe impl Send for RawQuery {}
+
 impl Deref for RawQuery {
     type Target = *mut ffi::tiledb_query_t;
     fn deref(&self) -> &Self::Target {
@@ -92,11 +117,48 @@ pub trait Query {
             RawSubarray::Owned(c_subarray),
         ))
     }
+
+    /// Returns the statistics collected while running this query.
+    ///
+    /// Statistics are only collected while they are enabled
+    /// (see [crate::stats::enable]). Returns `None` if no statistics
+    /// were collected.
+    #[cfg(feature = "serde")]
+    fn stats(&self) -> TileDBResult<Option<crate::stats::Metrics>> {
+        let c_query = *self.base().raw;
+        let mut c_json: *mut std::ffi::c_char = out_ptr!();
+        self.base().capi_call(|ctx| unsafe {
+            ffi::tiledb_query_get_stats(ctx, c_query, &mut c_json)
+        })?;
+
+        assert!(!c_json.is_null());
+        let raw = crate::stats::RawStatsString::Owned(c_json);
+        Ok(crate::stats::query_metrics(raw)?)
+    }
+}
+
+/// Estimated size, in bytes, of the buffers needed to hold
+/// the results of a read query for one field.
+///
+/// Estimates are computed by libtiledb from the fragment metadata
+/// of the array and the subarray of the query. They are not exact,
+/// but are usually close enough to size scratch space so that
+/// few (if any) incomplete steps are needed.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct ResultSizeEstimate {
+    /// Estimated size of the cell values.
+    pub data: u64,
+    /// Estimated size of the cell offsets, if the field is var-sized.
+    pub offsets: Option<u64>,
+    /// Estimated size of the cell validity, if the field is nullable.
+    pub validity: Option<u64>,
 }
 
 pub struct QueryBase {
     array: Array,
     raw: RawQuery,
+    deadline: Option<Instant>,
+    residual: Option<Residual>,
 }
 
 impl ContextBound for QueryBase {
@@ -113,10 +175,24 @@ impl QueryBase {
     /// Executes a single step of the query.
     fn do_submit(&self) -> TileDBResult<()> {
         let c_query = **self.cquery();
-        self.capi_call(|ctx| unsafe {
-            ffi::tiledb_query_submit(ctx, c_query)
-        })?;
-        Ok(())
+        let watch = self.watch()?;
+        let result = self
+            .capi_call(|ctx| unsafe { ffi::tiledb_query_submit(ctx, c_query) });
+        match result {
+            Ok(()) => Ok(()),
+            Err(_) if watch.as_ref().is_some_and(Watch::expired) => {
+                Err(Error::Cancelled)
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Registers a submission of this query with the watchdog,
+    /// if the query has a deadline.
+    fn watch(&self) -> TileDBResult<Option<Watch>> {
+        self.deadline
+            .map(|deadline| Watch::start(&self.context(), deadline))
+            .transpose()
     }
 
     /// Returns the ffi status of the last submit()
@@ -129,9 +205,89 @@ impl QueryBase {
         Ok(c_status)
     }
 
+    /// Returns the estimated result size of `field` for this query.
+    fn do_est_result_size(
+        &self,
+        field: &str,
+    ) -> TileDBResult<ResultSizeEstimate> {
+        let (is_var, is_nullable) = {
+            let schema = self.array.schema()?;
+            let field = schema.field(field)?;
+            (field.cell_val_num()?.is_var_sized(), field.nullability()?)
+        };
+
+        let c_query = **self.cquery();
+        let c_name = cstring!(field);
+
+        let mut data = 0u64;
+        let mut offsets = 0u64;
+        let mut validity = 0u64;
+
+        match (is_var, is_nullable) {
+            (false, false) => self.capi_call(|ctx| unsafe {
+                ffi::tiledb_query_get_est_result_size(
+                    ctx,
+                    c_query,
+                    c_name.as_ptr(),
+                    &mut data,
+                )
+            }),
+            (true, false) => self.capi_call(|ctx| unsafe {
+                ffi::tiledb_query_get_est_result_size_var(
+                    ctx,
+                    c_query,
+                    c_name.as_ptr(),
+                    &mut offsets,
+                    &mut data,
+                )
+            }),
+            (false, true) => self.capi_call(|ctx| unsafe {
+                ffi::tiledb_query_get_est_result_size_nullable(
+                    ctx,
+                    c_query,
+                    c_name.as_ptr(),
+                    &mut data,
+                    &mut validity,
+                )
+            }),
+            (true, true) => self.capi_call(|ctx| unsafe {
+                ffi::tiledb_query_get_est_result_size_var_nullable(
+                    ctx,
+                    c_query,
+                    c_name.as_ptr(),
+                    &mut offsets,
+                    &mut data,
+                    &mut validity,
+                )
+            }),
+        }?;
+
+        Ok(ResultSizeEstimate {
+            data,
+            offsets: if is_var { Some(offsets) } else { None },
+            validity: if is_nullable { Some(validity) } else { None },
+        })
+    }
+
     pub fn array(&self) -> &Array {
         &self.array
     }
+
+    /// Returns whether part of the query condition is evaluated
+    /// on the results of each step, rather than by TileDB.
+    pub(crate) fn has_residual(&self) -> bool {
+        self.residual.is_some()
+    }
+
+    /// Returns whether the last step read cells, but none of them
+    /// satisfied the part of the query condition which is evaluated
+    /// on the results of each step.
+    ///
+    /// Query adapters use this to distinguish a step which produced no
+    /// results from one which did not have enough space for any results.
+    pub(crate) fn filtered_all(&self) -> bool {
+        self.residual.as_ref().is_some_and(Residual::filtered_all)
+    }
 }
 
 impl Query for QueryBase {
@@ -153,11 +309,31 @@ impl ReadQuery for QueryBase {
     type Intermediate = ();
     type Final = ();
 
-    fn step(
+    fn prepare_step(&mut self) -> TileDBResult<()> {
+        if let Some(ref mut residual) = self.residual {
+            residual.prepare_step(&self.array, *self.raw)?;
+        }
+        Ok(())
+    }
+
+    fn complete_step(
         &mut self,
     ) -> TileDBResult<ReadStepOutput<Self::Intermediate, Self::Final>> {
-        self.do_submit()?;
+        let output = self.complete_status()?;
+        if let Some(ref mut residual) = self.residual {
+            residual.complete_step(
+                &self.array,
+                *self.raw,
+                output.is_final(),
+            )?;
+        }
+        Ok(output)
+    }
+}
 
+impl QueryBase {
+    /// Returns the result of the last submission, according to its status.
+    fn complete_status(&self) -> TileDBResult<ReadStepOutput<(), ()>> {
         match self.capi_status()? {
             ffi::tiledb_query_status_t_TILEDB_FAILED => {
                 Err(Error::from(self.context().get_last_error()
@@ -234,21 +410,76 @@ pub trait QueryBuilder: Sized {
         SubarrayBuilder::for_query(self)
     }
 
+    /// Adds a condition which the cells of the query must satisfy.
+    /// If this is called more than once, the cells must satisfy
+    /// all of the conditions.
+    ///
+    /// The part of the condition which TileDB can apply
+    /// (see [QueryConditionExpr::split_pushdown]) is applied by TileDB.
+    /// The rest of the condition, such as a [ComparisonPredicate] which
+    /// compares fields with each other, is evaluated on the results of
+    /// each step of the query, so a step may produce fewer cells than it
+    /// read, possibly none. The fields which it refers to are read even if
+    /// the query does not otherwise read them. Only reads of sparse arrays
+    /// can evaluate such a condition; for other queries it is rejected
+    /// with [Error::InvalidArgument].
+    ///
+    /// [ComparisonPredicate]: crate::query::condition::ComparisonPredicate
     fn query_condition(self, qc: QueryConditionExpr) -> TileDBResult<Self> {
-        let raw = qc.build(&self.base().context())?;
-        let c_query = **self.base().cquery();
-        let c_cond = *raw;
-        self.base().capi_call(|ctx| unsafe {
-            ffi::tiledb_query_set_condition(ctx, c_query, c_cond)
-        })?;
+        let base = self.base();
+        let (pushdown, residual) = qc.split_pushdown();
+
+        if let Some(residual) = residual {
+            base.check_residual(&residual)?;
+            base.residual.set(Some(match base.residual.take() {
+                Some(prev) => prev & residual,
+                None => residual,
+            }));
+        }
+
+        if let Some(pushdown) = pushdown {
+            /* setting a condition replaces the one which was set before */
+            let pushdown = match base.pushdown.take() {
+                Some(prev) => prev & pushdown,
+                None => pushdown,
+            };
+            let raw = pushdown.build(&base.context())?;
+            let c_query = **base.cquery();
+            let c_cond = *raw;
+            base.capi_call(|ctx| unsafe {
+                ffi::tiledb_query_set_condition(ctx, c_query, c_cond)
+            })?;
+            base.pushdown.set(Some(pushdown));
+        }
+
         Ok(self)
     }
 
+    /// Sets a time limit for running the query, starting from when
+    /// the query is built.
+    ///
+    /// Submitting the query after the deadline has passed, or a submission
+    /// which is still running when the deadline passes, fails with
+    /// [Error::Cancelled]. The running submission is stopped by cancelling
+    /// all of the tasks of the query's context (see [Context::cancel_tasks]),
+    /// which also aborts any other queries running on that context.
+    /// A query with a deadline should therefore use a dedicated [Context].
+    fn deadline(self, timeout: Duration) -> Self {
+        self.base().timeout.set(Some(timeout));
+        self
+    }
+
     fn build(self) -> Self::Query;
 }
 
 pub struct BuilderBase {
     query: QueryBase,
+    timeout: Cell<Option<Duration>>,
+    /// The part of the query condition which TileDB applies.
+    pushdown: Cell<Option<QueryConditionExpr>>,
+    /// The part of the query condition which is evaluated on the results
+    /// of each step.
+    residual: Cell<Option<QueryConditionExpr>>,
 }
 
 impl ContextBound for BuilderBase {
@@ -268,6 +499,34 @@ impl BuilderBase {
     pub fn array(&self) -> &Array {
         &self.query.array
     }
+
+    /// Returns an error if `residual`, a condition which TileDB cannot
+    /// apply, cannot be evaluated on the results of this query.
+    fn check_residual(
+        &self,
+        residual: &QueryConditionExpr,
+    ) -> TileDBResult<()> {
+        let c_query = **self.cquery();
+        let mut c_query_type: ffi::tiledb_query_type_t = out_ptr!();
+        self.capi_call(|ctx| unsafe {
+            ffi::tiledb_query_get_type(ctx, c_query, &mut c_query_type)
+        })?;
+
+        let schema = self.array().schema()?;
+        if c_query_type != ffi::tiledb_query_type_t_TILEDB_READ
+            || schema.array_type()? != ArrayType::Sparse
+        {
+            return Err(Error::InvalidArgument(anyhow!(
+                "Query condition '{}' cannot be applied by TileDB, \
+                 and can only be evaluated by reads of sparse arrays",
+                residual
+            )));
+        }
+        for field in residual.fields() {
+            schema.field(field)?;
+        }
+        Ok(())
+    }
 }
 
 impl QueryBuilder for BuilderBase {
@@ -278,7 +537,10 @@ impl QueryBuilder for BuilderBase {
     }
 
     fn build(self) -> Self::Query {
-        self.query
+        let mut query = self.query;
+        query.deadline = self.timeout.get().map(|t| Instant::now() + t);
+        query.residual = self.residual.take().map(Residual::new);
+        query
     }
 }
 
@@ -295,7 +557,12 @@ impl BuilderBase {
             query: QueryBase {
                 array,
                 raw: RawQuery::Owned(c_query),
+                deadline: None,
+                residual: None,
             },
+            timeout: Cell::new(None),
+            pushdown: Cell::new(None),
+            residual: Cell::new(None),
         })
     }
 }
@@ -185,6 +185,7 @@ pub struct AggregateQuery<T, Q> {
     data: T,
     data_size: u64,
     data_validity: Option<u8>,
+    data_validity_size: u64,
 }
 
 impl<T, B> QueryBuilder for AggregateBuilder<T, B>
@@ -205,6 +206,7 @@ where
             data: T::default(),
             data_size: mem::size_of::<T>() as u64,
             data_validity: None,
+            data_validity_size: mem::size_of::<u8>() as u64,
         }
     }
 }
@@ -233,9 +235,14 @@ where
     type Intermediate = ();
     type Final = (Option<T>, Q::Final);
 
-    fn step(
-        &mut self,
-    ) -> TileDBResult<ReadStepOutput<Self::Intermediate, Self::Final>> {
+    fn prepare_step(&mut self) -> TileDBResult<()> {
+        if self.base().has_residual() {
+            return Err(TileDBError::InvalidArgument(anyhow!(
+                "Aggregates cannot be computed with a query condition \
+                 which TileDB cannot apply"
+            )));
+        }
+
         // Register the data buffer (set data buffer)
         let context = self.base().context();
         let location_ptr = &mut self.data as *mut T;
@@ -265,7 +272,7 @@ where
 
                 let c_validity =
                     self.data_validity.as_mut().unwrap() as *mut u8;
-                let mut c_validity_size: u64 = std::mem::size_of::<u8>() as u64;
+                let c_validity_size = &mut self.data_validity_size as *mut u64;
 
                 context.capi_call(|ctx| unsafe {
                     ffi::tiledb_query_set_validity_buffer(
@@ -273,13 +280,19 @@ where
                         c_query,
                         agg_c_ptr,
                         c_validity,
-                        &mut c_validity_size as *mut u64,
+                        c_validity_size,
                     )
                 })?;
             }
         }
 
-        let base_result = self.base.step()?;
+        self.base.prepare_step()
+    }
+
+    fn complete_step(
+        &mut self,
+    ) -> TileDBResult<ReadStepOutput<Self::Intermediate, Self::Final>> {
+        let base_result = self.base.complete_step()?;
 
         // There are no intermediate results for aggregates since the buffer size should be one
         // element (and therefore, no space constraints).
@@ -0,0 +1,538 @@
+//! Reads query results as a stream of arrow `RecordBatch`es.
+
+use std::sync::Arc;
+
+use anyhow::anyhow;
+use arrow::array::Array as ArrowArray;
+use arrow::datatypes::SchemaRef;
+use arrow::error::ArrowError;
+use arrow::ffi::FFI_ArrowSchema;
+use arrow::ffi_stream::FFI_ArrowArrayStream;
+use arrow::record_batch::{RecordBatch, RecordBatchReader};
+
+use crate::array::Array;
+use crate::context::{Context, ContextBound};
+use crate::error::Error;
+use crate::physical_type_go;
+use crate::query::condition::QueryConditionExpr;
+use crate::query::read::output::TypedRawReadOutput;
+use crate::query::read::{
+    CallbackVarArgReadBuilder, CallbackVarArgReadQuery, FieldMetadata,
+    ManagedBuffer, RawReadHandle, ReadCallbackVarArg, ReadQuery,
+    ReadQueryBuilder, ReadStepOutput, TypedReadHandle,
+};
+use crate::query::{BuilderBase, Query, QueryBase, QueryBuilder};
+use crate::Result as TileDBResult;
+
+/// Assembles the raw results of each step of a query into a [RecordBatch].
+#[derive(Clone)]
+pub struct RecordBatchCallback {
+    schema: SchemaRef,
+}
+
+impl RecordBatchCallback {
+    fn record_batch(
+        &self,
+        args: Vec<TypedRawReadOutput>,
+    ) -> Result<RecordBatch, anyhow::Error> {
+        let columns = args
+            .into_iter()
+            .map(Arc::<dyn ArrowArray>::try_from)
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(RecordBatch::try_new(Arc::clone(&self.schema), columns)?)
+    }
+}
+
+impl ReadCallbackVarArg for RecordBatchCallback {
+    type Intermediate = RecordBatch;
+    type Final = RecordBatch;
+    type Error = anyhow::Error;
+
+    fn intermediate_result(
+        &mut self,
+        args: Vec<TypedRawReadOutput>,
+    ) -> Result<Self::Intermediate, Self::Error> {
+        self.record_batch(args)
+    }
+
+    fn final_result(
+        self,
+        args: Vec<TypedRawReadOutput>,
+    ) -> Result<Self::Final, Self::Error> {
+        self.record_batch(args)
+    }
+
+    fn cleared(&self) -> Option<Self> {
+        Some(self.clone())
+    }
+}
+
+/// Adapts a query to read a projection of the fields of an array
+/// into arrow [RecordBatch]es.
+///
+/// The arrow schema of the results is the projection of
+/// [Schema::to_arrow](crate::array::Schema::to_arrow) onto the requested
+/// fields. Buffers for each field are allocated and managed by the query.
+pub struct ArrowReadBuilder<'data, B> {
+    schema: SchemaRef,
+    base: CallbackVarArgReadBuilder<'data, RecordBatchCallback, B>,
+}
+
+impl<'data, B> ArrowReadBuilder<'data, B>
+where
+    B: ReadQueryBuilder<'data>,
+{
+    /// Adapts `base` to read the fields named in `projection`,
+    /// in that order.
+    pub fn new<I, S>(base: B, projection: I) -> TileDBResult<Self>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        Self::with_memory_limit(base, projection, None)
+    }
+
+    /// Adapts `base` to read all of the fields of the array:
+    /// the dimensions, followed by the attributes.
+    pub fn all_fields(base: B) -> TileDBResult<Self> {
+        let schema = base.base().array().schema()?;
+        let names = schema
+            .fields()?
+            .map(|f| f?.name())
+            .collect::<TileDBResult<Vec<String>>>()?;
+        Self::new(base, names)
+    }
+
+    /// Adapts `base` to read the fields named in `projection`, in that
+    /// order, using at most about `memory_limit` bytes for the buffers
+    /// of each field. Each step of the query produces one batch,
+    /// so this also bounds the size of each batch.
+    pub fn with_memory_limit<I, S>(
+        base: B,
+        projection: I,
+        memory_limit: Option<usize>,
+    ) -> TileDBResult<Self>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let tiledb_schema = base.base().array().schema()?;
+        let arrow_schema = tiledb_schema.to_arrow()?.ok().ok_or_else(|| {
+            Error::InvalidArgument(anyhow!(
+                "Schema cannot be represented in arrow"
+            ))
+        })?;
+
+        let mut indices = vec![];
+        let mut handles = vec![];
+        for name in projection {
+            let name = name.as_ref();
+            let index = arrow_schema.index_of(name).map_err(|e| {
+                Error::InvalidArgument(
+                    anyhow!("Cannot read field '{}' as arrow", name).context(e),
+                )
+            })?;
+            indices.push(index);
+
+            let field = tiledb_schema.field(name)?;
+            let handle = physical_type_go!(field.datatype()?, DT, {
+                let managed: ManagedBuffer<DT> = ManagedBuffer::new(
+                    field.query_scratch_allocator(memory_limit)?,
+                );
+                let metadata = FieldMetadata::try_from(&field)?;
+                TypedReadHandle::from(RawReadHandle::managed(metadata, managed))
+            });
+            handles.push(handle);
+        }
+
+        let schema = Arc::new(arrow_schema.project(&indices).map_err(|e| {
+            Error::InvalidArgument(anyhow!("Invalid projection").context(e))
+        })?);
+
+        Ok(ArrowReadBuilder {
+            schema: Arc::clone(&schema),
+            base: base.register_callback_var(
+                handles,
+                RecordBatchCallback { schema },
+            )?,
+        })
+    }
+
+    /// Adds a condition which each record produced by the query
+    /// must satisfy, using [QueryBuilder::query_condition].
+    ///
+    /// The condition may refer to fields which are not part of the
+    /// projection. The part of the condition which TileDB cannot apply,
+    /// such as a comparison between fields, is evaluated on the results
+    /// of each step, so a batch may have fewer records than the step
+    /// read, possibly none.
+    pub fn filter(self, condition: QueryConditionExpr) -> TileDBResult<Self> {
+        self.query_condition(condition)
+    }
+
+    /// Returns the arrow schema of the batches which the query produces.
+    pub fn schema(&self) -> SchemaRef {
+        Arc::clone(&self.schema)
+    }
+}
+
+impl<B> ContextBound for ArrowReadBuilder<'_, B>
+where
+    B: QueryBuilder,
+{
+    fn context(&self) -> Context {
+        self.base.base().context()
+    }
+}
+
+impl<'data, B> QueryBuilder for ArrowReadBuilder<'data, B>
+where
+    B: QueryBuilder,
+{
+    type Query = ArrowReadQuery<'data, B::Query>;
+
+    fn base(&self) -> &BuilderBase {
+        self.base.base()
+    }
+
+    fn build(self) -> Self::Query {
+        ArrowReadQuery {
+            schema: self.schema,
+            base: self.base.build(),
+            finished: false,
+        }
+    }
+}
+
+impl<'data, B> ReadQueryBuilder<'data> for ArrowReadBuilder<'data, B> where
+    B: QueryBuilder
+{
+}
+
+/// A query which produces an arrow [RecordBatch] from each step.
+///
+/// As a [RecordBatchReader], the query yields the batch from each step
+/// until the query completes.
+pub struct ArrowReadQuery<'data, Q> {
+    schema: SchemaRef,
+    base: CallbackVarArgReadQuery<'data, RecordBatchCallback, Q>,
+    finished: bool,
+}
+
+impl<Q> ContextBound for ArrowReadQuery<'_, Q>
+where
+    Q: Query,
+{
+    fn context(&self) -> Context {
+        self.base.base().context()
+    }
+}
+
+impl<Q> Query for ArrowReadQuery<'_, Q>
+where
+    Q: Query,
+{
+    fn base(&self) -> &QueryBase {
+        self.base.base()
+    }
+
+    fn finalize(self) -> TileDBResult<Array> {
+        self.base.finalize()
+    }
+}
+
+impl<Q> ReadQuery for ArrowReadQuery<'_, Q>
+where
+    Q: ReadQuery,
+{
+    type Intermediate = (RecordBatch, Q::Intermediate);
+    type Final = (RecordBatch, Q::Final);
+
+    fn prepare_step(&mut self) -> TileDBResult<()> {
+        self.base.prepare_step()
+    }
+
+    fn complete_step(
+        &mut self,
+    ) -> TileDBResult<ReadStepOutput<Self::Intermediate, Self::Final>> {
+        self.base.complete_step()
+    }
+}
+
+impl<Q> Iterator for ArrowReadQuery<'_, Q>
+where
+    Q: ReadQuery,
+{
+    type Item = Result<RecordBatch, ArrowError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.finished {
+            return None;
+        }
+        loop {
+            match self.step() {
+                Ok(ReadStepOutput::NotEnoughSpace) => continue,
+                Ok(ReadStepOutput::Intermediate((batch, _))) => {
+                    return Some(Ok(batch))
+                }
+                Ok(ReadStepOutput::Final((batch, _))) => {
+                    self.finished = true;
+                    return Some(Ok(batch));
+                }
+                Err(e) => {
+                    self.finished = true;
+                    return Some(Err(ArrowError::ExternalError(Box::new(e))));
+                }
+            }
+        }
+    }
+}
+
+impl<Q> RecordBatchReader for ArrowReadQuery<'_, Q>
+where
+    Q: ReadQuery,
+{
+    fn schema(&self) -> SchemaRef {
+        Arc::clone(&self.schema)
+    }
+}
+
+/// Adapts an [ArrowReadQuery] so that it can be exported
+/// as an [FFI_ArrowArrayStream], whose consumer may use it from any thread.
+struct StreamReader(ArrowReadQuery<'static, QueryBase>);
+
+// SAFETY: `ArrowReadQuery` is not `Send` because its read handles refer to
+// their buffers through `RefCell`s. Over a plain `QueryBase`, the only read
+// handles are the ones registered by `ArrowReadBuilder`, whose buffers are
+// owned by the query itself, so they move to another thread together.
+unsafe impl Send for StreamReader {}
+
+impl Iterator for StreamReader {
+    type Item = Result<RecordBatch, ArrowError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next()
+    }
+}
+
+impl RecordBatchReader for StreamReader {
+    fn schema(&self) -> SchemaRef {
+        Arc::clone(&self.0.schema)
+    }
+}
+
+impl ArrowReadQuery<'static, QueryBase> {
+    /// Returns the schema of the batches which this query produces,
+    /// using the Arrow C data interface.
+    pub fn ffi_schema(&self) -> TileDBResult<FFI_ArrowSchema> {
+        FFI_ArrowSchema::try_from(self.schema.as_ref())
+            .map_err(|e| Error::Other(e.to_string()))
+    }
+
+    /// Exports this query using the Arrow C stream interface,
+    /// so that other libraries can consume its batches without copying.
+    ///
+    /// Each call to `get_next` on the stream runs one step of the query.
+    /// The query is dropped when the consumer releases the stream.
+    pub fn into_ffi_stream(self) -> FFI_ArrowArrayStream {
+        FFI_ArrowArrayStream::new(Box::new(StreamReader(self)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use arrow::array::{BooleanArray, Int32Array, LargeBinaryArray};
+    use arrow::compute::{concat_batches, filter_record_batch};
+
+    use super::*;
+    use crate::tests::prelude::*;
+
+    /// Returns a sparse array with a var-sized nullable attribute,
+    /// and a batch which was written to it.
+    fn write_array(name: &str) -> anyhow::Result<(TestArray, RecordBatch)> {
+        let mut schema =
+            crate::tests::examples::quickstart::Builder::new(ArrayType::Sparse)
+                .build();
+        schema.attributes.push(AttributeData {
+            name: "b".to_owned(),
+            datatype: Datatype::Blob,
+            nullability: Some(true),
+            cell_val_num: Some(CellValNum::Var),
+            ..Default::default()
+        });
+
+        let mut array = TestArray::new(name, std::rc::Rc::new(schema))?;
+        let arrow_schema =
+            Arc::new(array.for_read()?.schema()?.to_arrow()?.ok().unwrap());
+
+        let rows = Int32Array::from(vec![1, 1, 2, 3, 3, 4]);
+        let cols = Int32Array::from(vec![1, 4, 2, 1, 3, 4]);
+        let a = Int32Array::from(vec![10, 11, 12, 13, 14, 15]);
+        let b = LargeBinaryArray::from_opt_vec(vec![
+            Some("foo".as_bytes()),
+            None,
+            Some("".as_bytes()),
+            Some("tiledb".as_bytes()),
+            None,
+            Some("arrow".as_bytes()),
+        ]);
+        let batch = RecordBatch::try_new(
+            arrow_schema,
+            vec![Arc::new(rows), Arc::new(cols), Arc::new(a), Arc::new(b)],
+        )?;
+
+        let w = WriteBuilder::new(array.for_write()?)?
+            .records(&batch)?
+            .build();
+        w.submit()?;
+        w.finalize()?;
+
+        Ok((array, batch))
+    }
+
+    #[test]
+    fn read_all_fields() -> anyhow::Result<()> {
+        let (mut array, expect) = write_array("arrow_read_all_fields")?;
+
+        let reader = ArrowReadBuilder::all_fields(
+            ReadBuilder::new(array.for_read()?)?
+                .layout(QueryLayout::RowMajor)?,
+        )?
+        .build();
+        assert_eq!(expect.schema(), RecordBatchReader::schema(&reader));
+
+        let batches = reader.collect::<Result<Vec<_>, _>>()?;
+        assert_eq!(1, batches.len());
+        assert_eq!(expect, batches[0]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn read_projection_in_steps() -> anyhow::Result<()> {
+        let (mut array, expect) = write_array("arrow_read_projection")?;
+        let expect = expect.project(&[3, 2])?;
+
+        let b = ArrowReadBuilder::with_memory_limit(
+            ReadBuilder::new(array.for_read()?)?
+                .layout(QueryLayout::RowMajor)?,
+            ["b", "a"],
+            Some(128),
+        )?;
+        assert_eq!(expect.schema(), b.schema());
+
+        let reader = b.build();
+        let batches = reader.collect::<Result<Vec<_>, _>>()?;
+        assert!(batches.len() > 1);
+        assert_eq!(expect, concat_batches(&expect.schema(), &batches)?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn read_invalid_field() -> anyhow::Result<()> {
+        let (mut array, _) = write_array("arrow_read_invalid_field")?;
+        assert!(matches!(
+            ArrowReadBuilder::new(ReadBuilder::new(array.for_read()?)?, ["c"]),
+            Err(Error::InvalidArgument(_))
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn read_filter() -> anyhow::Result<()> {
+        use crate::query::condition::Operand;
+
+        let (mut array, expect) = write_array("arrow_read_filter")?;
+        let expect = filter_record_batch(
+            &expect,
+            &BooleanArray::from(vec![false, true, true, false, true, true]),
+        )?
+        .project(&[2, 3])?;
+
+        // `rows <= cols` is evaluated in memory, `a >= 11` by TileDB
+        let condition = Operand::field("rows").le(Operand::field("cols"))
+            & QueryConditionExpr::field("a").ge(11);
+
+        let b = ArrowReadBuilder::with_memory_limit(
+            ReadBuilder::new(array.for_read()?)?
+                .layout(QueryLayout::RowMajor)?,
+            ["a", "b"],
+            Some(128),
+        )?
+        .filter(condition)?;
+        assert_eq!(expect.schema(), b.schema());
+
+        let reader = b.build();
+        let batches = reader.collect::<Result<Vec<_>, _>>()?;
+        assert_eq!(expect, concat_batches(&expect.schema(), &batches)?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn read_filter_repeated() -> anyhow::Result<()> {
+        use crate::query::condition::Operand;
+
+        let (mut array, expect) = write_array("arrow_read_filter_repeated")?;
+        let expect = filter_record_batch(
+            &expect,
+            &BooleanArray::from(vec![false, true, false, false, false, false]),
+        )?
+        .project(&[2, 3])?;
+
+        // each record must satisfy all of the conditions,
+        // including steps where none of them do
+        let b = ArrowReadBuilder::with_memory_limit(
+            ReadBuilder::new(array.for_read()?)?
+                .layout(QueryLayout::RowMajor)?,
+            ["a", "b"],
+            Some(128),
+        )?
+        .filter(QueryConditionExpr::field("a").ge(10))?
+        .filter(Operand::field("rows").lt(Operand::field("cols")))?
+        .filter(QueryConditionExpr::field("a").lt(15))?;
+
+        let reader = b.build();
+        let batches = reader.collect::<Result<Vec<_>, _>>()?;
+        assert_eq!(expect, concat_batches(&expect.schema(), &batches)?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn ffi_stream() -> anyhow::Result<()> {
+        let (mut array, expect) = write_array("arrow_read_ffi_stream")?;
+
+        let query = ArrowReadBuilder::with_memory_limit(
+            ReadBuilder::new(array.for_read()?)?
+                .layout(QueryLayout::RowMajor)?,
+            ["rows", "cols", "a", "b"],
+            Some(128),
+        )?
+        .build();
+
+        let ffi_schema = query.ffi_schema()?;
+        assert_eq!(
+            *expect.schema(),
+            arrow::datatypes::Schema::try_from(&ffi_schema)?
+        );
+
+        let stream = query.into_ffi_stream();
+        let consumer = std::thread::spawn(move || {
+            let reader =
+                arrow::ffi_stream::ArrowArrayStreamReader::try_new(stream)?;
+            assert_eq!(expect.schema(), reader.schema());
+            let batches = reader.collect::<Result<Vec<_>, _>>()?;
+            anyhow::Ok((expect, batches))
+        })
+        .join()
+        .expect("Stream consumer panicked");
+        let (expect, batches) = consumer?;
+
+        assert!(batches.len() > 1);
+        assert_eq!(expect, concat_batches(&expect.schema(), &batches)?);
+
+        Ok(())
+    }
+}
@@ -684,7 +684,7 @@ macro_rules! query_read_callback {
             type Intermediate = (T::Intermediate, Q::Intermediate);
             type Final = (T::Final, Q::Final);
 
-            fn step(&mut self) -> TileDBResult<ReadStepOutput<Self::Intermediate, Self::Final>> {
+            fn prepare_step(&mut self) -> TileDBResult<()> {
                 /*
                  * First we must attach all the buffers
                  */
@@ -696,14 +696,18 @@ macro_rules! query_read_callback {
                     )+
                 }
 
-                let base_result = self.base.step()?;
+                self.base.prepare_step()
+            }
+
+            fn complete_step(&mut self) -> TileDBResult<ReadStepOutput<Self::Intermediate, Self::Final>> {
+                let base_result = self.base.complete_step()?;
 
                 paste! {
                     $(
                         let [< ncells_ $U:snake >] = {
                             let ncells = self.[< arg_ $U:snake >].last_read_ncells();
                             if !base_result.is_final() {
-                                if ncells == 0 {
+                                if ncells == 0 && !self.base().filtered_all() {
                                     self.realloc_managed_buffers();
                                     return Ok(ReadStepOutput::NotEnoughSpace)
                                 }
@@ -988,10 +992,14 @@ where
     type Intermediate = (T::Intermediate, Q::Intermediate);
     type Final = (T::Final, Q::Final);
 
-    fn step(
+    fn prepare_step(&mut self) -> TileDBResult<()> {
+        self.base.prepare_step()
+    }
+
+    fn complete_step(
         &mut self,
     ) -> TileDBResult<ReadStepOutput<Self::Intermediate, Self::Final>> {
-        let base_result = self.base.step()?;
+        let base_result = self.base.complete_step()?;
 
         match base_result {
             ReadStepOutput::NotEnoughSpace => {
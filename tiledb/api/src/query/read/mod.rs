@@ -7,17 +7,21 @@ use paste::paste;
 
 use crate::config::Config;
 use crate::query::buffer::{BufferMut, QueryBuffersMut};
-use crate::query::read::output::ScratchAllocator;
+use crate::query::read::output::{EstimatedScratchAllocator, ScratchAllocator};
 use crate::Result as TileDBResult;
 
 pub mod aggregate;
+#[cfg(feature = "arrow")]
+pub mod arrow;
 mod callback;
 pub mod output;
+pub mod partitioned;
 mod raw;
 mod typed;
 
 pub use aggregate::*;
 pub use callback::*;
+pub use partitioned::PartitionedRead;
 pub use raw::*;
 pub use typed::*;
 
@@ -171,6 +175,11 @@ impl<U> ReadStepOutput<U, U> {
 pub enum ScratchStrategy<'data, C> {
     #[default]
     AttributeDefault,
+    /// Sizes scratch space using the estimated result size of the field
+    /// (see [`ReadQueryBuilder::est_result_size`]).
+    /// The estimate is taken when the field is registered, so the subarray
+    /// and query condition should be set on the builder before that.
+    Estimated,
     RawBuffers(&'data RefCell<QueryBuffersMut<'data, C>>),
     CustomAllocator(Box<dyn ScratchAllocator<C> + 'data>),
 }
@@ -188,10 +197,37 @@ pub trait ReadQuery: Query {
     type Intermediate;
     type Final;
 
+    /// Prepare the query to be submitted, e.g. by attaching scratch space.
+    ///
+    /// This is the first half of [`ReadQuery::step`]. Adapters must call
+    /// `prepare_step` on the query which they wrap.
+    fn prepare_step(&mut self) -> TileDBResult<()>;
+
+    /// Process the results of the submission which followed
+    /// [`ReadQuery::prepare_step`].
+    ///
+    /// This is the second half of [`ReadQuery::step`]. Adapters must call
+    /// `complete_step` on the query which they wrap.
+    fn complete_step(
+        &mut self,
+    ) -> TileDBResult<ReadStepOutput<Self::Intermediate, Self::Final>>;
+
     /// Run the query until it has filled up its scratch space.
     fn step(
         &mut self,
-    ) -> TileDBResult<ReadStepOutput<Self::Intermediate, Self::Final>>;
+    ) -> TileDBResult<ReadStepOutput<Self::Intermediate, Self::Final>> {
+        self.prepare_step()?;
+        self.base().do_submit()?;
+        self.complete_step()
+    }
+
+    /// Returns the estimated size of the results of this query for `field`.
+    fn est_result_size<S>(&self, field: S) -> TileDBResult<ResultSizeEstimate>
+    where
+        S: AsRef<str>,
+    {
+        self.base().do_est_result_size(field.as_ref())
+    }
 
     /// Run the query to completion.
     /// Query adapters may interleave their operations
@@ -204,6 +240,37 @@ pub trait ReadQuery: Query {
         })
     }
 
+    /// Returns a future which runs a single step of the query
+    /// on a background thread.
+    ///
+    /// The future owns the query and gives it back alongside the
+    /// output of the step.
+    ///
+    /// The query must be `Send`. Queries which read into buffers through
+    /// a [RawReadHandle] are not, since the buffers are shared with the
+    /// application or with its allocator.
+    #[cfg(feature = "async")]
+    fn step_async(self) -> crate::query::StepFuture<Self>
+    where
+        Self: Sized + Send + 'static,
+    {
+        crate::query::StepFuture::new(self)
+    }
+
+    /// Returns a future which runs the query to completion,
+    /// submitting each step on a background thread.
+    ///
+    /// The future owns the query and gives it back alongside the
+    /// final result. As with [ReadQuery::step_async], the query must
+    /// be `Send`.
+    #[cfg(feature = "async")]
+    fn execute_async(self) -> crate::query::ExecuteFuture<Self>
+    where
+        Self: Sized + Send + 'static,
+    {
+        crate::query::ExecuteFuture::new(self)
+    }
+
     /// Convert this query into an iterator which yields an item
     /// for each step of the query.
     fn into_iter(self) -> ReadQueryIterator<Self::Intermediate, Self::Final>
@@ -246,6 +313,12 @@ macro_rules! fn_register_callback {
                                 let managed = ManagedBuffer::from(alloc);
                                 RawReadHandle::managed(metadata, managed)
                             },
+                            ScratchStrategy::Estimated => {
+                                let estimate = self.est_result_size([< field_ $U:snake >])?;
+                                let alloc : Box<dyn ScratchAllocator<<T as $Callback>::$U> + 'data> = Box::new(EstimatedScratchAllocator::new(field.cell_val_num()?, estimate, None));
+                                let managed = ManagedBuffer::from(alloc);
+                                RawReadHandle::managed(metadata, managed)
+                            },
                             ScratchStrategy::RawBuffers(qb) => {
                                 RawReadHandle::new(metadata, qb)
                             },
@@ -275,6 +348,15 @@ macro_rules! fn_register_callback {
 /// Provides methods for flexibly adapting requested attributes into raw results,
 /// callbacks, or strongly-typed objects.
 pub trait ReadQueryBuilder<'data>: QueryBuilder {
+    /// Returns the estimated size of the results of the query for `field`,
+    /// given the subarray and query condition which have been set so far.
+    fn est_result_size<S>(&self, field: S) -> TileDBResult<ResultSizeEstimate>
+    where
+        S: AsRef<str>,
+    {
+        self.base().query.do_est_result_size(field.as_ref())
+    }
+
     /// Register a raw memory location to read query results into.
     fn register_raw<S, C>(
         self,
@@ -456,3 +538,59 @@ impl<I, F> Iterator for ReadQueryIterator<I, F> {
 }
 
 impl<I, F> std::iter::FusedIterator for ReadQueryIterator<I, F> {}
+
+#[cfg(test)]
+mod tests {
+    use std::rc::Rc;
+
+    use super::*;
+    use crate::tests::prelude::*;
+
+    #[test]
+    fn est_result_size() -> TileDBResult<()> {
+        let mut array = TestArray::new(
+            "est_result_size",
+            Rc::new(
+                crate::tests::examples::quickstart::Builder::new(
+                    ArrayType::Sparse,
+                )
+                .build(),
+            ),
+        )?;
+
+        let rows = vec![1, 1, 2, 2, 3, 3, 4, 4];
+        let cols = vec![1, 2, 1, 2, 1, 2, 1, 2];
+        let atts = vec![10, 11, 12, 13, 14, 15, 16, 17];
+        {
+            let w = WriteBuilder::new(array.for_write()?)?
+                .data_typed("rows", &rows)?
+                .data_typed("cols", &cols)?
+                .data_typed("a", &atts)?
+                .build();
+            w.submit()?;
+            w.finalize()?;
+        }
+
+        let b = ReadBuilder::new(array.for_read()?)?;
+        let ResultSizeEstimate {
+            data,
+            offsets,
+            validity,
+        } = b.est_result_size("a")?;
+        assert!(data > 0);
+        assert_eq!(None, offsets);
+        assert_eq!(None, validity);
+
+        let mut q = b
+            .register_constructor::<_, Vec<i32>>(
+                "a",
+                ScratchStrategy::Estimated,
+            )?
+            .build();
+        let (mut a, _) = q.execute()?;
+        a.sort();
+        assert_eq!(atts, a);
+
+        Ok(())
+    }
+}
@@ -8,7 +8,7 @@ use crate::array::CellValNum;
 use crate::datatype::PhysicalType;
 use crate::error::Error;
 use crate::query::buffer::*;
-use crate::query::CellValue;
+use crate::query::{CellValue, ResultSizeEstimate};
 use crate::Result as TileDBResult;
 use crate::{typed_query_buffers_go, Datatype};
 
@@ -554,33 +554,125 @@ where
     }
 
     fn realloc(&self, old: ScratchSpace<C>) -> ScratchSpace<C> {
-        let ScratchSpace(old_data, old_structure, old_validity) = old;
+        grow_scratch_space(old)
+    }
+}
 
-        let new_data = {
-            let mut v = old_data.to_vec();
-            v.resize(2 * v.len(), Default::default());
-            v.into_boxed_slice()
+/// Allocator for a schema field which sizes its scratch space
+/// using the estimated result size of a query.
+pub struct EstimatedScratchAllocator {
+    pub cell_val_num: CellValNum,
+    pub estimate: ResultSizeEstimate,
+}
+
+impl EstimatedScratchAllocator {
+    /// Returns an allocator for a field with `cell_val_num` which uses
+    /// `estimate` to size its scratch space.
+    /// The size of each buffer is capped at `memory_limit` bytes,
+    /// or [`FieldScratchAllocator::DEFAULT_MEMORY_LIMIT`] if `None`.
+    pub fn new(
+        cell_val_num: CellValNum,
+        estimate: ResultSizeEstimate,
+        memory_limit: Option<usize>,
+    ) -> Self {
+        let memory_limit = memory_limit
+            .unwrap_or(FieldScratchAllocator::DEFAULT_MEMORY_LIMIT)
+            as u64;
+        EstimatedScratchAllocator {
+            cell_val_num,
+            estimate: ResultSizeEstimate {
+                data: std::cmp::min(estimate.data, memory_limit),
+                offsets: estimate
+                    .offsets
+                    .map(|o| std::cmp::min(o, memory_limit)),
+                validity: estimate
+                    .validity
+                    .map(|v| std::cmp::min(v, memory_limit)),
+            },
+        }
+    }
+}
+
+impl<C> ScratchAllocator<C> for EstimatedScratchAllocator
+where
+    C: PhysicalType,
+{
+    fn alloc(&self) -> ScratchSpace<C> {
+        /*
+         * The estimate may be zero, e.g. for an empty array,
+         * so always allocate space for at least one cell.
+         */
+        let values_per_cell = match self.cell_val_num {
+            CellValNum::Fixed(nz) => nz.get() as usize,
+            CellValNum::Var => 1,
         };
+        let data_capacity = std::cmp::max(
+            self.estimate.data as usize / std::mem::size_of::<C>(),
+            values_per_cell,
+        );
 
-        let new_structure = match old_structure {
-            ScratchCellStructure::Fixed(nz) => ScratchCellStructure::Fixed(nz),
-            ScratchCellStructure::Var(old_offsets) => {
-                let mut v = old_offsets.to_vec();
-                v.resize(2 * v.len(), Default::default());
-                ScratchCellStructure::Var(v.into_boxed_slice())
+        let cell_structure = match self.cell_val_num {
+            CellValNum::Fixed(nz) => ScratchCellStructure::Fixed(nz),
+            CellValNum::Var => {
+                /* one extra offset for the end of the last cell */
+                let offsets_capacity = self.estimate.offsets.unwrap_or(0)
+                    as usize
+                    / std::mem::size_of::<u64>()
+                    + 1;
+                ScratchCellStructure::Var(
+                    vec![0u64; std::cmp::max(offsets_capacity, 2)]
+                        .into_boxed_slice(),
+                )
             }
         };
 
-        let new_validity = old_validity.map(|old_validity| {
-            let mut v = old_validity.to_vec();
-            v.resize(2 * v.len(), Default::default());
-            v.into_boxed_slice()
+        let validity = self.estimate.validity.map(|v| {
+            vec![0u8; std::cmp::max(v as usize, 1)].into_boxed_slice()
         });
 
-        ScratchSpace(new_data, new_structure, new_validity)
+        ScratchSpace(
+            vec![C::default(); data_capacity].into_boxed_slice(),
+            cell_structure,
+            validity,
+        )
+    }
+
+    fn realloc(&self, old: ScratchSpace<C>) -> ScratchSpace<C> {
+        grow_scratch_space(old)
     }
 }
 
+/// Returns scratch space with double the capacity of `old`.
+fn grow_scratch_space<C>(old: ScratchSpace<C>) -> ScratchSpace<C>
+where
+    C: PhysicalType,
+{
+    let ScratchSpace(old_data, old_structure, old_validity) = old;
+
+    let new_data = {
+        let mut v = old_data.to_vec();
+        v.resize(2 * v.len(), Default::default());
+        v.into_boxed_slice()
+    };
+
+    let new_structure = match old_structure {
+        ScratchCellStructure::Fixed(nz) => ScratchCellStructure::Fixed(nz),
+        ScratchCellStructure::Var(old_offsets) => {
+            let mut v = old_offsets.to_vec();
+            v.resize(2 * v.len(), Default::default());
+            ScratchCellStructure::Var(v.into_boxed_slice())
+        }
+    };
+
+    let new_validity = old_validity.map(|old_validity| {
+        let mut v = old_validity.to_vec();
+        v.resize(2 * v.len(), Default::default());
+        v.into_boxed_slice()
+    });
+
+    ScratchSpace(new_data, new_structure, new_validity)
+}
+
 pub struct CellStructureSingleIterator<'data, C> {
     ncells: usize,
     index: usize,
@@ -1026,4 +1118,54 @@ mod tests {
         );
         assert!(matches!(try_new, Err(Error::InvalidArgument(_))));
     }
+
+    #[test]
+    fn estimated_scratch_allocator() {
+        // var-sized nullable field
+        let alloc = EstimatedScratchAllocator::new(
+            CellValNum::Var,
+            ResultSizeEstimate {
+                data: 1024,
+                offsets: Some(8 * 16),
+                validity: Some(16),
+            },
+            None,
+        );
+        let ScratchSpace(data, cell_structure, validity) =
+            <EstimatedScratchAllocator as ScratchAllocator<u32>>::alloc(&alloc);
+        assert_eq!(256, data.len());
+        let ScratchCellStructure::Var(offsets) = cell_structure else {
+            unreachable!()
+        };
+        assert_eq!(17, offsets.len());
+        assert_eq!(Some(16), validity.map(|v| v.len()));
+
+        // empty estimate still has room for one cell
+        let alloc = EstimatedScratchAllocator::new(
+            CellValNum::try_from(4).unwrap(),
+            ResultSizeEstimate::default(),
+            None,
+        );
+        let ScratchSpace(data, cell_structure, validity) =
+            <EstimatedScratchAllocator as ScratchAllocator<u64>>::alloc(&alloc);
+        assert_eq!(4, data.len());
+        assert!(
+            matches!(cell_structure, ScratchCellStructure::Fixed(nz) if nz.get() == 4)
+        );
+        assert!(validity.is_none());
+
+        // estimates are capped by the memory limit
+        let alloc = EstimatedScratchAllocator::new(
+            CellValNum::single(),
+            ResultSizeEstimate {
+                data: 1 << 40,
+                offsets: None,
+                validity: None,
+            },
+            Some(4096),
+        );
+        let ScratchSpace(data, _, _) =
+            <EstimatedScratchAllocator as ScratchAllocator<u8>>::alloc(&alloc);
+        assert_eq!(4096, data.len());
+    }
 }
@@ -0,0 +1,506 @@
+//! Runs a read query over a large subarray as several smaller queries
+//! on worker threads.
+//!
+//! The subarray is split along its slowest-varying dimension at tile
+//! extent boundaries, so that each partition covers whole tiles (except
+//! possibly the first and last) and the results of the partitions can be
+//! concatenated to produce the results of the whole subarray in the
+//! requested layout.
+
+use anyhow::anyhow;
+use tiledb_common::{physical_type_go, single_value_range_go};
+
+use crate::array::{Array, ArrayPool, Schema};
+use crate::error::Error;
+use crate::key::LookupKey;
+#[cfg(feature = "arrow")]
+use crate::query::read::ReadStepOutput;
+use crate::query::read::{ReadBuilder, ReadQuery};
+#[cfg(feature = "arrow")]
+use crate::query::ArrowReadBuilder;
+use crate::query::{Query, QueryBuilder, QueryLayout};
+use crate::range::Range;
+use crate::Result as TileDBResult;
+
+/// Reads a subarray by running one query per partition of the subarray,
+/// each on its own thread.
+///
+/// ```ignore
+/// let pool = ArrayPool::new(&context, uri);
+/// let (a, _) = PartitionedRead::new(&pool)?
+///     .add_range("rows", &[1, 1024])?
+///     .parallelism(4)
+///     .execute_merged(
+///         |b| b.register_constructor::<_, Vec<i32>>("a", Default::default()),
+///         |(mut a, ()), (b, ())| {
+///             a.extend(b);
+///             (a, ())
+///         },
+///     )?
+///     .unwrap();
+/// ```
+pub struct PartitionedRead<'pool> {
+    pool: &'pool ArrayPool,
+    schema: Schema,
+    layout: QueryLayout,
+    parallelism: usize,
+    ranges: Vec<Option<Range>>,
+}
+
+impl<'pool> PartitionedRead<'pool> {
+    /// Prepares to read from the array of `pool`. Unless restricted using
+    /// [add_range](Self::add_range), the whole domain of each dimension
+    /// is read, in row-major order, using as many threads as are available
+    /// to the process.
+    pub fn new(pool: &'pool ArrayPool) -> TileDBResult<Self> {
+        let schema = pool.get()?.schema()?;
+        let ndim = schema.domain()?.num_dimensions()?;
+        let parallelism = std::thread::available_parallelism()
+            .map(|n| n.get())
+            .unwrap_or(1);
+
+        Ok(PartitionedRead {
+            pool,
+            schema,
+            layout: QueryLayout::RowMajor,
+            parallelism,
+            ranges: vec![None; ndim],
+        })
+    }
+
+    /// Sets the layout of the results of each partition.
+    /// The layout must be [QueryLayout::RowMajor] or
+    /// [QueryLayout::ColumnMajor].
+    pub fn layout(self, layout: QueryLayout) -> TileDBResult<Self> {
+        match layout {
+            QueryLayout::RowMajor | QueryLayout::ColumnMajor => {
+                Ok(PartitionedRead { layout, ..self })
+            }
+            _ => Err(Error::InvalidArgument(anyhow!(
+                "Partitioned reads require a row-major or column-major layout, found {:?}",
+                layout
+            ))),
+        }
+    }
+
+    /// Sets the maximum number of partitions, and thus threads,
+    /// which are used to read the subarray.
+    pub fn parallelism(self, parallelism: usize) -> Self {
+        PartitionedRead {
+            parallelism: parallelism.max(1),
+            ..self
+        }
+    }
+
+    /// Restricts the read to `range` on a dimension. Unlike a [Subarray],
+    /// there is at most one range per dimension; adding another range on
+    /// the same dimension replaces the previous one.
+    ///
+    /// [Subarray]: crate::query::Subarray
+    pub fn add_range<K, R>(mut self, key: K, range: R) -> TileDBResult<Self>
+    where
+        K: Into<LookupKey>,
+        R: Into<Range>,
+    {
+        let domain = self.schema.domain()?;
+        let idx = match key.into() {
+            LookupKey::Index(idx) => idx,
+            LookupKey::Name(name) => (0..self.ranges.len())
+                .map(|i| domain.dimension(i)?.name())
+                .position(|n| n.as_ref().is_ok_and(|n| *n == name))
+                .ok_or_else(|| {
+                    Error::InvalidArgument(anyhow!(
+                        "No such dimension: {}",
+                        name
+                    ))
+                })?,
+        };
+        if idx >= self.ranges.len() {
+            return Err(Error::InvalidIndex(idx));
+        }
+
+        let dim = domain.dimension(idx)?;
+        let range = range.into();
+        range
+            .check_dimension_compatibility(dim.datatype()?, dim.cell_val_num()?)
+            .map_err(|e| {
+                Error::InvalidArgument(
+                    anyhow!("Invalid range variant for dimension").context(e),
+                )
+            })?;
+
+        self.ranges[idx] = Some(range);
+        Ok(self)
+    }
+
+    /// Returns the ranges of each partition of the subarray, in the order
+    /// in which their results are laid out.
+    ///
+    /// The subarray is split on the first dimension for row-major reads,
+    /// or the last dimension for column-major reads. That dimension
+    /// must have an integral datatype.
+    pub fn partitions(&self) -> TileDBResult<Vec<Vec<Range>>> {
+        let domain = self.schema.domain()?;
+
+        let mut ranges = Vec::with_capacity(self.ranges.len());
+        for (i, range) in self.ranges.iter().enumerate() {
+            let range = if let Some(range) = range {
+                range.clone()
+            } else {
+                let dim = domain.dimension(i)?;
+                physical_type_go!(dim.datatype()?, DT, {
+                    match dim.domain::<DT>()? {
+                        Some(bounds) => Range::from(&bounds),
+                        None => {
+                            return Err(Error::InvalidArgument(anyhow!(
+                            "Dimension '{}' has no domain, add a range for it",
+                            dim.name()?
+                        )))
+                        }
+                    }
+                })
+            };
+            ranges.push(range);
+        }
+
+        let split = match self.layout {
+            QueryLayout::ColumnMajor => ranges.len() - 1,
+            _ => 0,
+        };
+        let dim = domain.dimension(split)?;
+
+        let Range::Single(ref split_range) = ranges[split] else {
+            return Err(Error::InvalidArgument(anyhow!(
+                "Cannot partition on dimension '{}': expected a fixed-size range, found {:?}",
+                dim.name()?,
+                ranges[split]
+            )));
+        };
+
+        let split_ranges = single_value_range_go!(
+            split_range,
+            DT: Integral,
+            start,
+            end,
+            {
+                /*
+                 * Arithmetic is done using i128, which can represent
+                 * every value of every integral physical type.
+                 * Dimensions without a tile extent are split as if
+                 * each cell were a tile.
+                 */
+                let lower = dim
+                    .domain::<DT>()?
+                    .map(|[lower, _]| lower)
+                    .unwrap_or(*start);
+                let lower = i128::from(lower);
+                let extent = dim
+                    .extent::<DT>()?
+                    .map(i128::from)
+                    .filter(|e| *e > 0)
+                    .unwrap_or(1);
+                let (start, end) = (i128::from(*start), i128::from(*end));
+
+                let first_tile = (start - lower) / extent;
+                let num_tiles = (end - lower) / extent - first_tile + 1;
+                let num_partitions = num_tiles.min(self.parallelism as i128);
+
+                (0..num_partitions)
+                    .map(|p| {
+                        let tile_start =
+                            first_tile + p * num_tiles / num_partitions;
+                        let tile_end =
+                            first_tile + (p + 1) * num_tiles / num_partitions;
+                        let pstart = start.max(lower + tile_start * extent);
+                        let pend = end.min(lower + tile_end * extent - 1);
+                        // N.B. `pstart` and `pend` lie within `[start, end]`
+                        // so these casts are lossless
+                        Range::from(&[pstart as DT, pend as DT])
+                    })
+                    .collect::<Vec<Range>>()
+            },
+            {
+                return Err(Error::InvalidArgument(anyhow!(
+                    "Cannot partition on dimension '{}' of non-integral type {}",
+                    dim.name()?,
+                    std::any::type_name::<DT>()
+                )));
+            }
+        );
+
+        Ok(split_ranges
+            .into_iter()
+            .map(|r| {
+                let mut partition = ranges.clone();
+                partition[split] = r;
+                partition
+            })
+            .collect::<Vec<_>>())
+    }
+
+    /// Runs a read query over each partition of the subarray, each on its
+    /// own thread, and returns their results in layout order.
+    ///
+    /// `recipe` adds the fields and other details of the query to a
+    /// [ReadBuilder] whose layout and subarray have already been set.
+    /// Each query uses its own array from the pool. Concatenating the
+    /// per-field results of each partition produces the result of the
+    /// whole subarray; see [execute_merged](Self::execute_merged).
+    pub fn execute<F, B, T>(&self, recipe: F) -> TileDBResult<Vec<T>>
+    where
+        F: Fn(ReadBuilder) -> TileDBResult<B> + Sync,
+        B: QueryBuilder,
+        B::Query: ReadQuery<Final = T>,
+        T: Send,
+    {
+        self.run(|b| {
+            let mut query = recipe(b)?.build();
+            let result = query.execute()?;
+            Ok((result, query.finalize()?))
+        })
+    }
+
+    /// Runs a read query over each partition of the subarray as in
+    /// [execute](Self::execute), and combines the results of the
+    /// partitions, in layout order, using `merge`.
+    ///
+    /// Returns `None` if the subarray has no partitions.
+    pub fn execute_merged<F, B, T, M>(
+        &self,
+        recipe: F,
+        merge: M,
+    ) -> TileDBResult<Option<T>>
+    where
+        F: Fn(ReadBuilder) -> TileDBResult<B> + Sync,
+        B: QueryBuilder,
+        B::Query: ReadQuery<Final = T>,
+        T: Send,
+        M: FnMut(T, T) -> T,
+    {
+        Ok(self.execute(recipe)?.into_iter().reduce(merge))
+    }
+
+    /// Runs an arrow read query over each partition of the subarray, each
+    /// on its own thread, and returns the batches produced by every step
+    /// of every partition in layout order.
+    ///
+    /// The batches all have the schema of the [ArrowReadBuilder] returned
+    /// by `recipe`, and may be concatenated to produce a single batch
+    /// for the whole subarray.
+    #[cfg(feature = "arrow")]
+    pub fn record_batches<F, B>(
+        &self,
+        recipe: F,
+    ) -> TileDBResult<Vec<arrow::array::RecordBatch>>
+    where
+        F: Fn(ReadBuilder) -> TileDBResult<ArrowReadBuilder<'static, B>> + Sync,
+        B: QueryBuilder,
+        B::Query: ReadQuery,
+    {
+        let partitions = self.run(|b| {
+            let mut query = recipe(b)?.build();
+            let mut batches = vec![];
+            loop {
+                match query.step()? {
+                    ReadStepOutput::NotEnoughSpace => continue,
+                    ReadStepOutput::Intermediate((batch, _)) => {
+                        batches.push(batch)
+                    }
+                    ReadStepOutput::Final((batch, _)) => {
+                        batches.push(batch);
+                        break;
+                    }
+                }
+            }
+            Ok((batches, query.finalize()?))
+        })?;
+        Ok(partitions.into_iter().flatten().collect())
+    }
+
+    /// Runs `read` over each partition of the subarray, each on its own
+    /// thread, and returns their results in layout order. `read` returns
+    /// its array so that it can be put back into the pool.
+    fn run<R, T>(&self, read: R) -> TileDBResult<Vec<T>>
+    where
+        R: Fn(ReadBuilder) -> TileDBResult<(T, Array)> + Sync,
+        T: Send,
+    {
+        let partitions = self.partitions()?;
+        let read = &read;
+
+        std::thread::scope(|s| {
+            let handles = partitions
+                .into_iter()
+                .map(|ranges| {
+                    s.spawn(move || self.read_partition(ranges, read))
+                })
+                .collect::<Vec<_>>();
+            handles
+                .into_iter()
+                .map(|h| h.join().expect("Partitioned read thread panicked"))
+                .collect::<TileDBResult<Vec<T>>>()
+        })
+    }
+
+    fn read_partition<R, T>(
+        &self,
+        ranges: Vec<Range>,
+        read: &R,
+    ) -> TileDBResult<T>
+    where
+        R: Fn(ReadBuilder) -> TileDBResult<(T, Array)>,
+    {
+        let array = self.pool.get()?.into_inner();
+        let mut subarray = ReadBuilder::new(array)?
+            .layout(self.layout)?
+            .start_subarray()?;
+        for (i, range) in ranges.into_iter().enumerate() {
+            subarray = subarray.add_range(i, range)?;
+        }
+
+        let (result, array) = read(subarray.finish_subarray()?)?;
+        self.pool.put(array);
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::query::ReadQueryBuilder;
+    use crate::tests::prelude::*;
+
+    #[test]
+    fn partitions() -> anyhow::Result<()> {
+        let test_array = dense_array("partitioned_read_partitions", 16, 4, 4)?;
+        let pool = ArrayPool::new(&test_array.context, &test_array.uri);
+
+        let rows = |partitions: Vec<Vec<Range>>| {
+            partitions
+                .into_iter()
+                .map(|p| p[0].clone())
+                .collect::<Vec<_>>()
+        };
+
+        let read = PartitionedRead::new(&pool)?.parallelism(4);
+        assert_eq!(
+            vec![
+                Range::from(&[1i32, 4]),
+                Range::from(&[5i32, 8]),
+                Range::from(&[9i32, 12]),
+                Range::from(&[13i32, 16])
+            ],
+            rows(read.partitions()?)
+        );
+
+        let read = read.parallelism(3).add_range("rows", &[3i32, 14])?;
+        assert_eq!(
+            vec![
+                Range::from(&[3i32, 4]),
+                Range::from(&[5i32, 8]),
+                Range::from(&[9i32, 14])
+            ],
+            rows(read.partitions()?)
+        );
+
+        let read = read.parallelism(8).add_range(0, &[6i32, 7])?;
+        assert_eq!(vec![Range::from(&[6i32, 7])], rows(read.partitions()?));
+
+        let read = read.layout(QueryLayout::ColumnMajor)?;
+        let partitions = read.partitions()?;
+        assert_eq!(1, partitions.len());
+        assert_eq!(
+            vec![Range::from(&[6i32, 7]), Range::from(&[1i32, 4])],
+            partitions[0]
+        );
+
+        assert!(matches!(
+            read.layout(QueryLayout::Global),
+            Err(Error::InvalidArgument(_))
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn execute() -> anyhow::Result<()> {
+        let test_array = dense_array("partitioned_read_execute", 16, 4, 4)?;
+        let pool = ArrayPool::new(&test_array.context, &test_array.uri);
+
+        let read = |layout, parallelism| -> anyhow::Result<Vec<i32>> {
+            let partitions = PartitionedRead::new(&pool)?
+                .layout(layout)?
+                .parallelism(parallelism)
+                .add_range("rows", &[2i32, 15])?
+                .execute(|b| {
+                    b.register_constructor::<_, Vec<i32>>(
+                        "a",
+                        Default::default(),
+                    )
+                })?;
+            Ok(partitions.into_iter().flat_map(|(a, _)| a).collect())
+        };
+
+        let row_major = (1..15)
+            .flat_map(|r| (0..4).map(move |c| r * 4 + c))
+            .collect::<Vec<i32>>();
+        let col_major = (0..4)
+            .flat_map(|c| (1..15).map(move |r| r * 4 + c))
+            .collect::<Vec<i32>>();
+
+        for parallelism in [1, 2, 3, 4, 8] {
+            assert_eq!(row_major, read(QueryLayout::RowMajor, parallelism)?);
+            assert_eq!(col_major, read(QueryLayout::ColumnMajor, parallelism)?);
+        }
+
+        let merged = PartitionedRead::new(&pool)?
+            .parallelism(4)
+            .add_range("rows", &[2i32, 15])?
+            .execute_merged(
+                |b| {
+                    b.register_constructor::<_, Vec<i32>>(
+                        "a",
+                        Default::default(),
+                    )
+                },
+                |(mut a, ()), (b, ())| {
+                    a.extend(b);
+                    (a, ())
+                },
+            )?;
+        assert_eq!(Some((row_major, ())), merged);
+
+        assert!(pool.num_idle() > 0);
+
+        Ok(())
+    }
+
+    #[cfg(feature = "arrow")]
+    #[test]
+    fn record_batches() -> anyhow::Result<()> {
+        use arrow::array::{AsArray, Int32Array};
+        use arrow::compute::concat_batches;
+        use arrow::datatypes::Int32Type;
+
+        let test_array =
+            dense_array("partitioned_read_record_batches", 16, 4, 4)?;
+        let pool = ArrayPool::new(&test_array.context, &test_array.uri);
+
+        // each step reads at most 8 cells, so each partition has several batches
+        let batches = PartitionedRead::new(&pool)?
+            .parallelism(4)
+            .add_range("rows", &[2i32, 15])?
+            .record_batches(|b| {
+                ArrowReadBuilder::with_memory_limit(b, ["a"], Some(32))
+            })?;
+        assert!(batches.len() > 4);
+
+        let merged = concat_batches(&batches[0].schema(), &batches)?;
+        assert_eq!(
+            &Int32Array::from((4..60).collect::<Vec<i32>>()),
+            merged.column(0).as_primitive::<Int32Type>()
+        );
+
+        Ok(())
+    }
+}
@@ -472,18 +472,18 @@ where
     type Intermediate = (usize, Q::Intermediate);
     type Final = (usize, Q::Final);
 
-    fn step(
-        &mut self,
-    ) -> TileDBResult<ReadStepOutput<Self::Intermediate, Self::Final>> {
+    fn prepare_step(&mut self) -> TileDBResult<()> {
         /* update the internal buffers */
         self.raw_read_output
             .attach_query(&self.base().context(), **self.base().cquery())?;
 
-        /* then execute */
-        let base_result = {
-            let _ = self.raw_read_output.borrow_mut();
-            self.base.step()?
-        };
+        self.base.prepare_step()
+    }
+
+    fn complete_step(
+        &mut self,
+    ) -> TileDBResult<ReadStepOutput<Self::Intermediate, Self::Final>> {
+        let base_result = self.base.complete_step()?;
 
         let ncells = self.raw_read_output.last_read_ncells();
 
@@ -496,7 +496,7 @@ where
                 ReadStepOutput::NotEnoughSpace
             }
             ReadStepOutput::Intermediate(base_result) => {
-                if ncells == 0 {
+                if ncells == 0 && !self.base().filtered_all() {
                     /*
                      * The input produced no data.
                      * The returned status itself is not enough to distinguish between
@@ -504,6 +504,8 @@ where
                      * The API tiledb_query_get_status_details exists but is experimental,
                      * so we will worry about it later.  For now, assume this is the first
                      * raw read and it is our responsibility to signal NotEnoughSpace.
+                     * A step whose cells were all removed by the query condition
+                     * did produce data, there just isn't any left.
                      */
                     ReadStepOutput::NotEnoughSpace
                 } else {
@@ -596,9 +598,7 @@ where
     type Intermediate = (Vec<usize>, Q::Intermediate);
     type Final = (Vec<usize>, Q::Final);
 
-    fn step(
-        &mut self,
-    ) -> TileDBResult<ReadStepOutput<Self::Intermediate, Self::Final>> {
+    fn prepare_step(&mut self) -> TileDBResult<()> {
         /* update the internal buffers */
         {
             let context = self.base().context();
@@ -608,15 +608,13 @@ where
             }
         }
 
-        /* then execute */
-        let base_result = {
-            let _ = self
-                .raw_read_output
-                .iter_mut()
-                .map(|r| r.borrow_mut())
-                .collect::<Vec<_>>();
-            self.base.step()?
-        };
+        self.base.prepare_step()
+    }
+
+    fn complete_step(
+        &mut self,
+    ) -> TileDBResult<ReadStepOutput<Self::Intermediate, Self::Final>> {
+        let base_result = self.base.complete_step()?;
 
         let read_sizes = self
             .raw_read_output
@@ -636,7 +634,7 @@ where
             }
             ReadStepOutput::Intermediate(base_result) => {
                 for ncells in read_sizes.iter() {
-                    if *ncells == 0 {
+                    if *ncells == 0 && !self.base().filtered_all() {
                         /*
                          * The input produced no data.
                          * The returned status itself is not enough to distinguish between
@@ -644,6 +642,8 @@ where
                          * The API tiledb_query_get_status_details exists but is experimental,
                          * so we will worry about it later.  For now, assume this is the first
                          * raw read and it is our responsibility to signal NotEnoughSpace.
+                         * A step whose cells were all removed by the query condition
+                         * did produce data, there just isn't any left.
                          */
                         return Ok(ReadStepOutput::NotEnoughSpace);
                     }
@@ -47,10 +47,14 @@ where
     type Intermediate = Q::Intermediate;
     type Final = (T, Q::Final);
 
-    fn step(
+    fn prepare_step(&mut self) -> TileDBResult<()> {
+        self.base.prepare_step()
+    }
+
+    fn complete_step(
         &mut self,
     ) -> TileDBResult<ReadStepOutput<Self::Intermediate, Self::Final>> {
-        Ok(match self.base.step()? {
+        Ok(match self.base.complete_step()? {
             ReadStepOutput::NotEnoughSpace => ReadStepOutput::NotEnoughSpace,
             ReadStepOutput::Intermediate((_, base_result)) => {
                 ReadStepOutput::Intermediate(base_result)
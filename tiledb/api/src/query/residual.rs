@@ -0,0 +1,524 @@
+//! Applies the part of a query condition which TileDB cannot apply,
+//! such as a comparison between two fields, to the results of a read query.
+//!
+//! After each step of the query, the condition is evaluated against the
+//! buffers which are attached to the query, and the cells which do not
+//! satisfy it are removed from all of those buffers. The sizes which
+//! libtiledb wrote for each buffer are updated to match, so the query
+//! adapters which own the buffers only see the remaining cells.
+//!
+//! The fields which the condition refers to are read even if no adapter
+//! reads them, into buffers which are owned by the query.
+
+use anyhow::anyhow;
+
+use crate::array::{Array, CellValNum, Field};
+use crate::context::{CApiInterface, Context, ContextBound};
+use crate::error::Error;
+use crate::physical_type_go;
+use crate::query::condition::{
+    CellValues, ConditionInput, FieldValues, QueryConditionExpr,
+};
+use crate::Datatype;
+use crate::Result as TileDBResult;
+
+/// Number of cells which buffers owned by the query initially hold.
+const INITIAL_CELL_CAPACITY: usize = 1024;
+
+/// Number of values per cell of a var-sized field which buffers owned by
+/// the query are sized for.
+const EST_VALUES_PER_VAR_CELL: usize = 64;
+
+pub(crate) struct Residual {
+    condition: QueryConditionExpr,
+    /// Buffers for the fields which `condition` refers to but which no
+    /// query adapter reads. Allocated when the query is first submitted.
+    extra: Option<Vec<ExtraBuffers>>,
+    /// Whether the last step read cells, none of which satisfied
+    /// `condition`.
+    filtered_all: bool,
+}
+
+impl Residual {
+    pub fn new(condition: QueryConditionExpr) -> Self {
+        Residual {
+            condition,
+            extra: None,
+            filtered_all: false,
+        }
+    }
+
+    /// Returns whether the last step read cells, none of which
+    /// satisfied the condition.
+    pub fn filtered_all(&self) -> bool {
+        self.filtered_all
+    }
+
+    /// Attaches buffers for the fields which the condition refers to but
+    /// which no query adapter reads. This must run after the adapters
+    /// have attached their buffers.
+    pub fn prepare_step(
+        &mut self,
+        array: &Array,
+        c_query: *mut ffi::tiledb_query_t,
+    ) -> TileDBResult<()> {
+        if self.extra.is_none() {
+            let schema = array.schema()?;
+            let mut extra: Vec<ExtraBuffers> = vec![];
+            for name in self.condition.fields() {
+                if extra.iter().any(|e| e.name == name) {
+                    continue;
+                }
+                let field = schema.field(name)?;
+                if AttachedField::get(array, c_query, &field)?.is_some() {
+                    continue;
+                }
+                extra.push(ExtraBuffers::new(
+                    name.to_owned(),
+                    field.datatype()?,
+                    field.cell_val_num()?,
+                    field.nullability()?,
+                    INITIAL_CELL_CAPACITY,
+                ));
+            }
+            self.extra = Some(extra);
+        }
+
+        let context = array.context();
+        for buffers in self.extra.iter_mut().flatten() {
+            buffers.attach(&context, c_query)?;
+        }
+        Ok(())
+    }
+
+    /// Removes the cells which do not satisfy the condition
+    /// from the results of the last step.
+    pub fn complete_step(
+        &mut self,
+        array: &Array,
+        c_query: *mut ffi::tiledb_query_t,
+        is_final: bool,
+    ) -> TileDBResult<()> {
+        self.filtered_all = false;
+
+        let schema = array.schema()?;
+        let mut fields = vec![];
+        for field in schema.fields()? {
+            if let Some(attached) = AttachedField::get(array, c_query, &field?)?
+            {
+                fields.push(attached);
+            }
+        }
+
+        let num_records = fields.first().map(|f| f.num_cells()).unwrap_or(0);
+        if num_records == 0 {
+            if !is_final {
+                /* some buffer is too small to hold even one cell */
+                for buffers in self.extra.iter_mut().flatten() {
+                    buffers.grow();
+                }
+            }
+            return Ok(());
+        }
+
+        let keep = self.condition.evaluate(&StepResults {
+            fields: &fields,
+            num_records,
+        })?;
+        if keep.iter().all(|k| *k) {
+            return Ok(());
+        }
+        self.filtered_all = !keep.contains(&true);
+
+        for field in fields.iter() {
+            // SAFETY: every field holds the `num_records` cells which
+            // were evaluated, and no references to the buffers remain.
+            unsafe { field.retain(&keep) }
+        }
+        Ok(())
+    }
+}
+
+/// Buffers owned by the query for reading one field.
+struct ExtraBuffers {
+    name: String,
+    datatype: Datatype,
+    cell_val_num: CellValNum,
+    is_nullable: bool,
+    /// Number of cells which the buffers can hold.
+    capacity: usize,
+    /// Size of the cell values in bytes.
+    /// `data` has `u64` elements so that it is aligned for any datatype.
+    data_capacity: usize,
+    data: Vec<u64>,
+    data_size: Box<u64>,
+    offsets: Option<Vec<u64>>,
+    offsets_size: Box<u64>,
+    validity: Option<Vec<u8>>,
+    validity_size: Box<u64>,
+}
+
+impl ExtraBuffers {
+    fn new(
+        name: String,
+        datatype: Datatype,
+        cell_val_num: CellValNum,
+        is_nullable: bool,
+        capacity: usize,
+    ) -> Self {
+        let values_per_cell = match cell_val_num {
+            CellValNum::Fixed(nz) => nz.get() as usize,
+            CellValNum::Var => EST_VALUES_PER_VAR_CELL,
+        };
+        let data_capacity = capacity * values_per_cell * datatype.size();
+        let data_len = data_capacity.div_ceil(std::mem::size_of::<u64>());
+
+        ExtraBuffers {
+            name,
+            datatype,
+            cell_val_num,
+            is_nullable,
+            capacity,
+            data_capacity,
+            data: vec![0; data_len],
+            data_size: Box::new(0),
+            offsets: cell_val_num.is_var_sized().then(|| vec![0; capacity + 1]),
+            offsets_size: Box::new(0),
+            validity: is_nullable.then(|| vec![0; capacity]),
+            validity_size: Box::new(0),
+        }
+    }
+
+    /// Replaces the buffers with ones which hold twice as many cells.
+    fn grow(&mut self) {
+        *self = ExtraBuffers::new(
+            std::mem::take(&mut self.name),
+            self.datatype,
+            self.cell_val_num,
+            self.is_nullable,
+            2 * self.capacity,
+        );
+    }
+
+    fn attach(
+        &mut self,
+        context: &Context,
+        c_query: *mut ffi::tiledb_query_t,
+    ) -> TileDBResult<()> {
+        let c_name = cstring!(self.name.as_str());
+
+        *self.data_size = self.data_capacity as u64;
+        let c_bufptr = self.data.as_mut_ptr() as *mut std::ffi::c_void;
+        let c_sizeptr = &mut *self.data_size as *mut u64;
+        context.capi_call(|ctx| unsafe {
+            ffi::tiledb_query_set_data_buffer(
+                ctx,
+                c_query,
+                c_name.as_ptr(),
+                c_bufptr,
+                c_sizeptr,
+            )
+        })?;
+
+        if let Some(ref mut offsets) = self.offsets {
+            *self.offsets_size = std::mem::size_of_val::<[u64]>(offsets) as u64;
+            let c_offptr = offsets.as_mut_ptr();
+            let c_sizeptr = &mut *self.offsets_size as *mut u64;
+            context.capi_call(|ctx| unsafe {
+                ffi::tiledb_query_set_offsets_buffer(
+                    ctx,
+                    c_query,
+                    c_name.as_ptr(),
+                    c_offptr,
+                    c_sizeptr,
+                )
+            })?;
+        }
+
+        if let Some(ref mut validity) = self.validity {
+            *self.validity_size = validity.len() as u64;
+            let c_validityptr = validity.as_mut_ptr();
+            let c_sizeptr = &mut *self.validity_size as *mut u64;
+            context.capi_call(|ctx| unsafe {
+                ffi::tiledb_query_set_validity_buffer(
+                    ctx,
+                    c_query,
+                    c_name.as_ptr(),
+                    c_validityptr,
+                    c_sizeptr,
+                )
+            })?;
+        }
+
+        Ok(())
+    }
+}
+
+/// How the cells of a field are laid out in its data buffer.
+#[derive(Clone, Copy)]
+enum CellLayout {
+    /// Each cell has the same size in bytes.
+    Fixed { cell_size: usize },
+    /// Each cell is delimited by offsets, counted in values,
+    /// with an extra offset for the end of the last cell.
+    Var {
+        offsets: *mut u64,
+        offsets_size: *mut u64,
+    },
+}
+
+/// The buffers which are attached to a query for one field,
+/// as libtiledb sees them.
+struct AttachedField {
+    name: String,
+    datatype: Datatype,
+    layout: CellLayout,
+    data: *mut u8,
+    data_size: *mut u64,
+    validity: Option<(*mut u8, *mut u64)>,
+}
+
+impl AttachedField {
+    /// Returns the buffers which are attached to the query for `field`,
+    /// if any.
+    fn get(
+        array: &Array,
+        c_query: *mut ffi::tiledb_query_t,
+        field: &Field,
+    ) -> TileDBResult<Option<Self>> {
+        let name = field.name()?;
+        let c_name = cstring!(name.as_str());
+
+        let mut data: *mut std::ffi::c_void = std::ptr::null_mut();
+        let mut data_size: *mut u64 = std::ptr::null_mut();
+        array.capi_call(|ctx| unsafe {
+            ffi::tiledb_query_get_data_buffer(
+                ctx,
+                c_query,
+                c_name.as_ptr(),
+                &mut data,
+                &mut data_size,
+            )
+        })?;
+        if data.is_null() || data_size.is_null() {
+            return Ok(None);
+        }
+
+        let datatype = field.datatype()?;
+
+        let layout = match field.cell_val_num()? {
+            CellValNum::Fixed(nz) => CellLayout::Fixed {
+                cell_size: nz.get() as usize * datatype.size(),
+            },
+            CellValNum::Var => {
+                let mut offsets: *mut u64 = std::ptr::null_mut();
+                let mut offsets_size: *mut u64 = std::ptr::null_mut();
+                array.capi_call(|ctx| unsafe {
+                    ffi::tiledb_query_get_offsets_buffer(
+                        ctx,
+                        c_query,
+                        c_name.as_ptr(),
+                        &mut offsets,
+                        &mut offsets_size,
+                    )
+                })?;
+                if offsets.is_null() || offsets_size.is_null() {
+                    return Err(Error::Internal(format!(
+                        "No offsets buffer for var-sized field '{}'",
+                        name
+                    )));
+                }
+                CellLayout::Var {
+                    offsets,
+                    offsets_size,
+                }
+            }
+        };
+
+        let validity = if field.nullability()? {
+            let mut validity: *mut u8 = std::ptr::null_mut();
+            let mut validity_size: *mut u64 = std::ptr::null_mut();
+            array.capi_call(|ctx| unsafe {
+                ffi::tiledb_query_get_validity_buffer(
+                    ctx,
+                    c_query,
+                    c_name.as_ptr(),
+                    &mut validity,
+                    &mut validity_size,
+                )
+            })?;
+            if validity.is_null() || validity_size.is_null() {
+                return Err(Error::Internal(format!(
+                    "No validity buffer for nullable field '{}'",
+                    name
+                )));
+            }
+            Some((validity, validity_size))
+        } else {
+            None
+        };
+
+        Ok(Some(AttachedField {
+            name,
+            datatype,
+            layout,
+            data: data as *mut u8,
+            data_size,
+            validity,
+        }))
+    }
+
+    /// Returns the number of cells which the last step wrote.
+    fn num_cells(&self) -> usize {
+        // SAFETY: libtiledb wrote the sizes, which stay valid
+        // while the buffers are attached
+        match self.layout {
+            CellLayout::Fixed { cell_size } => {
+                let data_size = unsafe { *self.data_size } as usize;
+                data_size / cell_size
+            }
+            CellLayout::Var { offsets_size, .. } => {
+                let offsets_size = unsafe { *offsets_size } as usize;
+                let noffsets = offsets_size / std::mem::size_of::<u64>();
+                /* there is no extra offset if there are no cells */
+                noffsets.saturating_sub(1)
+            }
+        }
+    }
+
+    /// Moves the cells for which `keep` is true to the front of the
+    /// buffers, in order, and updates the buffer sizes to match.
+    ///
+    /// # Safety
+    ///
+    /// The buffers must hold `keep.len()` cells written by libtiledb,
+    /// and must not be borrowed.
+    unsafe fn retain(&self, keep: &[bool]) {
+        let kept = keep
+            .iter()
+            .enumerate()
+            .filter(|(_, k)| **k)
+            .map(|(i, _)| i)
+            .collect::<Vec<usize>>();
+
+        match self.layout {
+            CellLayout::Fixed { cell_size } => {
+                for (j, i) in kept.iter().copied().enumerate() {
+                    std::ptr::copy(
+                        self.data.add(i * cell_size),
+                        self.data.add(j * cell_size),
+                        cell_size,
+                    );
+                }
+                *self.data_size = (kept.len() * cell_size) as u64;
+            }
+            CellLayout::Var {
+                offsets,
+                offsets_size,
+            } => {
+                let value_size = self.datatype.size();
+                let mut nvalues = 0;
+                for (j, i) in kept.iter().copied().enumerate() {
+                    let start = *offsets.add(i) as usize;
+                    let end = *offsets.add(i + 1) as usize;
+                    std::ptr::copy(
+                        self.data.add(start * value_size),
+                        self.data.add(nvalues * value_size),
+                        (end - start) * value_size,
+                    );
+                    *offsets.add(j) = nvalues as u64;
+                    nvalues += end - start;
+                }
+                if kept.is_empty() {
+                    *offsets_size = 0;
+                } else {
+                    *offsets.add(kept.len()) = nvalues as u64;
+                    *offsets_size =
+                        ((kept.len() + 1) * std::mem::size_of::<u64>()) as u64;
+                }
+                *self.data_size = (nvalues * value_size) as u64;
+            }
+        }
+
+        if let Some((validity, validity_size)) = self.validity {
+            for (j, i) in kept.iter().copied().enumerate() {
+                *validity.add(j) = *validity.add(i);
+            }
+            *validity_size = kept.len() as u64;
+        }
+    }
+}
+
+/// The results of one step of a query, for evaluating a condition.
+struct StepResults<'a> {
+    fields: &'a [AttachedField],
+    num_records: usize,
+}
+
+impl ConditionInput for StepResults<'_> {
+    fn num_records(&self) -> usize {
+        self.num_records
+    }
+
+    fn field_values(&self, field: &str) -> TileDBResult<FieldValues<'_>> {
+        let Some(attached) = self.fields.iter().find(|f| f.name == field)
+        else {
+            return Err(Error::InvalidArgument(anyhow!(
+                "No such field: {}",
+                field
+            )));
+        };
+        let n = self.num_records;
+
+        // SAFETY: libtiledb wrote `n` cells to each buffer, which the
+        // query adapters allocated for the datatype of the field
+        let values = match attached.layout {
+            CellLayout::Fixed { cell_size }
+                if cell_size == attached.datatype.size() =>
+            {
+                physical_type_go!(attached.datatype, DT, {
+                    CellValues::from(unsafe {
+                        std::slice::from_raw_parts(
+                            attached.data as *const DT,
+                            n,
+                        )
+                    })
+                })
+            }
+            CellLayout::Var { offsets, .. }
+                if attached.datatype.size() == 1 =>
+            {
+                let offsets =
+                    unsafe { std::slice::from_raw_parts(offsets, n + 1) };
+                let data = unsafe {
+                    std::slice::from_raw_parts(
+                        attached.data,
+                        *attached.data_size as usize,
+                    )
+                };
+                CellValues::Bytes(
+                    offsets
+                        .windows(2)
+                        .map(|w| &data[w[0] as usize..w[1] as usize])
+                        .collect(),
+                )
+            }
+            _ => {
+                return Err(Error::InvalidArgument(anyhow!(
+                    "Field '{}' has multiple values per cell, \
+                     which query conditions do not support",
+                    field
+                )))
+            }
+        };
+
+        let validity = attached.validity.map(|(validity, _)| {
+            unsafe { std::slice::from_raw_parts(validity, n) }
+                .iter()
+                .map(|v| *v != 0)
+                .collect()
+        });
+
+        Ok(FieldValues { values, validity })
+    }
+}
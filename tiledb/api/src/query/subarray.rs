@@ -304,6 +304,75 @@ where
         Ok(self)
     }
 
+    /// Add a range on a dimension to the subarray, specified by the values
+    /// of the dimension label `label` rather than by dimension coordinates.
+    /// TileDB uses the label to find the coordinates which correspond
+    /// to the range of label values.
+    pub fn add_label_range<IntoRange: Into<Range>>(
+        self,
+        label: &str,
+        range: IntoRange,
+    ) -> TileDBResult<Self> {
+        // Get the label so that we can assert the correct Range type.
+        let schema = self.query.base().query.array.schema()?;
+        let dim_label = schema.dimension_label(label)?;
+
+        let range = range.into();
+        range
+            .check_dimension_compatibility(
+                dim_label.datatype()?,
+                dim_label.cell_val_num()?,
+            )
+            .map_err(|e| {
+                Error::InvalidArgument(
+                    anyhow!("Invalid range variant for dimension label")
+                        .context(e),
+                )
+            })?;
+
+        let c_subarray = *self.raw;
+        let c_label = cstring!(label);
+
+        match range {
+            Range::Single(range) => {
+                single_value_range_go!(range, _DT, start, end, {
+                    let start = start.to_le_bytes();
+                    let end = end.to_le_bytes();
+                    self.query.base().capi_call(|ctx| unsafe {
+                        ffi::tiledb_subarray_add_label_range(
+                            ctx,
+                            c_subarray,
+                            c_label.as_ptr(),
+                            start.as_ptr() as *const std::ffi::c_void,
+                            end.as_ptr() as *const std::ffi::c_void,
+                            std::ptr::null(),
+                        )
+                    })?;
+                })
+            }
+            Range::Multi(_) => unreachable!(
+                "This is rejected by range.check_dimension_compatibility"
+            ),
+            Range::Var(range) => {
+                var_value_range_go!(range, _DT, start, end, {
+                    self.query.base().capi_call(|ctx| unsafe {
+                        ffi::tiledb_subarray_add_label_range_var(
+                            ctx,
+                            c_subarray,
+                            c_label.as_ptr(),
+                            start.as_ptr() as *const std::ffi::c_void,
+                            start.len() as u64,
+                            end.as_ptr() as *const std::ffi::c_void,
+                            end.len() as u64,
+                        )
+                    })?;
+                })
+            }
+        }
+
+        Ok(self)
+    }
+
     /// Add a list of point ranges to the query.
     pub fn add_point_ranges<Key: Into<LookupKey>, T: PhysicalType>(
         self,
@@ -384,8 +453,8 @@ mod tests {
     use super::*;
     use crate::array::*;
     use crate::query::{
-        Query, QueryBuilder, ReadBuilder, ReadQuery, ReadQueryBuilder,
-        WriteBuilder,
+        Query, QueryBuilder, QueryLayout, ReadBuilder, ReadQuery,
+        ReadQueryBuilder, WriteBuilder,
     };
     use crate::Datatype;
 
@@ -574,6 +643,85 @@ mod tests {
         Ok(array_uri)
     }
 
+    /// Test selecting a range of a dense array by dimension label
+    #[test]
+    fn label_range() -> TileDBResult<()> {
+        let ctx = Context::new().unwrap();
+
+        let test_uri = uri::get_uri_generator()
+            .map_err(|e| Error::Other(e.to_string()))?;
+        let array_uri = test_uri
+            .with_path("label_range")
+            .map_err(|e| Error::Other(e.to_string()))?;
+
+        {
+            let domain = DomainBuilder::new(&ctx)?
+                .add_dimension(
+                    DimensionBuilder::new(
+                        &ctx,
+                        "offset",
+                        Datatype::UInt64,
+                        ([0u64, 9], 10u64),
+                    )?
+                    .build(),
+                )?
+                .build();
+            let schema = SchemaBuilder::new(&ctx, ArrayType::Dense, domain)?
+                .add_attribute(
+                    AttributeBuilder::new(&ctx, "value", Datatype::Int32)?
+                        .build(),
+                )?
+                .add_dimension_label(
+                    "offset",
+                    "timestamp",
+                    DataOrder::Increasing,
+                    Datatype::Int64,
+                )?
+                .build()?;
+            Array::create(&ctx, &array_uri, schema)?;
+        }
+
+        let values = (0..10).collect::<Vec<i32>>();
+        let timestamps = (0..10).map(|t| 1000 + 10 * t).collect::<Vec<i64>>();
+
+        {
+            let w = Array::open(&ctx, &array_uri, Mode::Write)?;
+            let q = WriteBuilder::new(w)?
+                .layout(QueryLayout::RowMajor)?
+                .start_subarray()?
+                .add_range("offset", &[0u64, 9])?
+                .finish_subarray()?
+                .data("value", &values)?
+                .data("timestamp", &timestamps)?
+                .build();
+            q.submit()?;
+            q.finalize()?;
+        }
+
+        // the range must match the label type
+        {
+            let a = Array::open(&ctx, &array_uri, Mode::Read)?;
+            let b = ReadBuilder::new(a)?
+                .start_subarray()?
+                .add_label_range("timestamp", &[1020u64, 1050]);
+            assert!(matches!(b, Err(Error::InvalidArgument(_))));
+        }
+
+        let a = Array::open(&ctx, &array_uri, Mode::Read)?;
+        let mut q = ReadBuilder::new(a)?
+            .layout(QueryLayout::RowMajor)?
+            .register_constructor::<_, Vec<i32>>("value", Default::default())?
+            .start_subarray()?
+            .add_label_range("timestamp", &[1020i64, 1050])?
+            .finish_subarray()?
+            .build();
+
+        let (selected, _) = q.execute()?;
+        assert_eq!(vec![2, 3, 4, 5], selected);
+
+        Ok(())
+    }
+
     #[test]
     fn dimension_ranges() {
         let ctx = Context::new().unwrap();
@@ -0,0 +1,286 @@
+use super::*;
+
+use anyhow::anyhow;
+
+use crate::array::CellValNum;
+use crate::query::condition::Literal;
+use crate::Datatype;
+
+/// A query which assigns new attribute values to the cells of an array
+/// which match a query condition.
+///
+/// The array must be opened in [`Mode::Update`](crate::array::Mode::Update).
+/// Updates are experimental in libtiledb and require the
+/// `sm.allow_updates_experimental` configuration option to be set
+/// when opening the array.
+pub struct UpdateQuery {
+    base: QueryBase,
+}
+
+impl ContextBound for UpdateQuery {
+    fn context(&self) -> Context {
+        self.base.context()
+    }
+}
+
+impl Query for UpdateQuery {
+    fn base(&self) -> &QueryBase {
+        self.base.base()
+    }
+
+    fn finalize(self) -> TileDBResult<Array> {
+        self.base.finalize()
+    }
+}
+
+impl UpdateQuery {
+    pub fn submit(&self) -> TileDBResult<()> {
+        self.base.do_submit()
+    }
+}
+
+/// Builder for an [`UpdateQuery`].
+///
+/// The cells to update are chosen using [`QueryBuilder::query_condition`],
+/// and the new values are set using [`UpdateBuilder::value`].
+pub struct UpdateBuilder {
+    base: BuilderBase,
+}
+
+impl ContextBound for UpdateBuilder {
+    fn context(&self) -> Context {
+        self.base.context()
+    }
+}
+
+impl QueryBuilder for UpdateBuilder {
+    type Query = UpdateQuery;
+
+    fn base(&self) -> &BuilderBase {
+        &self.base
+    }
+
+    fn build(self) -> Self::Query {
+        UpdateQuery {
+            base: self.base.build(),
+        }
+    }
+}
+
+impl UpdateBuilder {
+    pub fn new(array: Array) -> TileDBResult<Self> {
+        Ok(UpdateBuilder {
+            base: BuilderBase::new(array, QueryType::Update)?,
+        })
+    }
+
+    /// Sets `value` as the new value of `field` for each cell
+    /// which matches the query condition.
+    ///
+    /// Returns an error if `field` is a dimension or is not
+    /// an attribute of the array schema, or if `value` cannot be
+    /// a cell value of `field`.
+    pub fn value<S, V>(self, field: S, value: V) -> TileDBResult<Self>
+    where
+        S: AsRef<str>,
+        V: Into<Literal>,
+    {
+        let field = field.as_ref();
+        let value = value.into();
+
+        {
+            let schema = self.base().array().schema()?;
+            if schema.domain()?.has_dimension(field)? {
+                return Err(Error::InvalidArgument(anyhow!(
+                    "Cannot update dimension '{}'",
+                    field
+                )));
+            }
+            if !schema.has_attribute(field)? {
+                return Err(Error::InvalidArgument(anyhow!(
+                    "No such attribute '{}'",
+                    field
+                )));
+            }
+
+            let attr = schema.attribute(field)?;
+            let (datatype, cell_val_num) =
+                (attr.datatype()?, attr.cell_val_num()?);
+            if !is_compatible_literal(&value, datatype, cell_val_num) {
+                return Err(Error::InvalidArgument(anyhow!(
+                    "Cannot update attribute '{}' of type ({}, {}) with value {}",
+                    field,
+                    datatype,
+                    cell_val_num,
+                    value
+                )));
+            }
+        }
+
+        let c_query = **self.base().cquery();
+        let c_name = cstring!(field);
+        let bytes = value.to_bytes();
+        let c_value = bytes.as_ptr() as *const std::ffi::c_void;
+        let c_size = bytes.len() as u64;
+
+        self.capi_call(|ctx| unsafe {
+            ffi::tiledb_query_add_update_value(
+                ctx,
+                c_query,
+                c_name.as_ptr(),
+                c_value,
+                c_size,
+            )
+        })?;
+
+        Ok(self)
+    }
+}
+
+/// Returns whether `value` can be a cell of a field with the
+/// given `datatype` and `cell_val_num`.
+fn is_compatible_literal(
+    value: &Literal,
+    datatype: Datatype,
+    cell_val_num: CellValNum,
+) -> bool {
+    if let Literal::String(_) = value {
+        return datatype.is_string_type() && cell_val_num.is_var_sized();
+    }
+    if !cell_val_num.is_single_valued() {
+        return false;
+    }
+    match value {
+        Literal::UInt8(_) => datatype.is_compatible_type::<u8>(),
+        Literal::UInt16(_) => datatype.is_compatible_type::<u16>(),
+        Literal::UInt32(_) => datatype.is_compatible_type::<u32>(),
+        Literal::UInt64(_) => datatype.is_compatible_type::<u64>(),
+        Literal::Int8(_) => datatype.is_compatible_type::<i8>(),
+        Literal::Int16(_) => datatype.is_compatible_type::<i16>(),
+        Literal::Int32(_) => datatype.is_compatible_type::<i32>(),
+        Literal::Int64(_) => datatype.is_compatible_type::<i64>(),
+        Literal::Float32(_) => datatype.is_compatible_type::<f32>(),
+        Literal::Float64(_) => datatype.is_compatible_type::<f64>(),
+        Literal::String(_) => unreachable!(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::rc::Rc;
+
+    use super::*;
+    use crate::array::ArrayOpener;
+    use crate::tests::prelude::*;
+
+    /// Cells written by `quickstart_init`, as `(rows, cols, a)`.
+    const ROWS: [i32; 8] = [1, 1, 2, 2, 3, 3, 4, 4];
+    const COLS: [i32; 8] = [1, 2, 1, 2, 1, 2, 1, 2];
+    const ATTS: [i32; 8] = [10, 11, 12, 13, 14, 15, 16, 17];
+
+    fn quickstart_init(name: &str) -> TileDBResult<TestArray> {
+        let array = TestArray::new(
+            name,
+            Rc::new(
+                crate::tests::examples::quickstart::Builder::new(
+                    ArrayType::Sparse,
+                )
+                .build(),
+            ),
+        )?;
+
+        // write at timestamp 1 and update at timestamp 2 so that
+        // the update is guaranteed to apply to the written cells
+        {
+            let w = ArrayOpener::new(&array.context, &array.uri, Mode::Write)?
+                .end_timestamp(1)?
+                .open()?;
+            let w = WriteBuilder::new(w)?
+                .data_typed("rows", &ROWS.to_vec())?
+                .data_typed("cols", &COLS.to_vec())?
+                .data_typed("a", &ATTS.to_vec())?
+                .build();
+            w.submit()?;
+            w.finalize()?;
+        }
+
+        Ok(array)
+    }
+
+    fn open_for_update(array: &TestArray) -> TileDBResult<Array> {
+        let mut config = crate::config::Config::new()?;
+        config.set("sm.allow_updates_experimental", "true")?;
+        ArrayOpener::new(&array.context, &array.uri, Mode::Update)?
+            .config(&config)?
+            .end_timestamp(2)?
+            .open()
+    }
+
+    /// Reads back all of the cells of `array` as `(rows, cols, a)`.
+    fn readback(
+        array: &TestArray,
+    ) -> TileDBResult<(Vec<i32>, Vec<i32>, Vec<i32>)> {
+        let mut q = ReadBuilder::new(array.for_read()?)?
+            .layout(QueryLayout::RowMajor)?
+            .register_constructor::<_, Vec<i32>>("rows", Default::default())?
+            .register_constructor::<_, Vec<i32>>("cols", Default::default())?
+            .register_constructor::<_, Vec<i32>>("a", Default::default())?
+            .build();
+        let (a, (cols, (rows, _))) = q.execute()?;
+        Ok((rows, cols, a))
+    }
+
+    #[test]
+    fn update_rejects_invalid_fields() -> TileDBResult<()> {
+        let array = quickstart_init("update_rejects_invalid_fields")?;
+
+        // these are rejected by the builder, not by libtiledb,
+        // so the error is `InvalidArgument` rather than `LibTileDB`
+
+        // dimensions cannot be updated
+        let r = UpdateBuilder::new(open_for_update(&array)?)?.value("rows", 1);
+        assert!(matches!(r, Err(Error::InvalidArgument(_))));
+
+        // unknown fields cannot be updated
+        let r = UpdateBuilder::new(open_for_update(&array)?)?.value("b", 1);
+        assert!(matches!(r, Err(Error::InvalidArgument(_))));
+
+        // values must match the attribute type
+        let r = UpdateBuilder::new(open_for_update(&array)?)?.value("a", 1u64);
+        assert!(matches!(r, Err(Error::InvalidArgument(_))));
+        let r = UpdateBuilder::new(open_for_update(&array)?)?.value("a", "one");
+        assert!(matches!(r, Err(Error::InvalidArgument(_))));
+
+        // and nothing was changed
+        assert_eq!(
+            (ROWS.to_vec(), COLS.to_vec(), ATTS.to_vec()),
+            readback(&array)?
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn update_submit() -> TileDBResult<()> {
+        let array = quickstart_init("update_submit")?;
+
+        let q = UpdateBuilder::new(open_for_update(&array)?)?
+            .query_condition(QueryConditionExpr::field("a").ge(14))?
+            .value("a", 0)?
+            .build();
+        q.submit()?;
+        q.finalize()?;
+
+        let expect_a = ATTS
+            .iter()
+            .map(|a| if *a >= 14 { 0 } else { *a })
+            .collect::<Vec<i32>>();
+
+        let (rows, cols, a) = readback(&array)?;
+        assert_eq!(ROWS.to_vec(), rows);
+        assert_eq!(COLS.to_vec(), cols);
+        assert_eq!(expect_a, a);
+
+        Ok(())
+    }
+}
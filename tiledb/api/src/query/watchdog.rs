@@ -0,0 +1,195 @@
+//! Cancels the tasks of queries which run past their deadline.
+//!
+//! A single background thread waits for the earliest deadline of all
+//! of the queries which are currently being submitted, and calls
+//! `tiledb_ctx_cancel_tasks` on the context of a query whose deadline
+//! passes before its submission finishes.
+
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Condvar, Mutex, MutexGuard, OnceLock};
+use std::thread;
+use std::time::Instant;
+
+use crate::context::Context;
+use crate::error::Error;
+use crate::Result as TileDBResult;
+
+struct RawContextPtr(*mut ffi::tiledb_ctx_t);
+
+// SAFETY: libtiledb contexts may be used from any thread.
+// A `Watch` keeps its context alive until it is dropped, and dropping
+// the `Watch` waits for the watchdog thread to stop using the context.
+unsafe impl Send for RawContextPtr {}
+
+struct Entry {
+    context: RawContextPtr,
+    expired: Arc<AtomicBool>,
+}
+
+#[derive(Default)]
+struct Watches {
+    next_id: u64,
+    entries: BTreeMap<(Instant, u64), Entry>,
+}
+
+#[derive(Default)]
+struct Watchdog {
+    watches: Mutex<Watches>,
+    changed: Condvar,
+}
+
+impl Watchdog {
+    fn lock(&self) -> MutexGuard<'_, Watches> {
+        self.watches.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn run(&self) {
+        let mut watches = self.lock();
+        loop {
+            let now = Instant::now();
+            let next = watches.entries.first_key_value().map(|(k, _)| *k);
+            watches = match next {
+                None => self
+                    .changed
+                    .wait(watches)
+                    .unwrap_or_else(|e| e.into_inner()),
+                Some(key) if key.0 <= now => {
+                    let entry = watches.entries.remove(&key).unwrap();
+                    entry.expired.store(true, Ordering::Release);
+                    /*
+                     * N.B. this happens while holding the lock so that
+                     * the context cannot be freed concurrently.
+                     * There is nothing useful to do if this fails.
+                     */
+                    let _ = unsafe {
+                        ffi::tiledb_ctx_cancel_tasks(entry.context.0)
+                    };
+                    watches
+                }
+                Some(key) => {
+                    self.changed
+                        .wait_timeout(watches, key.0 - now)
+                        .unwrap_or_else(|e| e.into_inner())
+                        .0
+                }
+            };
+        }
+    }
+}
+
+fn watchdog() -> &'static Watchdog {
+    static WATCHDOG: OnceLock<Watchdog> = OnceLock::new();
+    WATCHDOG.get_or_init(|| {
+        thread::Builder::new()
+            .name("tiledb-query-watchdog".to_owned())
+            .spawn(|| watchdog().run())
+            .expect("Error spawning query watchdog thread");
+        Watchdog::default()
+    })
+}
+
+/// Registration of a query submission with the watchdog.
+/// The submission is no longer watched once this is dropped.
+pub(crate) struct Watch {
+    key: (Instant, u64),
+    expired: Arc<AtomicBool>,
+    // keeps the watched context alive
+    _context: Context,
+}
+
+impl Watch {
+    /// Watches a submission which must finish before `deadline`.
+    /// Returns `Error::Cancelled` if the deadline has already passed.
+    pub(crate) fn start(
+        context: &Context,
+        deadline: Instant,
+    ) -> TileDBResult<Self> {
+        if deadline <= Instant::now() {
+            return Err(Error::Cancelled);
+        }
+
+        let watchdog = watchdog();
+        let expired = Arc::new(AtomicBool::new(false));
+
+        let key = {
+            let mut watches = watchdog.lock();
+            let key = (deadline, watches.next_id);
+            watches.next_id += 1;
+            watches.entries.insert(
+                key,
+                Entry {
+                    context: RawContextPtr(context.capi()),
+                    expired: Arc::clone(&expired),
+                },
+            );
+            key
+        };
+        watchdog.changed.notify_one();
+
+        Ok(Watch {
+            key,
+            expired,
+            _context: context.clone(),
+        })
+    }
+
+    /// Returns whether the deadline passed and the context was cancelled.
+    pub(crate) fn expired(&self) -> bool {
+        self.expired.load(Ordering::Acquire)
+    }
+}
+
+impl Drop for Watch {
+    fn drop(&mut self) {
+        watchdog().lock().entries.remove(&self.key);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::query::ReadQueryBuilder;
+    use crate::tests::prelude::*;
+
+    use super::*;
+
+    #[test]
+    fn query_deadline() -> anyhow::Result<()> {
+        let array = sparse_array("query_deadline")?;
+
+        // the deadline passes before the query is submitted
+        let mut q = ReadBuilder::new(array.for_read()?)?
+            .deadline(Duration::ZERO)
+            .register_constructor::<_, Vec<i32>>("a", Default::default())?
+            .build();
+        assert!(matches!(q.execute(), Err(Error::Cancelled)));
+
+        // the deadline can be set at any point while building the query
+        let mut q = ReadBuilder::new(array.for_read()?)?
+            .register_constructor::<_, Vec<i32>>("a", Default::default())?
+            .deadline(Duration::from_secs(3600))
+            .build();
+        let (a, _) = q.execute()?;
+        assert_eq!(vec![10, 11, 12, 13], a);
+
+        Ok(())
+    }
+
+    #[test]
+    fn query_deadline_during_submit() -> anyhow::Result<()> {
+        // reading all 16M cells takes much longer than the deadline
+        let array =
+            dense_array("query_deadline_during_submit", 4096, 4096, 1024)?;
+
+        let mut q = ReadBuilder::new(array.for_read()?)?
+            .deadline(Duration::from_millis(1))
+            .layout(QueryLayout::RowMajor)?
+            .register_constructor::<_, Vec<i32>>("a", Default::default())?
+            .build();
+        assert!(matches!(q.execute(), Err(Error::Cancelled)));
+
+        Ok(())
+    }
+}
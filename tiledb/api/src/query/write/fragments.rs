@@ -0,0 +1,157 @@
+use crate::array::fragment_info::{
+    Builder as FragmentInfoBuilder, FragmentInfo, FragmentInfoList,
+};
+use crate::context::Context;
+use crate::Result as TileDBResult;
+
+/// A fragment which was created by a [`WriteQuery`](super::WriteQuery).
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct WrittenFragment {
+    /// URI of the fragment.
+    pub uri: String,
+    /// Range of timestamps, inclusive, of the fragment.
+    pub timestamp_range: [u64; 2],
+}
+
+impl WrittenFragment {
+    /// Returns the name of the fragment, i.e. the last component of its URI.
+    pub fn name(&self) -> &str {
+        self.uri
+            .trim_end_matches('/')
+            .rsplit('/')
+            .next()
+            .unwrap_or(&self.uri)
+    }
+
+    /// Returns the entry of `fragment_info` which describes this fragment,
+    /// or `None` if there is no such entry.
+    pub fn find_in<'info>(
+        &self,
+        fragment_info: &'info FragmentInfoList,
+    ) -> TileDBResult<Option<FragmentInfo<'info>>> {
+        for fragment in fragment_info.iter()? {
+            if fragment.name()? == self.name() {
+                return Ok(Some(fragment));
+            }
+        }
+        Ok(None)
+    }
+}
+
+/// Summary of the fragments created by a [`WriteQuery`](super::WriteQuery).
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct WrittenFragments {
+    /// URI of the array which the fragments were written to.
+    pub array_uri: String,
+    /// The fragments, in the order they were written.
+    pub fragments: Vec<WrittenFragment>,
+}
+
+impl WrittenFragments {
+    pub fn len(&self) -> usize {
+        self.fragments.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.fragments.is_empty()
+    }
+
+    pub fn iter(&self) -> std::slice::Iter<'_, WrittenFragment> {
+        self.fragments.iter()
+    }
+
+    /// Loads the fragment info of the array which the fragments were
+    /// written to. Use [`WrittenFragment::find_in`] to look up
+    /// the fragment info of each written fragment.
+    pub fn fragment_info(
+        &self,
+        context: &Context,
+    ) -> TileDBResult<FragmentInfoList> {
+        FragmentInfoBuilder::new(context, &self.array_uri)?.build()
+    }
+}
+
+impl<'a> IntoIterator for &'a WrittenFragments {
+    type Item = &'a WrittenFragment;
+    type IntoIter = std::slice::Iter<'a, WrittenFragment>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl IntoIterator for WrittenFragments {
+    type Item = WrittenFragment;
+    type IntoIter = std::vec::IntoIter<WrittenFragment>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.fragments.into_iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::rc::Rc;
+
+    use super::*;
+    use crate::array::{ArrayOpener, Mode};
+    use crate::query::{Query, QueryBuilder, WriteBuilder};
+    use crate::tests::prelude::*;
+
+    #[test]
+    fn written_fragments() -> TileDBResult<()> {
+        let array = TestArray::new(
+            "written_fragments",
+            Rc::new(
+                crate::tests::examples::quickstart::Builder::new(
+                    ArrayType::Sparse,
+                )
+                .build(),
+            ),
+        )?;
+
+        let write = |timestamp: u64,
+                     atts: &Vec<i32>|
+         -> TileDBResult<WrittenFragments> {
+            let w = ArrayOpener::new(&array.context, &array.uri, Mode::Write)?
+                .end_timestamp(timestamp)?
+                .open()?;
+            let rows = vec![1, 2, 3, 4];
+            let cols = vec![1, 2, 3, 4];
+            let q = WriteBuilder::new(w)?
+                .data_typed("rows", &rows)?
+                .data_typed("cols", &cols)?
+                .data_typed("a", atts)?
+                .build();
+
+            // nothing has been written before the query is submitted
+            assert!(q.fragments()?.is_empty());
+
+            q.submit()?;
+            q.finalize_with_fragments().map(|(_, fragments)| fragments)
+        };
+
+        let first = write(1, &vec![1, 2, 3, 4])?;
+        let second = write(2, &vec![5, 6, 7, 8])?;
+
+        assert_eq!(1, first.len());
+        assert_eq!(1, second.len());
+        assert_eq!([1, 1], first.fragments[0].timestamp_range);
+        assert_eq!([2, 2], second.fragments[0].timestamp_range);
+        assert_ne!(first.fragments[0].uri, second.fragments[0].uri);
+
+        let fragment_info = second.fragment_info(&array.context)?;
+        assert_eq!(2, fragment_info.num_fragments()?);
+
+        for written in first.iter().chain(second.iter()) {
+            let fragment = written
+                .find_in(&fragment_info)?
+                .expect("Written fragment not found in fragment info");
+            assert_eq!(written.name(), fragment.name()?);
+            assert_eq!(written.timestamp_range, fragment.timestamp_range()?);
+            assert_eq!(4, fragment.num_cells()?);
+        }
+
+        Ok(())
+    }
+}
@@ -4,6 +4,7 @@ use std::collections::HashMap;
 use std::pin::Pin;
 use std::rc::Rc;
 
+use crate::array::CellValNum;
 use crate::config::Config;
 use crate::query::buffer::{CellStructure, QueryBuffers, TypedQueryBuffers};
 use crate::query::write::input::{
@@ -11,8 +12,11 @@ use crate::query::write::input::{
 };
 use crate::typed_query_buffers_go;
 
+mod fragments;
 pub mod input;
 
+pub use fragments::{WrittenFragment, WrittenFragments};
+
 struct RawWriteInput<'data> {
     _data_size: Pin<Box<u64>>,
     _offsets_size: Option<Pin<Box<u64>>>,
@@ -51,6 +55,80 @@ impl WriteQuery<'_> {
     }
 }
 
+#[cfg(feature = "async")]
+impl WriteQuery<'static> {
+    /// Returns a future which submits the query on a background thread.
+    ///
+    /// The future owns the query and gives it back alongside the
+    /// result of the submission, so that it can be finalized.
+    pub fn submit_async(self) -> crate::query::SubmitFuture {
+        crate::query::SubmitFuture::new(self)
+    }
+}
+
+impl WriteQuery<'_> {
+    /// Returns the fragments which this query has written so far.
+    ///
+    /// Fragments written in global order are not complete until the query
+    /// is finalized; use [`WriteQuery::finalize_with_fragments`] for those.
+    pub fn fragments(&self) -> TileDBResult<WrittenFragments> {
+        let c_query = **self.base.cquery();
+
+        let mut c_num: u32 = 0;
+        self.capi_call(|ctx| unsafe {
+            ffi::tiledb_query_get_fragment_num(ctx, c_query, &mut c_num)
+        })?;
+
+        let fragments = (0..c_num as u64)
+            .map(|idx| {
+                let mut c_uri: *const std::ffi::c_char = out_ptr!();
+                self.capi_call(|ctx| unsafe {
+                    ffi::tiledb_query_get_fragment_uri(
+                        ctx, c_query, idx, &mut c_uri,
+                    )
+                })?;
+
+                // N.B. This API only lends a pointer to an internally managed
+                // std::string, thus we do *not* want to free it.
+                let uri = unsafe { std::ffi::CStr::from_ptr(c_uri) }
+                    .to_string_lossy()
+                    .into_owned();
+
+                let (mut start, mut end) = (0u64, 0u64);
+                self.capi_call(|ctx| unsafe {
+                    ffi::tiledb_query_get_fragment_timestamp_range(
+                        ctx, c_query, idx, &mut start, &mut end,
+                    )
+                })?;
+
+                Ok(WrittenFragment {
+                    uri,
+                    timestamp_range: [start, end],
+                })
+            })
+            .collect::<TileDBResult<Vec<_>>>()?;
+
+        Ok(WrittenFragments {
+            array_uri: self.base.array().uri().to_owned(),
+            fragments,
+        })
+    }
+
+    /// Finalizes the query, and returns the array along with
+    /// all of the fragments written by the query.
+    pub fn finalize_with_fragments(
+        self,
+    ) -> TileDBResult<(Array, WrittenFragments)> {
+        let c_query = **self.base.cquery();
+        self.capi_call(|ctx| unsafe {
+            ffi::tiledb_query_finalize(ctx, c_query)
+        })?;
+
+        let fragments = self.fragments()?;
+        Ok((self.base.array, fragments))
+    }
+}
+
 pub struct WriteBuilder<'data> {
     base: BuilderBase,
     inputs: InputMap<'data>,
@@ -190,6 +268,19 @@ impl<'data> WriteBuilder<'data> {
         Ok(self)
     }
 
+    /// Returns the cell structure and nullability of the input for `name`,
+    /// which is either a field of the schema or a dimension label.
+    fn input_structure(&self, name: &str) -> TileDBResult<(CellValNum, bool)> {
+        let schema = self.base().array().schema()?;
+        if schema.has_dimension_label(name)? {
+            let label = schema.dimension_label(name)?;
+            Ok((label.cell_val_num()?, false))
+        } else {
+            let field = schema.field(name)?;
+            Ok((field.cell_val_num()?, field.nullability()?))
+        }
+    }
+
     pub fn data<S, T>(self, field: S, data: &'data T) -> TileDBResult<Self>
     where
         S: AsRef<str>,
@@ -200,12 +291,9 @@ impl<'data> WriteBuilder<'data> {
         let field_name = field.as_ref();
 
         let input = {
-            let schema = self.base().array().schema()?;
-            let schema_field = schema.field(field_name)?;
-            data.query_buffers(
-                schema_field.cell_val_num()?,
-                schema_field.nullability()?,
-            )?
+            let (cell_val_num, nullability) =
+                self.input_structure(field_name)?;
+            data.query_buffers(cell_val_num, nullability)?
         };
 
         self.buffers(field, input.into())
@@ -223,12 +311,9 @@ impl<'data> WriteBuilder<'data> {
         let field_name = field.as_ref();
 
         let input = {
-            let schema = self.base().array().schema()?;
-            let schema_field = schema.field(field_name)?;
-            data.typed_query_buffers(
-                schema_field.cell_val_num()?,
-                schema_field.nullability()?,
-            )?
+            let (cell_val_num, nullability) =
+                self.input_structure(field_name)?;
+            data.typed_query_buffers(cell_val_num, nullability)?
         };
 
         self.buffers(field, input)
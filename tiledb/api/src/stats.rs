@@ -44,8 +44,9 @@ impl Drop for RawStatsString {
     }
 }
 
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, Default, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct Metrics {
     pub timers: HashMap<String, f64>,
     pub counters: HashMap<String, u64>,
@@ -126,10 +127,32 @@ pub fn dump_json() -> Result<Option<Vec<Metrics>>, Error> {
     }
 }
 
+/// Parses the statistics of a single query, as returned by
+/// `tiledb_query_get_stats`, into `Metrics`.
+#[cfg(feature = "serde")]
+pub(crate) fn query_metrics(
+    raw: RawStatsString,
+) -> Result<Option<Metrics>, Error> {
+    use anyhow::anyhow;
+    let stats = unsafe { std::ffi::CStr::from_ptr(*raw) }.to_string_lossy();
+    if stats.trim().is_empty() {
+        return Ok(None);
+    }
+    serde_json::from_str::<Metrics>(&stats)
+        .map(Some)
+        .map_err(|e| Error::ToJson(anyhow!(e)))
+}
+
 #[cfg(test)]
 mod tests {
+    use std::sync::Mutex;
+
     use super::*;
 
+    /// Stats are enabled and disabled process-wide,
+    /// so tests which toggle them must not run concurrently.
+    static STATS_LOCK: Mutex<()> = Mutex::new(());
+
     struct ScopedStats {
         was_enabled: bool,
     }
@@ -152,6 +175,8 @@ mod tests {
 
     #[test]
     fn scoped_stats() -> anyhow::Result<()> {
+        let _lock = STATS_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+
         assert!(!is_enabled()?);
 
         {
@@ -163,4 +188,53 @@ mod tests {
 
         Ok(())
     }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn query_stats() -> anyhow::Result<()> {
+        use std::rc::Rc;
+
+        use crate::query::{
+            Query, QueryBuilder, ReadBuilder, ReadQuery, ReadQueryBuilder,
+        };
+        use crate::tests::prelude::*;
+
+        let _lock = STATS_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+
+        let mut array = TestArray::new(
+            "query_stats",
+            Rc::new(
+                crate::tests::examples::quickstart::Builder::new(
+                    ArrayType::Sparse,
+                )
+                .build(),
+            ),
+        )?;
+        {
+            let w = WriteBuilder::new(array.for_write()?)?
+                .data_typed("rows", &vec![1, 2, 3, 4])?
+                .data_typed("cols", &vec![1, 2, 3, 4])?
+                .data_typed("a", &vec![10, 11, 12, 13])?
+                .build();
+            w.submit()?;
+            w.finalize()?;
+        }
+
+        let metrics = {
+            let _stats = ScopedStats::new()?;
+
+            let mut q = ReadBuilder::new(array.for_read()?)?
+                .register_constructor::<_, Vec<i32>>("a", Default::default())?
+                .build();
+            let (a, _) = q.execute()?;
+            assert_eq!(4, a.len());
+
+            q.stats()?
+        };
+
+        let metrics = metrics.expect("Query did not have stats");
+        assert!(!metrics.timers.is_empty() || !metrics.counters.is_empty());
+
+        Ok(())
+    }
 }
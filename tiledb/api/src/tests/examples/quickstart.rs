@@ -1,6 +1,10 @@
 //! Provides methods for creating the "quickstart" example schema.
 
+use std::rc::Rc;
+
+use crate::array::ArrayOpener;
 use crate::tests::prelude::*;
+use crate::Result as TileDBResult;
 
 pub struct Builder {
     pub schema: SchemaData,
@@ -68,3 +72,70 @@ impl Builder {
         self.schema
     }
 }
+
+/// Creates a sparse quickstart array and writes the four cells
+/// `(1, 1) = 10`, `(2, 2) = 11`, `(3, 3) = 12` and `(4, 4) = 13`.
+///
+/// The cells are written at timestamp 1, so that a query which opens
+/// the array at any later timestamp is ordered after the write.
+pub fn sparse_array(name: &str) -> TileDBResult<TestArray> {
+    let array =
+        TestArray::new(name, Rc::new(Builder::new(ArrayType::Sparse).build()))?;
+
+    let w = ArrayOpener::new(&array.context, &array.uri, Mode::Write)?
+        .end_timestamp(1)?
+        .open()?;
+    let w = WriteBuilder::new(w)?
+        .data_typed("rows", &vec![1, 2, 3, 4])?
+        .data_typed("cols", &vec![1, 2, 3, 4])?
+        .data_typed("a", &vec![10, 11, 12, 13])?
+        .build();
+    w.submit()?;
+    w.finalize()?;
+
+    Ok(array)
+}
+
+/// Creates a dense quickstart array with `nrows` rows and `ncols` columns
+/// in tiles of `extent` by `extent` cells, and writes the index of each
+/// cell in row-major order as its value of `a`.
+///
+/// The cells are written at timestamp 1, as in [sparse_array].
+pub fn dense_array(
+    name: &str,
+    nrows: i32,
+    ncols: i32,
+    extent: i32,
+) -> TileDBResult<TestArray> {
+    let array = TestArray::new(
+        name,
+        Rc::new(
+            Builder::new(ArrayType::Dense)
+                .with_rows(DimensionConstraints::Int32(
+                    [1, nrows],
+                    Some(extent),
+                ))
+                .with_cols(DimensionConstraints::Int32(
+                    [1, ncols],
+                    Some(extent),
+                ))
+                .build(),
+        ),
+    )?;
+
+    let w = ArrayOpener::new(&array.context, &array.uri, Mode::Write)?
+        .end_timestamp(1)?
+        .open()?;
+    let w = WriteBuilder::new(w)?
+        .layout(QueryLayout::RowMajor)?
+        .start_subarray()?
+        .add_range("rows", &[1i32, nrows])?
+        .add_range("cols", &[1i32, ncols])?
+        .finish_subarray()?
+        .data_typed("a", &(0..nrows * ncols).collect::<Vec<i32>>())?
+        .build();
+    w.submit()?;
+    w.finalize()?;
+
+    Ok(array)
+}
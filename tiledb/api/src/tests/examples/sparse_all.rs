@@ -143,6 +143,8 @@ pub fn schema(params: Parameters) -> SchemaData {
     SchemaData {
         array_type: ArrayType::Sparse,
         domain: DomainData { dimension: dims },
+        current_domain: None,
+        dimension_labels: Default::default(),
         capacity: None,
         cell_order: None,
         tile_order: None,
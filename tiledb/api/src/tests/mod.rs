@@ -22,7 +22,49 @@ pub mod prelude {
         WriteQuery,
     };
 
+    pub use super::examples::quickstart::{dense_array, sparse_array};
     pub use super::examples::TestArray;
+
+    #[cfg(feature = "async")]
+    pub use super::futures::block_on;
+}
+
+/// Runs futures in tests without an async runtime.
+#[cfg(feature = "async")]
+pub mod futures {
+    use std::future::Future;
+    use std::sync::Arc;
+    use std::task::{Context, Poll, Wake, Waker};
+    use std::thread::{self, Thread};
+
+    struct ThreadWaker(Thread);
+
+    impl Wake for ThreadWaker {
+        fn wake(self: Arc<Self>) {
+            self.0.unpark();
+        }
+    }
+
+    /// Returns a waker which wakes up the current thread.
+    pub fn thread_waker() -> Waker {
+        Waker::from(Arc::new(ThreadWaker(thread::current())))
+    }
+
+    /// Runs a future to completion on the current thread.
+    pub fn block_on<F>(future: F) -> F::Output
+    where
+        F: Future,
+    {
+        let waker = thread_waker();
+        let mut cx = Context::from_waker(&waker);
+        let mut future = std::pin::pin!(future);
+        loop {
+            match future.as_mut().poll(&mut cx) {
+                Poll::Ready(output) => break output,
+                Poll::Pending => thread::park(),
+            }
+        }
+    }
 }
 
 #[cfg(any(test, feature = "proptest-strategies"))]
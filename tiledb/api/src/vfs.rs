@@ -1,3 +1,4 @@
+use std::io::{BufRead, Read, Seek, SeekFrom, Write};
 use std::ops::Deref;
 
 use crate::config::{Config, RawConfig};
@@ -291,6 +292,33 @@ impl VFS {
         })
     }
 
+    /// Opens the file at `uri` for use with the [std::io] traits.
+    ///
+    /// See [VFSFile].
+    pub fn open_file(&self, uri: &str, mode: VFSMode) -> TileDBResult<VFSFile> {
+        let size = match mode {
+            VFSMode::Read => self.file_size(uri)?,
+            VFSMode::Write => 0,
+            VFSMode::Append => {
+                if self.is_file(uri)? {
+                    self.file_size(uri)?
+                } else {
+                    0
+                }
+            }
+        };
+        let handle = self.open(uri, mode)?;
+        Ok(VFSFile {
+            handle,
+            mode,
+            size,
+            // writes always go to the end of the file
+            position: if mode == VFSMode::Read { 0 } else { size },
+            buffer: Vec::new(),
+            buffer_offset: 0,
+        })
+    }
+
     pub fn remove_file(&self, uri: &str) -> TileDBResult<()> {
         let c_vfs = *self.raw;
         let c_uri = cstring!(uri);
@@ -508,6 +536,218 @@ impl VFSHandle {
     }
 }
 
+/// A file opened through a [VFS] which implements the [std::io] traits
+/// [Read], [BufRead], [Write] and [Seek].
+///
+/// A file opened with [VFSMode::Read] can be read and seeked freely.
+/// Reads are buffered, so small reads do not each go to the VFS backend.
+///
+/// A file opened with [VFSMode::Write] or [VFSMode::Append] can only be
+/// written sequentially. Seeking is supported only to the current
+/// position, which is the end of the file.
+///
+/// The file is closed when it is dropped. Errors closing the file
+/// are ignored in that case; use [Self::close] to observe them.
+pub struct VFSFile {
+    handle: VFSHandle,
+    mode: VFSMode,
+    size: u64,
+    position: u64,
+    buffer: Vec<u8>,
+    buffer_offset: usize,
+}
+
+impl VFSFile {
+    /// Capacity of the read buffer.
+    const BUFFER_CAPACITY: usize = 64 * 1024;
+
+    /// Returns the underlying [VFSHandle].
+    pub fn handle(&self) -> &VFSHandle {
+        &self.handle
+    }
+
+    /// Returns the mode which this file was opened with.
+    pub fn mode(&self) -> VFSMode {
+        self.mode
+    }
+
+    /// Returns the size of the file in bytes.
+    ///
+    /// For a file opened for writing this includes the bytes
+    /// written through this file so far.
+    pub fn size(&self) -> u64 {
+        self.size
+    }
+
+    /// Returns the current position in the file.
+    pub fn position(&self) -> u64 {
+        self.position
+    }
+
+    /// Flushes any written data and closes the file.
+    pub fn close(self) -> TileDBResult<()> {
+        if self.mode != VFSMode::Read {
+            self.handle.sync()?;
+        }
+        self.handle.close()
+        // `Drop` will see that the handle is already closed
+    }
+
+    fn check_readable(&self) -> std::io::Result<()> {
+        if self.mode == VFSMode::Read {
+            Ok(())
+        } else {
+            Err(std::io::Error::new(
+                std::io::ErrorKind::Unsupported,
+                format!("Cannot read from file opened in {:?} mode", self.mode),
+            ))
+        }
+    }
+
+    fn check_writable(&self) -> std::io::Result<()> {
+        if self.mode == VFSMode::Read {
+            Err(std::io::Error::new(
+                std::io::ErrorKind::Unsupported,
+                "Cannot write to file opened in Read mode",
+            ))
+        } else {
+            Ok(())
+        }
+    }
+
+    fn discard_buffer(&mut self) {
+        self.buffer.clear();
+        self.buffer_offset = 0;
+    }
+}
+
+impl Read for VFSFile {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        self.check_readable()?;
+
+        // large reads with nothing buffered bypass the buffer
+        if self.buffer_offset >= self.buffer.len()
+            && buf.len() >= Self::BUFFER_CAPACITY
+        {
+            let remaining = self.size.saturating_sub(self.position);
+            let nbytes = std::cmp::min(buf.len() as u64, remaining) as usize;
+            if nbytes > 0 {
+                self.handle.read(self.position, &mut buf[0..nbytes])?;
+                self.position += nbytes as u64;
+            }
+            return Ok(nbytes);
+        }
+
+        let available = self.fill_buf()?;
+        let nbytes = std::cmp::min(available.len(), buf.len());
+        buf[0..nbytes].copy_from_slice(&available[0..nbytes]);
+        self.consume(nbytes);
+        Ok(nbytes)
+    }
+}
+
+impl BufRead for VFSFile {
+    fn fill_buf(&mut self) -> std::io::Result<&[u8]> {
+        self.check_readable()?;
+
+        if self.buffer_offset >= self.buffer.len() {
+            let remaining = self.size.saturating_sub(self.position);
+            let nbytes =
+                std::cmp::min(Self::BUFFER_CAPACITY as u64, remaining) as usize;
+            self.buffer.resize(nbytes, 0);
+            self.buffer_offset = 0;
+            if nbytes > 0 {
+                if let Err(e) =
+                    self.handle.read(self.position, &mut self.buffer)
+                {
+                    self.discard_buffer();
+                    return Err(e.into());
+                }
+            }
+        }
+        Ok(&self.buffer[self.buffer_offset..])
+    }
+
+    fn consume(&mut self, amt: usize) {
+        let amt = std::cmp::min(amt, self.buffer.len() - self.buffer_offset);
+        self.buffer_offset += amt;
+        self.position += amt as u64;
+    }
+}
+
+impl Write for VFSFile {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.check_writable()?;
+
+        self.handle.write(buf)?;
+        self.position += buf.len() as u64;
+        self.size = self.position;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        if self.mode != VFSMode::Read {
+            self.handle.sync()?;
+        }
+        Ok(())
+    }
+}
+
+impl Seek for VFSFile {
+    fn seek(&mut self, pos: SeekFrom) -> std::io::Result<u64> {
+        let target = match pos {
+            SeekFrom::Start(offset) => Some(offset),
+            SeekFrom::End(offset) => self.size.checked_add_signed(offset),
+            SeekFrom::Current(offset) => {
+                self.position.checked_add_signed(offset)
+            }
+        };
+        let Some(target) = target else {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                "Invalid seek to a negative or overflowing position",
+            ));
+        };
+
+        if self.mode != VFSMode::Read {
+            return if target == self.position {
+                Ok(target)
+            } else {
+                Err(std::io::Error::new(
+                    std::io::ErrorKind::Unsupported,
+                    format!(
+                        "Cannot seek in file opened in {:?} mode",
+                        self.mode
+                    ),
+                ))
+            };
+        }
+
+        // keep the buffer if the target is within it
+        let buffer_start = self.position - self.buffer_offset as u64;
+        let buffer_end = buffer_start + self.buffer.len() as u64;
+        if buffer_start <= target && target <= buffer_end {
+            self.buffer_offset = (target - buffer_start) as usize;
+        } else {
+            self.discard_buffer();
+        }
+        self.position = target;
+        Ok(target)
+    }
+
+    fn stream_position(&mut self) -> std::io::Result<u64> {
+        Ok(self.position)
+    }
+}
+
+impl Drop for VFSFile {
+    fn drop(&mut self) {
+        if let Ok(false) = self.handle.is_closed() {
+            let _ = self.handle.close();
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -647,6 +887,67 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn vfs_file_io() -> anyhow::Result<()> {
+        let ctx = Context::new()?;
+        let cfg = Config::new()?;
+        let vfs = VFS::new(&ctx, &cfg)?;
+
+        let test_uri = TestDirectory::new()?;
+        let file_uri = test_uri.with_path("vfs_test_file_io")?;
+
+        // Write some lines, then append some more.
+        {
+            let mut f = vfs.open_file(&file_uri, VFSMode::Write)?;
+            writeln!(f, "first line")?;
+            writeln!(f, "second line")?;
+            assert_eq!(23, f.stream_position()?);
+            assert_eq!(23, f.seek(SeekFrom::End(0))?);
+            assert!(f.seek(SeekFrom::Start(0)).is_err());
+            assert!(f.read(&mut [0u8; 4]).is_err());
+            f.close()?;
+        }
+        {
+            let mut f = vfs.open_file(&file_uri, VFSMode::Append)?;
+            assert_eq!(23, f.position());
+            f.write_all(b"third line")?;
+            f.flush()?;
+            // dropping closes the file
+        }
+        assert_eq!(33, vfs.file_size(&file_uri)?);
+
+        let mut f = vfs.open_file(&file_uri, VFSMode::Read)?;
+        assert_eq!(33, f.size());
+        assert!(f.write(b"nope").is_err());
+
+        let mut contents = String::new();
+        f.read_to_string(&mut contents)?;
+        assert_eq!("first line\nsecond line\nthird line", contents);
+        assert_eq!(0, f.read(&mut [0u8; 4])?);
+
+        // Seek within the buffered data and outside of it.
+        assert_eq!(6, f.seek(SeekFrom::Start(6))?);
+        let mut word = [0u8; 4];
+        f.read_exact(&mut word)?;
+        assert_eq!(b"line", &word);
+
+        assert_eq!(28, f.seek(SeekFrom::End(-5))?);
+        f.read_exact(&mut word)?;
+        assert_eq!(b" lin", &word);
+
+        assert_eq!(11, f.seek(SeekFrom::Current(-21))?);
+        let lines = f.by_ref().lines().collect::<std::io::Result<Vec<_>>>()?;
+        assert_eq!(vec!["second line", "third line"], lines);
+
+        assert!(f.seek(SeekFrom::Current(-34)).is_err());
+        assert_eq!(33, f.position());
+
+        f.rewind()?;
+        assert_eq!("first line", f.lines().next().unwrap()?);
+
+        Ok(())
+    }
+
     fn create_test_dir_structure(
         vfs: &VFS,
         test_uri: &TestDirectory,
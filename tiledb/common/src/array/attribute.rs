@@ -144,6 +144,18 @@ where
     }
 }
 
+impl IntoFillValue for &[bool] {
+    type PhysicalType = u8;
+
+    fn to_raw(&self) -> &[Self::PhysicalType] {
+        // SAFETY: `bool` has the same size and alignment as `u8`,
+        // and its values `false` and `true` are represented as 0 and 1
+        unsafe {
+            std::slice::from_raw_parts(self.as_ptr() as *const u8, self.len())
+        }
+    }
+}
+
 impl IntoFillValue for &str {
     type PhysicalType = u8;
 
@@ -1,5 +1,7 @@
 use std::convert::From;
 
+use thiserror::Error;
+
 use crate::datatype::Datatype;
 use crate::datatype::Error as DatatypeError;
 
@@ -23,10 +25,21 @@ pub enum Value {
     Int64Value(Vec<i64>),
     Float32Value(Vec<f32>),
     Float64Value(Vec<f64>),
+    /// Value of [Datatype::StringAscii] or [Datatype::StringUtf8] metadata.
+    StringValue(String),
+    /// Value of [Datatype::Blob] metadata.
+    BlobValue(Vec<u8>),
+    /// Value of [Datatype::Boolean] metadata.
+    BooleanValue(Vec<bool>),
 }
 
 /// Applies a generic expression to the interior of a `Value`.
 ///
+/// The interior of a `Value::StringValue` is a `String`, whose
+/// `$typename` is `u8`. Expressions which must apply to every variant
+/// are limited to those which are valid for both `Vec` and `String`,
+/// such as `len` and `as_ptr`.
+///
 /// # Examples
 /// ```
 /// use tiledb_common::metadata::Value;
@@ -85,13 +98,25 @@ macro_rules! metadata_value_go {
                 type $typename = f64;
                 $then
             }
+            Value::StringValue($vec) => {
+                type $typename = u8;
+                $then
+            }
+            Value::BlobValue($vec) => {
+                type $typename = u8;
+                $then
+            }
+            Value::BooleanValue($vec) => {
+                type $typename = bool;
+                $then
+            }
         }
     }};
 }
 pub use metadata_value_go;
 
 /// Applies a generic expression to the interiors of two `Value`s with matching variants,
-/// i.e. with the same physical data type. Typical usage is for comparing the insides of the two
+/// i.e. with the same physical data type, or both string, blob or boolean values. Typical usage is for comparing the insides of the two
 /// `Value`s.
 #[macro_export]
 macro_rules! value_cmp {
@@ -138,12 +163,26 @@ macro_rules! value_cmp {
                 type $typename = f64;
                 $same_type
             }
+            (Value::StringValue($lpat), Value::StringValue($rpat)) => {
+                type $typename = u8;
+                $same_type
+            }
+            (Value::BlobValue($lpat), Value::BlobValue($rpat)) => {
+                type $typename = u8;
+                $same_type
+            }
+            (Value::BooleanValue($lpat), Value::BooleanValue($rpat)) => {
+                type $typename = bool;
+                $same_type
+            }
             _ => $else,
         }
     }};
 }
 
 impl Value {
+    /// Returns the number of values, or the number of bytes
+    /// of a `StringValue`.
     pub fn len(&self) -> usize {
         metadata_value_go!(self, _DT, ref v, v.len())
     }
@@ -151,28 +190,165 @@ impl Value {
     pub fn is_empty(&self) -> bool {
         metadata_value_go!(self, _DT, ref v, v.is_empty())
     }
+
+    /// Returns the datatype of metadata with this value when
+    /// no other datatype is requested.
+    pub fn default_datatype(&self) -> Datatype {
+        match self {
+            Value::Int8Value(_) => Datatype::Int8,
+            Value::Int16Value(_) => Datatype::Int16,
+            Value::Int32Value(_) => Datatype::Int32,
+            Value::Int64Value(_) => Datatype::Int64,
+            Value::UInt8Value(_) => Datatype::UInt8,
+            Value::UInt16Value(_) => Datatype::UInt16,
+            Value::UInt32Value(_) => Datatype::UInt32,
+            Value::UInt64Value(_) => Datatype::UInt64,
+            Value::Float32Value(_) => Datatype::Float32,
+            Value::Float64Value(_) => Datatype::Float64,
+            Value::StringValue(_) => Datatype::StringUtf8,
+            Value::BlobValue(_) => Datatype::Blob,
+            Value::BooleanValue(_) => Datatype::Boolean,
+        }
+    }
+
+    fn variant_name(&self) -> &'static str {
+        match self {
+            Value::Int8Value(_) => "Int8Value",
+            Value::Int16Value(_) => "Int16Value",
+            Value::Int32Value(_) => "Int32Value",
+            Value::Int64Value(_) => "Int64Value",
+            Value::UInt8Value(_) => "UInt8Value",
+            Value::UInt16Value(_) => "UInt16Value",
+            Value::UInt32Value(_) => "UInt32Value",
+            Value::UInt64Value(_) => "UInt64Value",
+            Value::Float32Value(_) => "Float32Value",
+            Value::Float64Value(_) => "Float64Value",
+            Value::StringValue(_) => "StringValue",
+            Value::BlobValue(_) => "BlobValue",
+            Value::BooleanValue(_) => "BooleanValue",
+        }
+    }
+
+    /// Converts a value of the physical type of `datatype` into
+    /// the variant which represents `datatype`.
+    ///
+    /// String, blob and boolean metadata have the physical type `u8`,
+    /// and are converted from a `UInt8Value` into a `StringValue`,
+    /// `BlobValue` or `BooleanValue` respectively.
+    /// Invalid UTF-8 in string metadata is replaced with
+    /// `U+FFFD REPLACEMENT CHARACTER`.
+    /// Other values are returned unchanged.
+    pub fn into_logical(self, datatype: Datatype) -> Self {
+        match (datatype, self) {
+            (
+                Datatype::StringAscii | Datatype::StringUtf8,
+                Value::UInt8Value(bytes),
+            ) => Value::StringValue(match String::from_utf8(bytes) {
+                Ok(s) => s,
+                Err(e) => String::from_utf8_lossy(e.as_bytes()).into_owned(),
+            }),
+            (Datatype::Blob, Value::UInt8Value(bytes)) => {
+                Value::BlobValue(bytes)
+            }
+            (Datatype::Boolean, Value::UInt8Value(bytes)) => {
+                Value::BooleanValue(bytes.into_iter().map(|b| b != 0).collect())
+            }
+            (_, value) => value,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Eq, Error, PartialEq)]
+pub enum FromValueError {
+    #[error("Cannot convert {found} into {expected}")]
+    UnexpectedType {
+        expected: &'static str,
+        found: &'static str,
+    },
+    #[error("Expected a single value, found {0} values")]
+    UnexpectedLength(usize),
 }
 
 macro_rules! metadata_value_impl {
-    ($ty:ty, $constructor:expr) => {
+    ($ty:ty, $variant:ident $(, $alt:ident)*) => {
         impl From<Vec<$ty>> for Value {
             fn from(vec: Vec<$ty>) -> Self {
-                $constructor(vec)
+                Value::$variant(vec)
+            }
+        }
+
+        impl From<$ty> for Value {
+            fn from(value: $ty) -> Self {
+                Value::$variant(vec![value])
+            }
+        }
+
+        impl TryFrom<Value> for Vec<$ty> {
+            type Error = FromValueError;
+
+            fn try_from(value: Value) -> Result<Self, Self::Error> {
+                match value {
+                    Value::$variant(vec) $(| Value::$alt(vec))* => Ok(vec),
+                    other => Err(FromValueError::UnexpectedType {
+                        expected: std::any::type_name::<Self>(),
+                        found: other.variant_name(),
+                    }),
+                }
+            }
+        }
+
+        impl TryFrom<Value> for $ty {
+            type Error = FromValueError;
+
+            fn try_from(value: Value) -> Result<Self, Self::Error> {
+                let vec = Vec::<$ty>::try_from(value)?;
+                if vec.len() == 1 {
+                    Ok(vec[0])
+                } else {
+                    Err(FromValueError::UnexpectedLength(vec.len()))
+                }
             }
         }
     };
 }
 
-metadata_value_impl!(i8, Value::Int8Value);
-metadata_value_impl!(i16, Value::Int16Value);
-metadata_value_impl!(i32, Value::Int32Value);
-metadata_value_impl!(i64, Value::Int64Value);
-metadata_value_impl!(u8, Value::UInt8Value);
-metadata_value_impl!(u16, Value::UInt16Value);
-metadata_value_impl!(u32, Value::UInt32Value);
-metadata_value_impl!(u64, Value::UInt64Value);
-metadata_value_impl!(f32, Value::Float32Value);
-metadata_value_impl!(f64, Value::Float64Value);
+metadata_value_impl!(i8, Int8Value);
+metadata_value_impl!(i16, Int16Value);
+metadata_value_impl!(i32, Int32Value);
+metadata_value_impl!(i64, Int64Value);
+metadata_value_impl!(u8, UInt8Value, BlobValue);
+metadata_value_impl!(u16, UInt16Value);
+metadata_value_impl!(u32, UInt32Value);
+metadata_value_impl!(u64, UInt64Value);
+metadata_value_impl!(f32, Float32Value);
+metadata_value_impl!(f64, Float64Value);
+metadata_value_impl!(bool, BooleanValue);
+
+impl From<String> for Value {
+    fn from(value: String) -> Self {
+        Value::StringValue(value)
+    }
+}
+
+impl From<&str> for Value {
+    fn from(value: &str) -> Self {
+        Value::StringValue(value.to_owned())
+    }
+}
+
+impl TryFrom<Value> for String {
+    type Error = FromValueError;
+
+    fn try_from(value: Value) -> Result<Self, Self::Error> {
+        match value {
+            Value::StringValue(s) => Ok(s),
+            other => Err(FromValueError::UnexpectedType {
+                expected: std::any::type_name::<Self>(),
+                found: other.variant_name(),
+            }),
+        }
+    }
+}
 
 #[derive(Clone, Debug, PartialEq)]
 pub struct Metadata {
@@ -182,6 +358,12 @@ pub struct Metadata {
 }
 
 impl Metadata {
+    /// Returns metadata with key `key` whose values `vec` have
+    /// the physical type of `datatype`.
+    ///
+    /// Values of string, blob and boolean types are converted as in
+    /// [Value::into_logical], so that the result is equal to the
+    /// metadata which is read back after it is written.
     pub fn new<T>(
         key: String,
         datatype: Datatype,
@@ -199,9 +381,39 @@ impl Metadata {
         Ok(Metadata {
             key,
             datatype,
-            value: Value::from(vec),
+            value: Value::from(vec).into_logical(datatype),
         })
     }
+
+    /// Returns [Datatype::StringUtf8] metadata with key `key`.
+    pub fn string<S>(key: String, value: S) -> Self
+    where
+        S: Into<String>,
+    {
+        Metadata {
+            key,
+            datatype: Datatype::StringUtf8,
+            value: Value::StringValue(value.into()),
+        }
+    }
+
+    /// Returns [Datatype::Blob] metadata with key `key`.
+    pub fn blob(key: String, value: Vec<u8>) -> Self {
+        Metadata {
+            key,
+            datatype: Datatype::Blob,
+            value: Value::BlobValue(value),
+        }
+    }
+
+    /// Returns [Datatype::Boolean] metadata with key `key`.
+    pub fn boolean(key: String, value: Vec<bool>) -> Self {
+        Metadata {
+            key,
+            datatype: Datatype::Boolean,
+            value: Value::BooleanValue(value),
+        }
+    }
 }
 
 #[cfg(any(test, feature = "proptest-strategies"))]
@@ -244,11 +456,36 @@ pub mod strategy {
             params
                 .datatype
                 .prop_flat_map(move |dt| {
-                    let value_strat = physical_type_go!(dt, DT, {
-                        vec(any::<DT>(), params.value_length.clone())
-                            .prop_map(Value::from)
-                            .boxed()
-                    });
+                    let value_length = params.value_length.clone();
+                    let value_strat = match dt {
+                        Datatype::StringAscii => {
+                            vec(0x20u8..0x7f, value_length)
+                                .prop_map(|v| {
+                                    Value::StringValue(
+                                        String::from_utf8(v).unwrap(),
+                                    )
+                                })
+                                .boxed()
+                        }
+                        Datatype::StringUtf8 => {
+                            vec(any::<char>(), value_length)
+                                .prop_map(|v| {
+                                    Value::StringValue(String::from_iter(v))
+                                })
+                                .boxed()
+                        }
+                        Datatype::Blob => vec(any::<u8>(), value_length)
+                            .prop_map(Value::BlobValue)
+                            .boxed(),
+                        Datatype::Boolean => vec(any::<bool>(), value_length)
+                            .prop_map(Value::BooleanValue)
+                            .boxed(),
+                        _ => physical_type_go!(dt, DT, {
+                            vec(any::<DT>(), value_length)
+                                .prop_map(Value::from)
+                                .boxed()
+                        }),
+                    };
                     (params.key.clone(), Just(dt), value_strat)
                 })
                 .prop_map(|(key, datatype, value)| Metadata {
@@ -266,20 +503,129 @@ mod tests {
     use super::*;
     use proptest::prelude::*;
 
+    /// Returns whether metadata of `datatype` is represented by
+    /// a `Value` variant other than that of its physical type.
+    fn is_logical_value(datatype: Datatype) -> bool {
+        matches!(
+            datatype,
+            Datatype::StringAscii
+                | Datatype::StringUtf8
+                | Datatype::Blob
+                | Datatype::Boolean
+        )
+    }
+
     fn do_value_cmp(m1: Metadata, m2: Metadata) {
-        if m1.datatype.same_physical_type(&m2.datatype) {
+        let same_variant =
+            if is_logical_value(m1.datatype) || is_logical_value(m2.datatype) {
+                m1.datatype == m2.datatype
+                    || (matches!(
+                        m1.datatype,
+                        Datatype::StringAscii | Datatype::StringUtf8
+                    ) && matches!(
+                        m2.datatype,
+                        Datatype::StringAscii | Datatype::StringUtf8
+                    ))
+            } else {
+                m1.datatype.same_physical_type(&m2.datatype)
+            };
+        if same_variant {
             value_cmp!(&m1.value, &m2.value, _DT, _, _,
                 (),
-                unreachable!("Non-matching `Value` variants for same physical type: {:?} and {:?}",
+                unreachable!("Non-matching `Value` variants for same type: {:?} and {:?}",
                     m1, m2));
         } else {
             value_cmp!(&m1.value, &m2.value, _DT, _, _,
-                unreachable!("Matching `Value` variants for different physical type: {:?} and {:?}",
+                unreachable!("Matching `Value` variants for different type: {:?} and {:?}",
                     m1, m2),
                 ());
         }
     }
 
+    #[test]
+    fn into_logical() {
+        assert_eq!(
+            Value::StringValue("foo".to_owned()),
+            Value::UInt8Value(b"foo".to_vec())
+                .into_logical(Datatype::StringUtf8)
+        );
+        assert_eq!(
+            Value::StringValue("f\u{FFFD}o".to_owned()),
+            Value::UInt8Value(vec![b'f', 0xff, b'o'])
+                .into_logical(Datatype::StringAscii)
+        );
+        assert_eq!(
+            Value::BlobValue(vec![0, 1, 2]),
+            Value::UInt8Value(vec![0, 1, 2]).into_logical(Datatype::Blob)
+        );
+        assert_eq!(
+            Value::BooleanValue(vec![false, true, true]),
+            Value::UInt8Value(vec![0, 1, 2]).into_logical(Datatype::Boolean)
+        );
+        assert_eq!(
+            Value::UInt8Value(vec![0, 1, 2]),
+            Value::UInt8Value(vec![0, 1, 2]).into_logical(Datatype::UInt8)
+        );
+        assert_eq!(
+            Value::Int8Value(vec![1]),
+            Value::Int8Value(vec![1]).into_logical(Datatype::Char)
+        );
+
+        let m = Metadata::new(
+            "key".to_owned(),
+            Datatype::StringAscii,
+            b"abc".to_vec(),
+        )
+        .unwrap();
+        assert_eq!(
+            Metadata::string("key".to_owned(), "abc"),
+            Metadata {
+                datatype: Datatype::StringUtf8,
+                ..m.clone()
+            }
+        );
+        assert_eq!(3, m.value.len());
+    }
+
+    #[test]
+    fn try_from_value() {
+        assert_eq!(
+            Ok(vec![1i32, 2]),
+            Vec::<i32>::try_from(Value::from(vec![1i32, 2]))
+        );
+        assert_eq!(Ok(7u64), u64::try_from(Value::from(7u64)));
+        assert_eq!(Ok(true), bool::try_from(Value::from(true)));
+        assert_eq!(Ok("foo".to_owned()), String::try_from(Value::from("foo")));
+        assert_eq!(
+            Ok(vec![0u8, 1]),
+            Vec::<u8>::try_from(Value::BlobValue(vec![0, 1]))
+        );
+        assert_eq!(
+            Err(FromValueError::UnexpectedLength(2)),
+            i32::try_from(Value::from(vec![1i32, 2]))
+        );
+        assert!(matches!(
+            f64::try_from(Value::from(1.0f32)),
+            Err(FromValueError::UnexpectedType {
+                found: "Float32Value",
+                ..
+            })
+        ));
+        assert!(String::try_from(Value::BlobValue(vec![b'a'])).is_err());
+    }
+
+    proptest! {
+        #[test]
+        fn default_datatype(m in any::<Metadata>()) {
+            let datatype = m.value.default_datatype();
+            if matches!(m.datatype, Datatype::StringAscii | Datatype::StringUtf8) {
+                assert_eq!(Datatype::StringUtf8, datatype);
+            } else {
+                assert!(m.datatype.same_physical_type(&datatype));
+            }
+        }
+    }
+
     proptest! {
         #[test]
         fn value_cmp((m1, m2) in (any::<Metadata>(), any::<Metadata>())) {
@@ -1396,6 +1396,9 @@ pub enum Range {
     Var(VarValueRange),
 }
 
+#[cfg(feature = "option-subset")]
+tiledb_utils::option_subset_partialeq!(Range);
+
 impl Range {
     pub fn cell_val_num(&self) -> CellValNum {
         match self {
@@ -0,0 +1,74 @@
+#[cfg(feature = "option-subset")]
+use tiledb_utils::option::OptionSubset;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use tiledb_common::range::Range;
+
+/// Encapsulation of data needed to construct an NDRectangle
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "option-subset", derive(OptionSubset))]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub struct NDRectangleData {
+    /// The range of each dimension, in the same order as the
+    /// dimensions of the array domain.
+    pub dimension: Vec<Range>,
+}
+
+/// Encapsulation of data needed to construct a CurrentDomain
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "option-subset", derive(OptionSubset))]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub enum CurrentDomainData {
+    NDRectangle(NDRectangleData),
+}
+
+impl CurrentDomainData {
+    /// Returns the range of each dimension of this current domain.
+    pub fn ranges(&self) -> &[Range] {
+        match self {
+            Self::NDRectangle(ndr) => &ndr.dimension,
+        }
+    }
+}
+
+impl From<NDRectangleData> for CurrentDomainData {
+    fn from(value: NDRectangleData) -> Self {
+        Self::NDRectangle(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tiledb_common::range::{SingleValueRange, VarValueRange};
+
+    use super::*;
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_roundtrip() {
+        let current_domain = CurrentDomainData::from(NDRectangleData {
+            dimension: vec![
+                Range::Single(SingleValueRange::Int32(1, 100)),
+                Range::Var(VarValueRange::from(("a", "m"))),
+            ],
+        });
+        assert_eq!(
+            current_domain,
+            crate::test::serde::roundtrip(&current_domain).unwrap()
+        );
+    }
+
+    #[test]
+    fn ranges() {
+        let ranges = vec![
+            Range::Single(SingleValueRange::UInt64(0, 10)),
+            Range::Single(SingleValueRange::Float64(-1.0, 1.0)),
+        ];
+        let current_domain = CurrentDomainData::from(NDRectangleData {
+            dimension: ranges.clone(),
+        });
+        assert_eq!(ranges, current_domain.ranges());
+    }
+}
@@ -0,0 +1,25 @@
+#[cfg(feature = "option-subset")]
+use tiledb_utils::option::OptionSubset;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use tiledb_common::array::DataOrder;
+use tiledb_common::datatype::Datatype;
+use tiledb_common::filter::FilterData;
+
+/// Encapsulation of data needed to construct a dimension label
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "option-subset", derive(OptionSubset))]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub struct DimensionLabelData {
+    pub name: String,
+    /// Index of the dimension which this label applies to.
+    pub dimension: usize,
+    pub order: DataOrder,
+    pub datatype: Datatype,
+    /// Filters applied to the label values.
+    /// This cannot be read back from a schema and is always `None`
+    /// for data which was obtained from a schema.
+    pub filters: Option<Vec<FilterData>>,
+}
@@ -1,11 +1,15 @@
 pub mod attribute;
+pub mod current_domain;
 pub mod dimension;
+pub mod dimension_label;
 pub mod domain;
 pub mod enumeration;
 pub mod schema;
 
 pub use attribute::AttributeData;
+pub use current_domain::{CurrentDomainData, NDRectangleData};
 pub use dimension::DimensionData;
+pub use dimension_label::DimensionLabelData;
 pub use domain::DomainData;
 pub use enumeration::EnumerationData;
 pub use schema::SchemaData;
@@ -12,7 +12,10 @@ use tiledb_common::datatype::Datatype;
 use tiledb_common::filter::FilterData;
 use tiledb_common::key::LookupKey;
 
-use crate::array::{AttributeData, DimensionData, DomainData, EnumerationData};
+use crate::array::{
+    AttributeData, CurrentDomainData, DimensionData, DimensionLabelData,
+    DomainData, EnumerationData,
+};
 
 /// Encapsulation of data needed to construct a Schema
 #[derive(Clone, Default, Debug, PartialEq)]
@@ -21,6 +24,11 @@ use crate::array::{AttributeData, DimensionData, DomainData, EnumerationData};
 pub struct SchemaData {
     pub array_type: ArrayType,
     pub domain: DomainData,
+    /// The region of `domain` which can currently be written to,
+    /// or `None` if the whole domain can be written to.
+    pub current_domain: Option<CurrentDomainData>,
+    /// Labels which can be used to query `domain` by label value.
+    pub dimension_labels: Vec<DimensionLabelData>,
     pub capacity: Option<u64>,
     pub cell_order: Option<CellOrder>,
     pub tile_order: Option<TileOrder>,
@@ -260,6 +260,8 @@ fn prop_schema_for_domain(
                 SchemaData {
                     array_type,
                     domain,
+                    current_domain: None,
+                    dimension_labels: Vec::new(),
                     capacity: Some(capacity),
                     cell_order: Some(cell_order),
                     tile_order: Some(tile_order),
@@ -435,6 +437,8 @@ impl ValueTree for SchemaValueTree {
         SchemaData {
             array_type: self.array_type,
             domain: self.domain.current(),
+            current_domain: None,
+            dimension_labels: Vec::new(),
             capacity: self.capacity.current(),
             cell_order: self.cell_order.current(),
             tile_order: self.tile_order.current(),
@@ -202,11 +202,17 @@ where
     type Intermediate = ();
     type Final = (Option<PhysicalValue>, Q::Final);
 
-    fn step(
+    fn prepare_step(&mut self) -> TileDBResult<()> {
+        aggregate_physical_value_query_go!(self, _DT, reader, {
+            reader.prepare_step()
+        })
+    }
+
+    fn complete_step(
         &mut self,
     ) -> TileDBResult<ReadStepOutput<Self::Intermediate, Self::Final>> {
         aggregate_physical_value_query_go!(self, _DT, reader, {
-            let step_result = reader.step()?;
+            let step_result = reader.complete_step()?;
             let enum_result = match step_result {
                 ReadStepOutput::Final((return_val, base_q)) => {
                     ReadStepOutput::Final((
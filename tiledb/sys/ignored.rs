@@ -219,11 +219,6 @@ extern "C" {
         error: *mut *mut tiledb_error_t,
     ) -> capi_return_t;
 
-    // Ignoring the async tasks as those likely won't be useful in Rust land given
-    // they don't at all map to async Rust.
-
-    pub fn tiledb_ctx_cancel_tasks(ctx: *mut tiledb_ctx_t) -> capi_return_t;
-
     // Filter types are not part of the public Rust API and the filter API's types
     // already have their Debug traits implemented.
 
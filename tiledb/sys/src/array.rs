@@ -32,6 +32,12 @@ extern "C" {
         is_open: *mut i32,
     ) -> capi_return_t;
 
+    pub fn tiledb_array_get_query_type(
+        ctx: *mut tiledb_ctx_t,
+        array: *mut tiledb_array_t,
+        query_type: *mut tiledb_query_type_t,
+    ) -> capi_return_t;
+
     pub fn tiledb_array_reopen(
         ctx: *mut tiledb_ctx_t,
         array: *mut tiledb_array_t,
@@ -229,6 +235,20 @@ extern "C" {
         uri: *const ::std::os::raw::c_char,
     ) -> capi_return_t;
 
+    pub fn tiledb_array_delete_fragments_v2(
+        ctx: *mut tiledb_ctx_t,
+        uri_str: *const ::std::os::raw::c_char,
+        timestamp_start: u64,
+        timestamp_end: u64,
+    ) -> capi_return_t;
+
+    pub fn tiledb_array_delete_fragments_list(
+        ctx: *mut tiledb_ctx_t,
+        uri_str: *const ::std::os::raw::c_char,
+        fragment_uris: *mut *const ::std::os::raw::c_char,
+        num_fragments: usize,
+    ) -> capi_return_t;
+
     pub fn tiledb_array_get_enumeration(
         ctx: *mut tiledb_ctx_t,
         array: *const tiledb_array_t,
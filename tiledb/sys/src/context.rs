@@ -11,6 +11,8 @@ extern "C" {
 
     pub fn tiledb_ctx_free(ctx: *mut *mut tiledb_ctx_t);
 
+    pub fn tiledb_ctx_cancel_tasks(ctx: *mut tiledb_ctx_t) -> capi_return_t;
+
     pub fn tiledb_ctx_get_stats(
         ctx: *mut tiledb_ctx_t,
         stats_json: *mut *mut ::std::os::raw::c_char,
@@ -0,0 +1,39 @@
+use crate::capi_enum::tiledb_current_domain_type_t;
+use crate::types::{
+    capi_return_t, tiledb_ctx_t, tiledb_current_domain_t, tiledb_ndrectangle_t,
+};
+
+extern "C" {
+    pub fn tiledb_current_domain_create(
+        ctx: *mut tiledb_ctx_t,
+        current_domain: *mut *mut tiledb_current_domain_t,
+    ) -> capi_return_t;
+
+    pub fn tiledb_current_domain_free(
+        current_domain: *mut *mut tiledb_current_domain_t,
+    ) -> capi_return_t;
+
+    pub fn tiledb_current_domain_set_ndrectangle(
+        ctx: *mut tiledb_ctx_t,
+        current_domain: *mut tiledb_current_domain_t,
+        ndr: *mut tiledb_ndrectangle_t,
+    ) -> capi_return_t;
+
+    pub fn tiledb_current_domain_get_ndrectangle(
+        ctx: *mut tiledb_ctx_t,
+        current_domain: *mut tiledb_current_domain_t,
+        ndr: *mut *mut tiledb_ndrectangle_t,
+    ) -> capi_return_t;
+
+    pub fn tiledb_current_domain_get_is_empty(
+        ctx: *mut tiledb_ctx_t,
+        current_domain: *mut tiledb_current_domain_t,
+        is_empty: *mut u32,
+    ) -> capi_return_t;
+
+    pub fn tiledb_current_domain_get_type(
+        ctx: *mut tiledb_ctx_t,
+        current_domain: *mut tiledb_current_domain_t,
+        type_: *mut tiledb_current_domain_type_t,
+    ) -> capi_return_t;
+}
@@ -0,0 +1,104 @@
+use crate::capi_enum::{tiledb_data_order_t, tiledb_datatype_t};
+use crate::types::{
+    capi_return_t, tiledb_array_schema_t, tiledb_ctx_t,
+    tiledb_dimension_label_t, tiledb_filter_list_t,
+};
+
+extern "C" {
+    pub fn tiledb_dimension_label_free(
+        dim_label: *mut *mut tiledb_dimension_label_t,
+    );
+
+    pub fn tiledb_dimension_label_get_dimension_index(
+        ctx: *mut tiledb_ctx_t,
+        dim_label: *mut tiledb_dimension_label_t,
+        dim_index: *mut u32,
+    ) -> capi_return_t;
+
+    pub fn tiledb_dimension_label_get_label_attr_name(
+        ctx: *mut tiledb_ctx_t,
+        dim_label: *mut tiledb_dimension_label_t,
+        label_attr_name: *mut *const ::std::os::raw::c_char,
+    ) -> capi_return_t;
+
+    pub fn tiledb_dimension_label_get_label_cell_val_num(
+        ctx: *mut tiledb_ctx_t,
+        dim_label: *mut tiledb_dimension_label_t,
+        label_cell_val_num: *mut u32,
+    ) -> capi_return_t;
+
+    pub fn tiledb_dimension_label_get_label_order(
+        ctx: *mut tiledb_ctx_t,
+        dim_label: *mut tiledb_dimension_label_t,
+        label_order: *mut tiledb_data_order_t,
+    ) -> capi_return_t;
+
+    pub fn tiledb_dimension_label_get_label_type(
+        ctx: *mut tiledb_ctx_t,
+        dim_label: *mut tiledb_dimension_label_t,
+        label_type: *mut tiledb_datatype_t,
+    ) -> capi_return_t;
+
+    pub fn tiledb_dimension_label_get_name(
+        ctx: *mut tiledb_ctx_t,
+        dim_label: *mut tiledb_dimension_label_t,
+        name: *mut *const ::std::os::raw::c_char,
+    ) -> capi_return_t;
+
+    pub fn tiledb_dimension_label_get_uri(
+        ctx: *mut tiledb_ctx_t,
+        dim_label: *mut tiledb_dimension_label_t,
+        uri: *mut *const ::std::os::raw::c_char,
+    ) -> capi_return_t;
+
+    pub fn tiledb_array_schema_add_dimension_label(
+        ctx: *mut tiledb_ctx_t,
+        array_schema: *mut tiledb_array_schema_t,
+        dim_index: u32,
+        name: *const ::std::os::raw::c_char,
+        label_order: tiledb_data_order_t,
+        label_type: tiledb_datatype_t,
+    ) -> capi_return_t;
+
+    pub fn tiledb_array_schema_get_dimension_label_from_name(
+        ctx: *mut tiledb_ctx_t,
+        array_schema: *mut tiledb_array_schema_t,
+        label_name: *const ::std::os::raw::c_char,
+        dim_label: *mut *mut tiledb_dimension_label_t,
+    ) -> capi_return_t;
+
+    pub fn tiledb_array_schema_get_dimension_label_from_index(
+        ctx: *mut tiledb_ctx_t,
+        array_schema: *mut tiledb_array_schema_t,
+        dim_label_index: u64,
+        dim_label: *mut *mut tiledb_dimension_label_t,
+    ) -> capi_return_t;
+
+    pub fn tiledb_array_schema_get_dimension_label_num(
+        ctx: *mut tiledb_ctx_t,
+        array_schema: *mut tiledb_array_schema_t,
+        dim_label_num: *mut u64,
+    ) -> capi_return_t;
+
+    pub fn tiledb_array_schema_has_dimension_label(
+        ctx: *mut tiledb_ctx_t,
+        array_schema: *const tiledb_array_schema_t,
+        name: *const ::std::os::raw::c_char,
+        has_dim_label: *mut i32,
+    ) -> capi_return_t;
+
+    pub fn tiledb_array_schema_set_dimension_label_filter_list(
+        ctx: *mut tiledb_ctx_t,
+        array_schema: *mut tiledb_array_schema_t,
+        label_name: *const ::std::os::raw::c_char,
+        filter_list: *mut tiledb_filter_list_t,
+    ) -> capi_return_t;
+
+    pub fn tiledb_array_schema_set_dimension_label_tile_extent(
+        ctx: *mut tiledb_ctx_t,
+        array_schema: *mut tiledb_array_schema_t,
+        label_name: *const ::std::os::raw::c_char,
+        type_: tiledb_datatype_t,
+        tile_extent: *const ::std::os::raw::c_void,
+    ) -> capi_return_t;
+}
@@ -7,9 +7,12 @@ mod array;
 mod array_type;
 mod attribute;
 mod config;
+mod consolidation_plan;
 mod constants;
 mod context;
+mod current_domain;
 mod dimension;
+mod dimension_label;
 mod domain;
 mod encryption;
 mod enumeration;
@@ -21,6 +24,7 @@ mod filter_option;
 mod filter_type;
 mod fragment_info;
 mod group;
+mod ndrectangle;
 mod object;
 mod query;
 mod query_condition;
@@ -41,9 +45,12 @@ pub use array_type::*;
 pub use attribute::*;
 pub use capi_enum::*;
 pub use config::*;
+pub use consolidation_plan::*;
 pub use constants::*;
 pub use context::*;
+pub use current_domain::*;
 pub use dimension::*;
+pub use dimension_label::*;
 pub use domain::*;
 pub use encryption::*;
 pub use enumeration::*;
@@ -55,6 +62,7 @@ pub use filter_option::*;
 pub use filter_type::*;
 pub use fragment_info::*;
 pub use group::*;
+pub use ndrectangle::*;
 pub use object::*;
 pub use query::*;
 pub use query_condition::*;
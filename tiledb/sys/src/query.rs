@@ -106,6 +106,14 @@ extern "C" {
         cond: *const tiledb_query_condition_t,
     ) -> i32;
 
+    pub fn tiledb_query_add_update_value(
+        ctx: *mut tiledb_ctx_t,
+        query: *mut tiledb_query_t,
+        field_name: *const ::std::os::raw::c_char,
+        update_value: *const ::std::os::raw::c_void,
+        update_value_size: u64,
+    ) -> i32;
+
     pub fn tiledb_query_finalize(
         ctx: *mut tiledb_ctx_t,
         query: *mut tiledb_query_t,
@@ -41,6 +41,12 @@ extern "C" {
         combined_cond: *mut *mut tiledb_query_condition_t,
     ) -> i32;
 
+    pub fn tiledb_query_condition_set_use_enumeration(
+        ctx: *mut tiledb_ctx_t,
+        cond: *const tiledb_query_condition_t,
+        use_enumeration: ::std::os::raw::c_int,
+    ) -> capi_return_t;
+
     pub fn tiledb_query_condition_negate(
         ctx: *mut tiledb_ctx_t,
         cond: *const tiledb_query_condition_t,
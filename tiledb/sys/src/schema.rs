@@ -3,7 +3,8 @@ use crate::capi_enum::{
 };
 use crate::types::{
     capi_return_t, tiledb_array_schema_t, tiledb_attribute_t, tiledb_ctx_t,
-    tiledb_domain_t, tiledb_enumeration_t, tiledb_filter_list_t,
+    tiledb_current_domain_t, tiledb_domain_t, tiledb_enumeration_t,
+    tiledb_filter_list_t,
 };
 
 extern "C" {
@@ -165,6 +166,18 @@ extern "C" {
         domain: *mut *mut tiledb_domain_t,
     ) -> capi_return_t;
 
+    pub fn tiledb_array_schema_set_current_domain(
+        ctx: *mut tiledb_ctx_t,
+        array_schema: *mut tiledb_array_schema_t,
+        current_domain: *mut tiledb_current_domain_t,
+    ) -> capi_return_t;
+
+    pub fn tiledb_array_schema_get_current_domain(
+        ctx: *mut tiledb_ctx_t,
+        array_schema: *mut tiledb_array_schema_t,
+        current_domain: *mut *mut tiledb_current_domain_t,
+    ) -> capi_return_t;
+
     pub fn tiledb_array_schema_get_tile_order(
         ctx: *mut tiledb_ctx_t,
         array_schema: *const tiledb_array_schema_t,
@@ -110,4 +110,72 @@ extern "C" {
         start: *mut ::std::os::raw::c_void,
         end: *mut ::std::os::raw::c_void,
     ) -> capi_return_t;
+
+    pub fn tiledb_subarray_add_label_range(
+        ctx: *mut tiledb_ctx_t,
+        subarray: *mut tiledb_subarray_t,
+        label_name: *const ::std::os::raw::c_char,
+        start: *const ::std::os::raw::c_void,
+        end: *const ::std::os::raw::c_void,
+        stride: *const ::std::os::raw::c_void,
+    ) -> capi_return_t;
+
+    pub fn tiledb_subarray_add_label_range_var(
+        ctx: *mut tiledb_ctx_t,
+        subarray: *mut tiledb_subarray_t,
+        label_name: *const ::std::os::raw::c_char,
+        start: *const ::std::os::raw::c_void,
+        start_size: u64,
+        end: *const ::std::os::raw::c_void,
+        end_size: u64,
+    ) -> capi_return_t;
+
+    pub fn tiledb_subarray_get_label_name(
+        ctx: *mut tiledb_ctx_t,
+        subarray: *mut tiledb_subarray_t,
+        dim_idx: u32,
+        label_name: *mut *const ::std::os::raw::c_char,
+    ) -> capi_return_t;
+
+    pub fn tiledb_subarray_get_label_range_num(
+        ctx: *mut tiledb_ctx_t,
+        subarray: *const tiledb_subarray_t,
+        label_name: *const ::std::os::raw::c_char,
+        range_num: *mut u64,
+    ) -> capi_return_t;
+
+    pub fn tiledb_subarray_get_label_range(
+        ctx: *mut tiledb_ctx_t,
+        subarray: *const tiledb_subarray_t,
+        label_name: *const ::std::os::raw::c_char,
+        range_idx: u64,
+        start: *mut *const ::std::os::raw::c_void,
+        end: *mut *const ::std::os::raw::c_void,
+        stride: *mut *const ::std::os::raw::c_void,
+    ) -> capi_return_t;
+
+    pub fn tiledb_subarray_get_label_range_var_size(
+        ctx: *mut tiledb_ctx_t,
+        subarray: *const tiledb_subarray_t,
+        label_name: *const ::std::os::raw::c_char,
+        range_idx: u64,
+        start_size: *mut u64,
+        end_size: *mut u64,
+    ) -> capi_return_t;
+
+    pub fn tiledb_subarray_get_label_range_var(
+        ctx: *mut tiledb_ctx_t,
+        subarray: *const tiledb_subarray_t,
+        label_name: *const ::std::os::raw::c_char,
+        range_idx: u64,
+        start: *mut ::std::os::raw::c_void,
+        end: *mut ::std::os::raw::c_void,
+    ) -> capi_return_t;
+
+    pub fn tiledb_subarray_has_label_ranges(
+        ctx: *mut tiledb_ctx_t,
+        subarray: *const tiledb_subarray_t,
+        dim_idx: u32,
+        has_label_range: *mut i32,
+    ) -> capi_return_t;
 }